@@ -0,0 +1,46 @@
+//! Generates the tonic server and client of `hospitopt.v1.Dispatch`.
+//!
+//! The service is declared here rather than compiled from
+//! `proto/hospitopt/v1/dispatch.proto`, so building needs no `protoc`. Keep
+//! the methods in step with the proto file; `tests/grpc_proto.rs` checks them.
+
+use tonic_build::manual::{Builder, Method, MethodBuilder, Service};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let service = Service::builder()
+        .name("Dispatch")
+        .package("hospitopt.v1")
+        .method(method("ListHospitals", "ListHospitalsResponse").build())
+        .method(method("ListTriageBuckets", "ListTriageBucketsResponse").build())
+        .method(method("GetPatient", "Patient").build())
+        .method(method("ReportIncident", "Patient").build())
+        .method(method("Optimize", "OptimizeResponse").build())
+        .method(
+            method("OptimizeStream", "PlanUpdate")
+                .server_streaming()
+                .build(),
+        )
+        .build();
+
+    Builder::new().compile(&[service]);
+}
+
+/// RPC `route` taking `<route>Request`, handled by the snake-case method.
+fn method(route: &str, output: &str) -> MethodBuilder {
+    let mut name = String::new();
+    for (i, c) in route.char_indices() {
+        if c.is_ascii_uppercase() && i > 0 {
+            name.push('_');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+
+    Method::builder()
+        .name(name)
+        .route_name(route)
+        .input_type(format!("super::{route}Request"))
+        .output_type(format!("super::{output}"))
+        .codec_path("tonic_prost::ProstCodec")
+}
@@ -0,0 +1,88 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "ambulances")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub call_sign: String,
+    pub ambulance_type: AmbulanceType,
+    pub crew_status: CrewStatus,
+    /// Patients the vehicle can transport at once.
+    pub capacity: i32,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub home_base: String,
+    pub home_latitude: f64,
+    pub home_longitude: f64,
+}
+
+/// Level of care the crew can deliver on scene and during transport.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Deserialize, Serialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "ambulance_type")]
+#[serde(rename_all = "snake_case")]
+pub enum AmbulanceType {
+    /// Basic life support, e.g. INEM AEM or fire brigade ambulances.
+    #[sea_orm(string_value = "bls")]
+    Bls,
+    /// Advanced life support, e.g. INEM SIV.
+    #[sea_orm(string_value = "als")]
+    Als,
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Deserialize, Serialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "crew_status")]
+#[serde(rename_all = "snake_case")]
+pub enum CrewStatus {
+    #[sea_orm(string_value = "available")]
+    Available,
+    #[sea_orm(string_value = "dispatched")]
+    Dispatched,
+    #[sea_orm(string_value = "transporting")]
+    Transporting,
+    #[sea_orm(string_value = "out_of_service")]
+    OutOfService,
+}
+
+impl AmbulanceType {
+    pub fn label(self) -> &'static str {
+        match self {
+            AmbulanceType::Bls => "BLS",
+            AmbulanceType::Als => "ALS",
+        }
+    }
+}
+
+impl fmt::Display for AmbulanceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl CrewStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            CrewStatus::Available => "available",
+            CrewStatus::Dispatched => "dispatched",
+            CrewStatus::Transporting => "transporting",
+            CrewStatus::OutOfService => "out of service",
+        }
+    }
+}
+
+impl fmt::Display for CrewStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,43 @@
+use crate::TriageLevel;
+use sea_orm::entity::prelude::*;
+
+/// The decision an optimization run made for one patient.
+///
+/// Patient, hospital, bucket and ambulance ids are copied without foreign
+/// keys so the audit trail outlives the rows it refers to.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "assignments")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub run_id: i32,
+    pub patient_id: i32,
+    pub triage_level: TriageLevel,
+    /// `None` when the patient was left unassigned.
+    pub hospital_id: Option<i32>,
+    pub hospital_speciality_id: Option<i32>,
+    /// Only set by dispatch runs.
+    pub ambulance_id: Option<i32>,
+    pub time_to_treatment_seconds: Option<f64>,
+    pub survival_probability: f64,
+    pub unassigned_reason: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::optimization_run::Entity",
+        from = "Column::RunId",
+        to = "super::optimization_run::Column::Id",
+        on_delete = "Cascade"
+    )]
+    OptimizationRun,
+}
+
+impl Related<super::optimization_run::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::OptimizationRun.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
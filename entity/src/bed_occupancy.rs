@@ -0,0 +1,34 @@
+use crate::TriageLevel;
+use sea_orm::entity::prelude::*;
+
+/// Beds in use for one hospital and triage level, as observed at `recorded_at`.
+/// Rows are append-only; the latest one at or before a given time wins.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "bed_occupancy")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub hospital_id: i32,
+    pub triage_level: TriageLevel,
+    pub occupied_beds: i32,
+    pub recorded_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::hospital::Entity",
+        from = "Column::HospitalId",
+        to = "super::hospital::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Hospital,
+}
+
+impl Related<super::hospital::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Hospital.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
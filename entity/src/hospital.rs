@@ -15,6 +15,10 @@ pub struct Model {
 pub enum Relation {
     #[sea_orm(has_many = "super::hospital_speciality::Entity")]
     HospitalSpeciality,
+    #[sea_orm(has_many = "super::bed_occupancy::Entity")]
+    BedOccupancy,
+    #[sea_orm(has_many = "super::patient::Entity")]
+    Patient,
 }
 
 impl Related<super::hospital_speciality::Entity> for Entity {
@@ -23,4 +27,16 @@ impl Related<super::hospital_speciality::Entity> for Entity {
     }
 }
 
+impl Related<super::bed_occupancy::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BedOccupancy.def()
+    }
+}
+
+impl Related<super::patient::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Patient.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
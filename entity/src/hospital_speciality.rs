@@ -1,3 +1,4 @@
+use crate::TriageLevel;
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
@@ -6,7 +7,7 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub hospital_id: i32,
-    pub triage_level: String,
+    pub triage_level: TriageLevel,
     pub waiting_time_seconds: i64,
     pub beds_capacity: i32,
 }
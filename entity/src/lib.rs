@@ -1,9 +1,20 @@
+pub mod ambulance;
+pub mod assignment;
+pub mod bed_occupancy;
 pub mod hospital;
 pub mod hospital_speciality;
+pub mod optimization_run;
 pub mod patient;
+pub mod triage_level;
+
+pub use triage_level::{Locale, TriageLevel};
 
 pub mod prelude {
+    pub use super::ambulance::Entity as Ambulance;
+    pub use super::assignment::Entity as Assignment;
+    pub use super::bed_occupancy::Entity as BedOccupancy;
     pub use super::hospital::Entity as Hospital;
     pub use super::hospital_speciality::Entity as HospitalSpeciality;
+    pub use super::optimization_run::Entity as OptimizationRun;
     pub use super::patient::Entity as Patient;
 }
@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+
+/// One optimizer invocation: what it was given, how it was configured and
+/// what it achieved. Its per-patient decisions are the [`super::assignment`] rows.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "optimization_runs")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub created_at: DateTimeWithTimeZone,
+    /// SHA-256 of the optimization input, equal for runs over the same snapshot.
+    pub input_hash: String,
+    pub backend: String,
+    pub parameters: Json,
+    pub status: String,
+    pub objective_value: f64,
+    pub objective_bound: Option<f64>,
+    /// Per-criterion values as `[{"criterion": …, "value": …}]`.
+    pub objectives: Json,
+    pub wall_time_ms: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::assignment::Entity")]
+    Assignment,
+}
+
+impl Related<super::assignment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Assignment.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,16 +1,116 @@
+use crate::TriageLevel;
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "patients")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
-    pub condition: String,
+    pub condition: TriageLevel,
     pub latitude: f64,
     pub longitude: f64,
+    pub status: PatientStatus,
+    pub reported_at: DateTimeWithTimeZone,
+    pub admitted_at: Option<DateTimeWithTimeZone>,
+    /// Hospital the patient was sent to, once a plan is acted upon.
+    pub assigned_hospital_id: Option<i32>,
+}
+
+/// Where a patient is in the care pathway, from the emergency call onwards.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Deserialize,
+    Serialize,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "patient_status")]
+#[serde(rename_all = "snake_case")]
+pub enum PatientStatus {
+    #[sea_orm(string_value = "reported")]
+    Reported,
+    #[sea_orm(string_value = "dispatched")]
+    Dispatched,
+    #[sea_orm(string_value = "in_transport")]
+    InTransport,
+    #[sea_orm(string_value = "admitted")]
+    Admitted,
+    #[sea_orm(string_value = "discharged")]
+    Discharged,
+    #[sea_orm(string_value = "deceased")]
+    Deceased,
+}
+
+impl PatientStatus {
+    /// Statuses of patients still waiting for a hospital.
+    pub const PENDING: &'static [PatientStatus] = &[PatientStatus::Reported];
+
+    /// Statuses of patients on their way to `assigned_hospital_id`, who will
+    /// need a bed there that the latest occupancy does not count yet.
+    pub const EN_ROUTE: &'static [PatientStatus] =
+        &[PatientStatus::Dispatched, PatientStatus::InTransport];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PatientStatus::Reported => "reported",
+            PatientStatus::Dispatched => "dispatched",
+            PatientStatus::InTransport => "in transport",
+            PatientStatus::Admitted => "admitted",
+            PatientStatus::Discharged => "discharged",
+            PatientStatus::Deceased => "deceased",
+        }
+    }
+
+    /// Still waiting for a hospital, so the optimizer should plan for them.
+    pub fn is_pending(self) -> bool {
+        Self::PENDING.contains(&self)
+    }
+}
+
+impl fmt::Display for PatientStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl Entity {
+    /// Patients with a [pending](PatientStatus::is_pending) status.
+    pub fn find_pending() -> Select<Entity> {
+        Self::find().filter(Column::Status.is_in(PatientStatus::PENDING.iter().copied()))
+    }
+
+    /// [En-route](PatientStatus::EN_ROUTE) patients with an assigned hospital.
+    pub fn find_en_route() -> Select<Entity> {
+        Self::find()
+            .filter(Column::Status.is_in(PatientStatus::EN_ROUTE.iter().copied()))
+            .filter(Column::AssignedHospitalId.is_not_null())
+    }
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::hospital::Entity",
+        from = "Column::AssignedHospitalId",
+        to = "super::hospital::Column::Id",
+        on_delete = "SetNull"
+    )]
+    Hospital,
+}
+
+impl Related<super::hospital::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Hospital.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,132 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use utoipa::ToSchema;
+
+/// Manchester triage colour, stored as the Postgres `triage_level` enum.
+///
+/// Variants are declared from most to least urgent, so the derived `Ord`
+/// sorts by urgency (`Red < Orange < … < Blue`).
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Deserialize,
+    Serialize,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "triage_level")]
+#[serde(rename_all = "snake_case")]
+pub enum TriageLevel {
+    #[sea_orm(string_value = "red")]
+    Red,
+    #[sea_orm(string_value = "orange")]
+    Orange,
+    #[sea_orm(string_value = "yellow")]
+    Yellow,
+    #[sea_orm(string_value = "green")]
+    Green,
+    #[sea_orm(string_value = "blue")]
+    Blue,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Locale {
+    Portuguese,
+    English,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseTriageLevelError(String);
+
+impl fmt::Display for ParseTriageLevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Unknown triage level '{}'. Expected a Manchester colour such as 'red' or a label such as 'Urgente (Amarelo)'.",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseTriageLevelError {}
+
+impl TriageLevel {
+    /// Every level, most urgent first.
+    pub const ALL: [TriageLevel; 5] = [
+        TriageLevel::Red,
+        TriageLevel::Orange,
+        TriageLevel::Yellow,
+        TriageLevel::Green,
+        TriageLevel::Blue,
+    ];
+
+    pub fn display_name(self, locale: Locale) -> &'static str {
+        match (self, locale) {
+            (TriageLevel::Red, Locale::Portuguese) => "Emergência (Vermelho)",
+            (TriageLevel::Orange, Locale::Portuguese) => "Muito Urgente (Laranja)",
+            (TriageLevel::Yellow, Locale::Portuguese) => "Urgente (Amarelo)",
+            (TriageLevel::Green, Locale::Portuguese) => "Pouco Urgente (Verde)",
+            (TriageLevel::Blue, Locale::Portuguese) => "Não Urgente (Azul)",
+            (TriageLevel::Red, Locale::English) => "Emergency (Red)",
+            (TriageLevel::Orange, Locale::English) => "Very Urgent (Orange)",
+            (TriageLevel::Yellow, Locale::English) => "Urgent (Yellow)",
+            (TriageLevel::Green, Locale::English) => "Standard (Green)",
+            (TriageLevel::Blue, Locale::English) => "Non-Urgent (Blue)",
+        }
+    }
+
+    /// Manchester target for the longest acceptable wait before treatment.
+    pub fn max_wait_seconds(self) -> i64 {
+        match self {
+            TriageLevel::Red => 0,
+            TriageLevel::Orange => 10 * 60,
+            TriageLevel::Yellow => 60 * 60,
+            TriageLevel::Green => 120 * 60,
+            TriageLevel::Blue => 240 * 60,
+        }
+    }
+
+    fn colour_names(self) -> [&'static str; 3] {
+        match self {
+            TriageLevel::Red => ["red", "vermelho", "emergência"],
+            TriageLevel::Orange => ["orange", "laranja", "muito urgente"],
+            TriageLevel::Yellow => ["yellow", "amarelo", "urgente"],
+            TriageLevel::Green => ["green", "verde", "pouco urgente"],
+            TriageLevel::Blue => ["blue", "azul", "não urgente"],
+        }
+    }
+}
+
+impl fmt::Display for TriageLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.display_name(Locale::Portuguese))
+    }
+}
+
+/// Accepts the database value, either localized label, or a bare colour or
+/// urgency name in Portuguese or English, ignoring case and surrounding
+/// whitespace.
+impl FromStr for TriageLevel {
+    type Err = ParseTriageLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let needle = s.trim().to_lowercase();
+
+        TriageLevel::ALL
+            .into_iter()
+            .find(|level| {
+                level.display_name(Locale::Portuguese).to_lowercase() == needle
+                    || level.display_name(Locale::English).to_lowercase() == needle
+                    || level.colour_names().contains(&needle.as_str())
+            })
+            .ok_or_else(|| ParseTriageLevelError(s.to_string()))
+    }
+}
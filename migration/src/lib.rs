@@ -4,6 +4,11 @@ mod m20251005_195352_hospitals;
 mod m20251006_210502_patients;
 mod m20251006_211530_beds_per_speciality;
 mod m20251007_182626_rename_speciality_to_triage;
+mod m20251014_101500_triage_level_enum;
+mod m20251016_093000_ambulances;
+mod m20251018_090000_bed_occupancy;
+mod m20251018_140000_patient_lifecycle;
+mod m20251018_160000_optimization_runs;
 
 pub struct Migrator;
 
@@ -15,6 +20,11 @@ impl MigratorTrait for Migrator {
             Box::new(m20251006_210502_patients::Migration),
             Box::new(m20251006_211530_beds_per_speciality::Migration),
             Box::new(m20251007_182626_rename_speciality_to_triage::Migration),
+            Box::new(m20251014_101500_triage_level_enum::Migration),
+            Box::new(m20251016_093000_ambulances::Migration),
+            Box::new(m20251018_090000_bed_occupancy::Migration),
+            Box::new(m20251018_140000_patient_lifecycle::Migration),
+            Box::new(m20251018_160000_optimization_runs::Migration),
         ]
     }
 }
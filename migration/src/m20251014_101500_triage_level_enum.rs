@@ -0,0 +1,92 @@
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Database value and the Portuguese label previously stored as free text.
+const LEVELS: [(&str, &str); 5] = [
+    ("red", "Emergência (Vermelho)"),
+    ("orange", "Muito Urgente (Laranja)"),
+    ("yellow", "Urgente (Amarelo)"),
+    ("green", "Pouco Urgente (Verde)"),
+    ("blue", "Não Urgente (Azul)"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(TriageLevel::Enum)
+                    .values([
+                        TriageLevel::Red,
+                        TriageLevel::Orange,
+                        TriageLevel::Yellow,
+                        TriageLevel::Green,
+                        TriageLevel::Blue,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Rows holding an unknown label map to NULL and make the NOT NULL
+        // columns reject the conversion instead of silently dropping data.
+        let db = manager.get_connection();
+        db.execute_unprepared(&label_to_enum_sql("hospital_specialities", "triage_level"))
+            .await?;
+        db.execute_unprepared(&label_to_enum_sql("patients", "condition"))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(&enum_to_label_sql("hospital_specialities", "triage_level"))
+            .await?;
+        db.execute_unprepared(&enum_to_label_sql("patients", "condition"))
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(TriageLevel::Enum).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn label_to_enum_sql(table: &str, column: &str) -> String {
+    let cases: String = LEVELS
+        .iter()
+        .map(|(value, label)| format!(" WHEN '{label}' THEN '{value}'"))
+        .collect();
+
+    format!(
+        "ALTER TABLE \"{table}\" ALTER COLUMN \"{column}\" TYPE triage_level \
+         USING (CASE \"{column}\"{cases} END)::triage_level"
+    )
+}
+
+fn enum_to_label_sql(table: &str, column: &str) -> String {
+    let cases: String = LEVELS
+        .iter()
+        .map(|(value, label)| format!(" WHEN '{value}' THEN '{label}'"))
+        .collect();
+
+    format!(
+        "ALTER TABLE \"{table}\" ALTER COLUMN \"{column}\" TYPE varchar \
+         USING (CASE \"{column}\"::text{cases} END)"
+    )
+}
+
+#[derive(DeriveIden)]
+enum TriageLevel {
+    #[sea_orm(iden = "triage_level")]
+    Enum,
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+}
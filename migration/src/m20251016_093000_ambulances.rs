@@ -0,0 +1,118 @@
+use sea_orm_migration::{
+    prelude::{extension::postgres::Type, *},
+    schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(AmbulanceType::Enum)
+                    .values([AmbulanceType::Bls, AmbulanceType::Als])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(CrewStatus::Enum)
+                    .values([
+                        CrewStatus::Available,
+                        CrewStatus::Dispatched,
+                        CrewStatus::Transporting,
+                        CrewStatus::OutOfService,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Ambulances::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Ambulances::Id))
+                    .col(string(Ambulances::CallSign).not_null().unique_key())
+                    .col(
+                        ColumnDef::new(Ambulances::AmbulanceType)
+                            .custom(AmbulanceType::Enum)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Ambulances::CrewStatus)
+                            .custom(CrewStatus::Enum)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Ambulances::Capacity).integer().not_null())
+                    .col(ColumnDef::new(Ambulances::Latitude).double().not_null())
+                    .col(ColumnDef::new(Ambulances::Longitude).double().not_null())
+                    .col(string(Ambulances::HomeBase).not_null())
+                    .col(ColumnDef::new(Ambulances::HomeLatitude).double().not_null())
+                    .col(
+                        ColumnDef::new(Ambulances::HomeLongitude)
+                            .double()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Ambulances::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(CrewStatus::Enum).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(AmbulanceType::Enum).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Ambulances {
+    #[sea_orm(iden = "ambulances")]
+    Table,
+    Id,
+    CallSign,
+    AmbulanceType,
+    CrewStatus,
+    Capacity,
+    Latitude,
+    Longitude,
+    HomeBase,
+    HomeLatitude,
+    HomeLongitude,
+}
+
+#[derive(DeriveIden)]
+enum AmbulanceType {
+    #[sea_orm(iden = "ambulance_type")]
+    Enum,
+    Bls,
+    Als,
+}
+
+#[derive(DeriveIden)]
+enum CrewStatus {
+    #[sea_orm(iden = "crew_status")]
+    Enum,
+    Available,
+    Dispatched,
+    Transporting,
+    OutOfService,
+}
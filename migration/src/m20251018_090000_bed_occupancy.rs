@@ -0,0 +1,84 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BedOccupancy::Table)
+                    .if_not_exists()
+                    .col(pk_auto(BedOccupancy::Id))
+                    .col(integer(BedOccupancy::HospitalId).not_null())
+                    .col(
+                        ColumnDef::new(BedOccupancy::TriageLevel)
+                            .custom(TriageLevel::Enum)
+                            .not_null(),
+                    )
+                    .col(integer(BedOccupancy::OccupiedBeds).not_null())
+                    .col(
+                        timestamp_with_time_zone(BedOccupancy::RecordedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_bed_occupancy_hospital")
+                            .from(BedOccupancy::Table, BedOccupancy::HospitalId)
+                            .to(Hospitals::Table, Hospitals::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_bed_occupancy_lookup")
+                    .table(BedOccupancy::Table)
+                    .col(BedOccupancy::HospitalId)
+                    .col(BedOccupancy::TriageLevel)
+                    .col(BedOccupancy::RecordedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BedOccupancy::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum BedOccupancy {
+    #[sea_orm(iden = "bed_occupancy")]
+    Table,
+    Id,
+    HospitalId,
+    TriageLevel,
+    OccupiedBeds,
+    RecordedAt,
+}
+
+#[derive(DeriveIden)]
+enum Hospitals {
+    #[sea_orm(iden = "hospitals")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum TriageLevel {
+    #[sea_orm(iden = "triage_level")]
+    Enum,
+}
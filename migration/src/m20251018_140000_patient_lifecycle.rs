@@ -0,0 +1,130 @@
+use sea_orm_migration::{
+    prelude::{extension::postgres::Type, *},
+    schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(PatientStatus::Enum)
+                    .values([
+                        PatientStatus::Reported,
+                        PatientStatus::Dispatched,
+                        PatientStatus::InTransport,
+                        PatientStatus::Admitted,
+                        PatientStatus::Discharged,
+                        PatientStatus::Deceased,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Existing patients become `reported` now, which keeps them pending.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Patients::Table)
+                    .add_column(
+                        ColumnDef::new(Patients::Status)
+                            .custom(PatientStatus::Enum)
+                            .not_null()
+                            .default(Expr::val("reported").as_enum(PatientStatus::Enum)),
+                    )
+                    .add_column(
+                        timestamp_with_time_zone(Patients::ReportedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .add_column(timestamp_with_time_zone_null(Patients::AdmittedAt))
+                    .add_column(integer_null(Patients::AssignedHospitalId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_patients_assigned_hospital")
+                            .from_tbl(Patients::Table)
+                            .from_col(Patients::AssignedHospitalId)
+                            .to_tbl(Hospitals::Table)
+                            .to_col(Hospitals::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_patients_status")
+                    .table(Patients::Table)
+                    .col(Patients::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_patients_status")
+                    .table(Patients::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Patients::Table)
+                    .drop_foreign_key(Alias::new("fk_patients_assigned_hospital"))
+                    .drop_column(Patients::AssignedHospitalId)
+                    .drop_column(Patients::AdmittedAt)
+                    .drop_column(Patients::ReportedAt)
+                    .drop_column(Patients::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(PatientStatus::Enum).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Patients {
+    #[sea_orm(iden = "patients")]
+    Table,
+    Status,
+    ReportedAt,
+    AdmittedAt,
+    AssignedHospitalId,
+}
+
+#[derive(DeriveIden)]
+enum Hospitals {
+    #[sea_orm(iden = "hospitals")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PatientStatus {
+    #[sea_orm(iden = "patient_status")]
+    Enum,
+    Reported,
+    Dispatched,
+    InTransport,
+    Admitted,
+    Discharged,
+    Deceased,
+}
@@ -0,0 +1,144 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(OptimizationRuns::Table)
+                    .if_not_exists()
+                    .col(pk_auto(OptimizationRuns::Id))
+                    .col(
+                        timestamp_with_time_zone(OptimizationRuns::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(string(OptimizationRuns::InputHash).not_null())
+                    .col(string(OptimizationRuns::Backend).not_null())
+                    .col(json_binary(OptimizationRuns::Parameters).not_null())
+                    .col(string(OptimizationRuns::Status).not_null())
+                    .col(double(OptimizationRuns::ObjectiveValue).not_null())
+                    .col(double_null(OptimizationRuns::ObjectiveBound))
+                    .col(json_binary(OptimizationRuns::Objectives).not_null())
+                    .col(big_integer(OptimizationRuns::WallTimeMs).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_optimization_runs_input_hash")
+                    .table(OptimizationRuns::Table)
+                    .col(OptimizationRuns::InputHash)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Assignments::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Assignments::Id))
+                    .col(integer(Assignments::RunId).not_null())
+                    .col(integer(Assignments::PatientId).not_null())
+                    .col(
+                        ColumnDef::new(Assignments::TriageLevel)
+                            .custom(TriageLevel::Enum)
+                            .not_null(),
+                    )
+                    .col(integer_null(Assignments::HospitalId))
+                    .col(integer_null(Assignments::HospitalSpecialityId))
+                    .col(integer_null(Assignments::AmbulanceId))
+                    .col(double_null(Assignments::TimeToTreatmentSeconds))
+                    .col(double(Assignments::SurvivalProbability).not_null())
+                    .col(string_null(Assignments::UnassignedReason))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_assignments_run")
+                            .from(Assignments::Table, Assignments::RunId)
+                            .to(OptimizationRuns::Table, OptimizationRuns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_assignments_run")
+                    .table(Assignments::Table)
+                    .col(Assignments::RunId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_assignments_patient")
+                    .table(Assignments::Table)
+                    .col(Assignments::PatientId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Assignments::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(OptimizationRuns::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum OptimizationRuns {
+    #[sea_orm(iden = "optimization_runs")]
+    Table,
+    Id,
+    CreatedAt,
+    InputHash,
+    Backend,
+    Parameters,
+    Status,
+    ObjectiveValue,
+    ObjectiveBound,
+    Objectives,
+    WallTimeMs,
+}
+
+#[derive(DeriveIden)]
+enum Assignments {
+    #[sea_orm(iden = "assignments")]
+    Table,
+    Id,
+    RunId,
+    PatientId,
+    TriageLevel,
+    HospitalId,
+    HospitalSpecialityId,
+    AmbulanceId,
+    TimeToTreatmentSeconds,
+    SurvivalProbability,
+    UnassignedReason,
+}
+
+#[derive(DeriveIden)]
+enum TriageLevel {
+    #[sea_orm(iden = "triage_level")]
+    Enum,
+}
@@ -0,0 +1,208 @@
+use anyhow::Result;
+use entity::{
+    ambulance::{self, AmbulanceType, CrewStatus},
+    prelude::Ambulance,
+};
+use rand::Rng;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, DatabaseConnection, EntityTrait, TransactionTrait,
+};
+
+const LAT_MIN: f64 = 38.70;
+const LAT_MAX: f64 = 38.80;
+const LON_MIN: f64 = -9.23;
+const LON_MAX: f64 = -9.10;
+
+pub struct AmbulanceSeedReport {
+    pub ambulances: usize,
+    pub available: usize,
+}
+
+struct BaseSeed {
+    name: &'static str,
+    latitude: f64,
+    longitude: f64,
+}
+
+struct AmbulanceSeed {
+    call_sign: &'static str,
+    ambulance_type: AmbulanceType,
+    capacity: i32,
+    base: usize,
+}
+
+pub async fn seed(db: &DatabaseConnection, rng: &mut impl Rng) -> Result<AmbulanceSeedReport> {
+    let bases = base_blueprints();
+    let fleet = fleet_blueprints();
+    let txn = db.begin().await?;
+
+    Ambulance::delete_many().exec(&txn).await?;
+
+    let mut available = 0usize;
+
+    for vehicle in &fleet {
+        let base = &bases[vehicle.base];
+        let crew_status = sample_crew_status(rng);
+
+        // Crews on a mission are somewhere in the city; the rest wait at base.
+        let (latitude, longitude) = match crew_status {
+            CrewStatus::Dispatched | CrewStatus::Transporting => (
+                rng.random_range(LAT_MIN..LAT_MAX),
+                rng.random_range(LON_MIN..LON_MAX),
+            ),
+            CrewStatus::Available | CrewStatus::OutOfService => (base.latitude, base.longitude),
+        };
+
+        if crew_status == CrewStatus::Available {
+            available += 1;
+        }
+
+        ambulance::ActiveModel {
+            call_sign: Set(vehicle.call_sign.to_string()),
+            ambulance_type: Set(vehicle.ambulance_type),
+            crew_status: Set(crew_status),
+            capacity: Set(vehicle.capacity),
+            latitude: Set(latitude),
+            longitude: Set(longitude),
+            home_base: Set(base.name.to_string()),
+            home_latitude: Set(base.latitude),
+            home_longitude: Set(base.longitude),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(AmbulanceSeedReport {
+        ambulances: fleet.len(),
+        available,
+    })
+}
+
+fn sample_crew_status(rng: &mut impl Rng) -> CrewStatus {
+    match rng.random_range(0..100) {
+        0..70 => CrewStatus::Available,
+        70..85 => CrewStatus::Dispatched,
+        85..95 => CrewStatus::Transporting,
+        _ => CrewStatus::OutOfService,
+    }
+}
+
+fn base_blueprints() -> Vec<BaseSeed> {
+    vec![
+        BaseSeed {
+            name: "INEM Delegação Regional do Sul",
+            latitude: 38.7330,
+            longitude: -9.1430,
+        },
+        BaseSeed {
+            name: "Regimento de Sapadores Bombeiros - Quartel D. Carlos I",
+            latitude: 38.7085,
+            longitude: -9.1545,
+        },
+        BaseSeed {
+            name: "Bombeiros Voluntários Lisbonenses",
+            latitude: 38.7170,
+            longitude: -9.1300,
+        },
+        BaseSeed {
+            name: "Bombeiros Voluntários de Campo de Ourique",
+            latitude: 38.7180,
+            longitude: -9.1660,
+        },
+        BaseSeed {
+            name: "Bombeiros Voluntários do Beato",
+            latitude: 38.7340,
+            longitude: -9.1030,
+        },
+        BaseSeed {
+            name: "Bombeiros Voluntários de Cabo Ruivo",
+            latitude: 38.7610,
+            longitude: -9.1020,
+        },
+        BaseSeed {
+            name: "Bombeiros Voluntários de Belém",
+            latitude: 38.6985,
+            longitude: -9.2000,
+        },
+    ]
+}
+
+fn fleet_blueprints() -> Vec<AmbulanceSeed> {
+    vec![
+        AmbulanceSeed {
+            call_sign: "AEM Lisboa 1",
+            ambulance_type: AmbulanceType::Bls,
+            capacity: 1,
+            base: 0,
+        },
+        AmbulanceSeed {
+            call_sign: "AEM Lisboa 2",
+            ambulance_type: AmbulanceType::Bls,
+            capacity: 1,
+            base: 0,
+        },
+        AmbulanceSeed {
+            call_sign: "AEM Lisboa 3",
+            ambulance_type: AmbulanceType::Bls,
+            capacity: 1,
+            base: 0,
+        },
+        AmbulanceSeed {
+            call_sign: "SIV Lisboa",
+            ambulance_type: AmbulanceType::Als,
+            capacity: 1,
+            base: 0,
+        },
+        AmbulanceSeed {
+            call_sign: "RSB ABSC 01",
+            ambulance_type: AmbulanceType::Bls,
+            capacity: 2,
+            base: 1,
+        },
+        AmbulanceSeed {
+            call_sign: "RSB ABSC 02",
+            ambulance_type: AmbulanceType::Bls,
+            capacity: 2,
+            base: 1,
+        },
+        AmbulanceSeed {
+            call_sign: "RSB SIV 01",
+            ambulance_type: AmbulanceType::Als,
+            capacity: 1,
+            base: 1,
+        },
+        AmbulanceSeed {
+            call_sign: "BVL ABSC 01",
+            ambulance_type: AmbulanceType::Bls,
+            capacity: 2,
+            base: 2,
+        },
+        AmbulanceSeed {
+            call_sign: "BVCO ABSC 01",
+            ambulance_type: AmbulanceType::Bls,
+            capacity: 2,
+            base: 3,
+        },
+        AmbulanceSeed {
+            call_sign: "BVB ABSC 01",
+            ambulance_type: AmbulanceType::Bls,
+            capacity: 2,
+            base: 4,
+        },
+        AmbulanceSeed {
+            call_sign: "BVCR ABSC 01",
+            ambulance_type: AmbulanceType::Bls,
+            capacity: 2,
+            base: 5,
+        },
+        AmbulanceSeed {
+            call_sign: "BVBelém ABSC 01",
+            ambulance_type: AmbulanceType::Bls,
+            capacity: 2,
+            base: 6,
+        },
+    ]
+}
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use entity::{
-    hospital, hospital_speciality,
+    TriageLevel, hospital, hospital_speciality,
     prelude::{Hospital, HospitalSpeciality},
 };
 use rand::Rng;
@@ -13,38 +13,37 @@ pub struct HospitalSeedReport {
     pub triage_levels: usize,
 }
 
-struct HospitalSeed {
-    name: &'static str,
-    latitude: f64,
-    longitude: f64,
-    bed_capacity: i32,
+pub struct HospitalSeed {
+    pub name: &'static str,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub bed_capacity: i32,
 }
 
-struct TriageLevelSeed {
-    name: &'static str,
-    waiting_time_seconds: i64,
-    beds_capacity: i32,
+pub struct TriageLevelSeed {
+    pub level: TriageLevel,
+    pub waiting_time_seconds: i64,
+    pub beds_capacity: i32,
 }
 
 struct TriageLevelConfig {
-    name: &'static str,
+    level: TriageLevel,
     wait_min_seconds: i64,
     wait_max_seconds: i64,
     share_min: f32,
     share_max: f32,
 }
 
-pub async fn seed(db: &DatabaseConnection) -> Result<HospitalSeedReport> {
-    let hospitals = hospital_blueprints();
-    let mut txn = db.begin().await?;
+pub async fn seed(db: &DatabaseConnection, rng: &mut impl Rng) -> Result<HospitalSeedReport> {
+    let hospitals = network(rng);
+    let txn = db.begin().await?;
 
-    HospitalSpeciality::delete_many().exec(&mut txn).await?;
-    Hospital::delete_many().exec(&mut txn).await?;
+    HospitalSpeciality::delete_many().exec(&txn).await?;
+    Hospital::delete_many().exec(&txn).await?;
 
-    let mut rng = rand::rng();
     let mut triage_total = 0usize;
 
-    for hospital_seed in &hospitals {
+    for (hospital_seed, levels) in &hospitals {
         let inserted = hospital::ActiveModel {
             location_name: Set(hospital_seed.name.to_string()),
             latitude: Set(hospital_seed.latitude),
@@ -52,18 +51,18 @@ pub async fn seed(db: &DatabaseConnection) -> Result<HospitalSeedReport> {
             bed_capacity: Set(hospital_seed.bed_capacity),
             ..Default::default()
         }
-        .insert(&mut txn)
+        .insert(&txn)
         .await?;
 
-        for triage in build_manchester_levels(hospital_seed.bed_capacity, &mut rng) {
+        for triage in levels {
             hospital_speciality::ActiveModel {
                 hospital_id: Set(inserted.id),
-                triage_level: Set(triage.name.to_string()),
+                triage_level: Set(triage.level),
                 waiting_time_seconds: Set(triage.waiting_time_seconds),
                 beds_capacity: Set(triage.beds_capacity),
                 ..Default::default()
             }
-            .insert(&mut txn)
+            .insert(&txn)
             .await?;
 
             triage_total += 1;
@@ -78,6 +77,17 @@ pub async fn seed(db: &DatabaseConnection) -> Result<HospitalSeedReport> {
     })
 }
 
+/// The Lisbon hospitals, each with freshly sampled Manchester triage buckets.
+pub fn network(rng: &mut impl Rng) -> Vec<(HospitalSeed, Vec<TriageLevelSeed>)> {
+    hospital_blueprints()
+        .into_iter()
+        .map(|hospital| {
+            let levels = build_manchester_levels(hospital.bed_capacity, rng);
+            (hospital, levels)
+        })
+        .collect()
+}
+
 fn hospital_blueprints() -> Vec<HospitalSeed> {
     vec![
         HospitalSeed {
@@ -134,35 +144,35 @@ fn hospital_blueprints() -> Vec<HospitalSeed> {
 fn build_manchester_levels(total_beds: i32, rng: &mut impl Rng) -> Vec<TriageLevelSeed> {
     const LEVEL_CONFIG: [TriageLevelConfig; 5] = [
         TriageLevelConfig {
-            name: "Emergência (Vermelho)",
+            level: TriageLevel::Red,
             wait_min_seconds: 0,
             wait_max_seconds: 120,
             share_min: 0.02,
             share_max: 0.05,
         },
         TriageLevelConfig {
-            name: "Muito Urgente (Laranja)",
+            level: TriageLevel::Orange,
             wait_min_seconds: 5 * 60,
             wait_max_seconds: 12 * 60,
             share_min: 0.05,
             share_max: 0.12,
         },
         TriageLevelConfig {
-            name: "Urgente (Amarelo)",
+            level: TriageLevel::Yellow,
             wait_min_seconds: 45 * 60,
             wait_max_seconds: 75 * 60,
             share_min: 0.20,
             share_max: 0.32,
         },
         TriageLevelConfig {
-            name: "Pouco Urgente (Verde)",
+            level: TriageLevel::Green,
             wait_min_seconds: 90 * 60,
             wait_max_seconds: 150 * 60,
             share_min: 0.20,
             share_max: 0.30,
         },
         TriageLevelConfig {
-            name: "Não Urgente (Azul)",
+            level: TriageLevel::Blue,
             wait_min_seconds: 180 * 60,
             wait_max_seconds: 300 * 60,
             share_min: 0.20,
@@ -214,9 +224,9 @@ fn build_manchester_levels(total_beds: i32, rng: &mut impl Rng) -> Vec<TriageLev
 
     LEVEL_CONFIG
         .iter()
-        .zip(allocations.into_iter())
+        .zip(allocations)
         .map(|(cfg, beds)| TriageLevelSeed {
-            name: cfg.name,
+            level: cfg.level,
             waiting_time_seconds: rng.random_range(cfg.wait_min_seconds..=cfg.wait_max_seconds),
             beds_capacity: beds,
         })
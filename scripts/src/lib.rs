@@ -0,0 +1,31 @@
+//! Seeding and Monte Carlo helpers behind the `scripts` binary.
+
+use anyhow::{Result, anyhow};
+
+pub mod ambulances;
+pub mod hospitals;
+pub mod monte_carlo;
+pub mod patients;
+
+/// `--seed N` (or `--seed=N`) from the arguments, removing it, or else the
+/// `SEED` value in `env_seed`.
+pub fn resolve_seed(args: &mut Vec<String>, env_seed: Option<&str>) -> Result<Option<u64>> {
+    let parse = |value: &str| {
+        value
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("The seed must be a non-negative whole number, got '{value}'"))
+    };
+
+    if let Some(index) = args.iter().position(|arg| arg.starts_with("--seed")) {
+        let flag = args.remove(index);
+        let value = match flag.strip_prefix("--seed=") {
+            Some(value) => value.to_string(),
+            None if flag == "--seed" && index < args.len() => args.remove(index),
+            None => return Err(anyhow!("Expected a value after '{flag}'")),
+        };
+        return parse(&value).map(Some);
+    }
+
+    env_seed.map(parse).transpose()
+}
@@ -1,20 +1,43 @@
 use anyhow::{Context, Result, anyhow};
 use entity::{
-    hospital, patient,
-    prelude::{Hospital, HospitalSpeciality, Patient},
+    ambulance, hospital, patient,
+    prelude::{Ambulance, Hospital, HospitalSpeciality, Patient},
 };
+use rand::{SeedableRng, rngs::StdRng};
+use scripts::{ambulances, hospitals, monte_carlo, patients, resolve_seed};
 use sea_orm::{Database, DatabaseConnection, EntityTrait, QueryOrder};
 use std::str::FromStr;
 
-mod hospitals;
-mod patients;
-
-const TRIAGE_LEVELS: &[&str] = patients::TRIAGE_CONDITIONS;
-
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
 
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let env_seed = match std::env::var("SEED") {
+        Ok(value) => Some(value),
+        Err(std::env::VarError::NotPresent) => None,
+        Err(e) => return Err(anyhow!(e)),
+    };
+    let seed = resolve_seed(&mut args, env_seed.as_deref())?;
+    match args.first().map(String::as_str) {
+        None => seed_database(seed.unwrap_or_else(rand::random)).await,
+        Some("monte-carlo") => {
+            let mut settings = monte_carlo::MonteCarloSettings::from_env()?;
+            settings.first_seed = seed.unwrap_or(0);
+            let runs = monte_carlo::run(&settings)?;
+            monte_carlo::print_report(&settings, &runs);
+            Ok(())
+        }
+        Some(other) => Err(anyhow!(
+            "Unknown command '{other}'. Expected 'monte-carlo' or no command."
+        )),
+    }
+}
+
+/// Replaces the hospitals and ambulances and seeds patients. Every random
+/// draw comes from `seed`, so the same seed reproduces the same data. Ids
+/// come from the table sequences, so they differ between reruns.
+async fn seed_database(seed: u64) -> Result<()> {
     let database_url =
         std::env::var("DATABASE_URL").context("DATABASE_URL environment variable is not set")?;
 
@@ -24,8 +47,12 @@ async fn main() -> Result<()> {
 
     let patient_mode = resolve_patient_mode()?;
 
-    let hospital_report = hospitals::seed(&db).await?;
-    let patient_report = patients::seed(&db, patient_mode).await?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let hospital_report = hospitals::seed(&db, &mut rng).await?;
+    let patient_report = patients::seed(&db, patient_mode, &mut rng).await?;
+    let ambulance_report = ambulances::seed(&db, &mut rng).await?;
+
+    println!("Seed: {seed} (rerun with SEED={seed} to reproduce this data)");
 
     println!(
         "Seeded {hospitals} hospitals and {triage_levels} triage buckets",
@@ -38,9 +65,15 @@ async fn main() -> Result<()> {
         inserted = patient_report.inserted,
         total = patient_report.total,
     );
+    println!(
+        "Seeded {ambulances} ambulances ({available} available)",
+        ambulances = ambulance_report.ambulances,
+        available = ambulance_report.available,
+    );
 
     print_hospital_snapshot(&db).await?;
     print_patient_snapshot(&db).await?;
+    print_ambulance_snapshot(&db).await?;
 
     Ok(())
 }
@@ -70,7 +103,7 @@ async fn print_hospital_snapshot(db: &DatabaseConnection) -> Result<()> {
             lon = hospital.longitude,
         );
 
-        triages.sort_by_key(|triage| triage_rank(&triage.triage_level, TRIAGE_LEVELS));
+        triages.sort_by_key(|triage| triage.triage_level);
 
         for triage in triages {
             let minutes = triage.waiting_time_seconds / 60;
@@ -89,15 +122,9 @@ async fn print_hospital_snapshot(db: &DatabaseConnection) -> Result<()> {
     Ok(())
 }
 
-fn triage_rank(level: &str, ordering: &[&str]) -> usize {
-    ordering
-        .iter()
-        .position(|name| *name == level)
-        .unwrap_or(usize::MAX)
-}
-
 async fn print_patient_snapshot(db: &DatabaseConnection) -> Result<()> {
-    let mut patients = Patient::find()
+    let patients = Patient::find()
+        // The Postgres triage_level enum sorts by urgency, most urgent first.
         .order_by_asc(patient::Column::Condition)
         .all(db)
         .await
@@ -110,12 +137,11 @@ async fn print_patient_snapshot(db: &DatabaseConnection) -> Result<()> {
 
     println!("\nCurrent patient snapshot:");
 
-    patients.sort_by_key(|patient| triage_rank(&patient.condition, TRIAGE_LEVELS));
-
     for patient in patients {
         println!(
-            "- {condition}: lat {lat:.5}, lon {lon:.5}",
+            "- {condition} ({status}): lat {lat:.5}, lon {lon:.5}",
             condition = patient.condition,
+            status = patient.status,
             lat = patient.latitude,
             lon = patient.longitude,
         );
@@ -124,6 +150,36 @@ async fn print_patient_snapshot(db: &DatabaseConnection) -> Result<()> {
     Ok(())
 }
 
+async fn print_ambulance_snapshot(db: &DatabaseConnection) -> Result<()> {
+    let ambulances = Ambulance::find()
+        .order_by_asc(ambulance::Column::CallSign)
+        .all(db)
+        .await
+        .context("Failed to fetch ambulance data")?;
+
+    if ambulances.is_empty() {
+        println!("\nNo ambulances found in the database.");
+        return Ok(());
+    }
+
+    println!("\nCurrent ambulance snapshot:");
+
+    for ambulance in ambulances {
+        println!(
+            "- {call_sign} ({kind}, capacity {capacity}): {status} at lat {lat:.5}, lon {lon:.5} (base: {base})",
+            call_sign = ambulance.call_sign,
+            kind = ambulance.ambulance_type,
+            capacity = ambulance.capacity,
+            status = ambulance.crew_status,
+            lat = ambulance.latitude,
+            lon = ambulance.longitude,
+            base = ambulance.home_base,
+        );
+    }
+
+    Ok(())
+}
+
 fn resolve_patient_mode() -> Result<patients::PatientSeedMode> {
     match std::env::var("PATIENT_SEED_MODE") {
         Ok(value) => patients::PatientSeedMode::from_str(&value)
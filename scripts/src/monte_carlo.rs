@@ -0,0 +1,284 @@
+use crate::{hospitals, patients};
+use anyhow::{Context, Result, anyhow};
+use entity::TriageLevel;
+use hospitopt_rs::{
+    optimize::{
+        DEFAULT_SOLVER, HospitalSite, PatientDemand, Problem, Solver, SolverRegistry,
+        SurvivalModel, TriageBucket, objective,
+    },
+    travel::TravelTimes,
+};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use std::str::FromStr;
+
+pub struct MonteCarloSettings {
+    pub runs: u64,
+    /// Run `n` uses seed `first_seed + n`.
+    pub first_seed: u64,
+    /// Patients per run; `None` samples the seeding range every run.
+    pub patients: Option<usize>,
+    /// Lowest share of each triage bucket's beds already occupied.
+    pub min_occupancy: f64,
+    pub solver: String,
+}
+
+impl MonteCarloSettings {
+    /// `MONTE_CARLO_RUNS` (200), `MONTE_CARLO_PATIENTS`,
+    /// `MONTE_CARLO_MIN_OCCUPANCY` (0.8) and `SOLVER`.
+    pub fn from_env() -> Result<Self> {
+        let min_occupancy = env_or("MONTE_CARLO_MIN_OCCUPANCY", 0.8)?;
+        if !(0.0..=1.0).contains(&min_occupancy) {
+            return Err(anyhow!(
+                "MONTE_CARLO_MIN_OCCUPANCY must be between 0 and 1, got {min_occupancy}"
+            ));
+        }
+
+        Ok(MonteCarloSettings {
+            runs: env_or("MONTE_CARLO_RUNS", 200)?,
+            first_seed: 0,
+            patients: optional_env("MONTE_CARLO_PATIENTS")?,
+            min_occupancy,
+            solver: std::env::var("SOLVER").unwrap_or_else(|_| DEFAULT_SOLVER.to_string()),
+        })
+    }
+}
+
+/// Outcome of one sampled scenario.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunMetrics {
+    pub seed: u64,
+    pub patients: usize,
+    pub unassigned: usize,
+    /// Red and Orange patients left without a bed.
+    pub critical_unassigned: usize,
+    /// Share of the network's beds in use once the plan is carried out.
+    pub utilization: f64,
+    /// The same share for the fullest hospital.
+    pub busiest_hospital: f64,
+    /// Expected survival over all patients, unassigned ones counting as zero.
+    pub mean_survival: f64,
+}
+
+/// Samples and solves one scenario per seed in `first_seed..first_seed + runs`.
+/// Each draws the triage buckets, their occupancy, the patients and the hour
+/// of day.
+pub fn run(settings: &MonteCarloSettings) -> Result<Vec<RunMetrics>> {
+    let registry = SolverRegistry::default();
+    let solver = registry.get(&settings.solver).with_context(|| {
+        format!(
+            "Unknown SOLVER '{}'. Available backends: {}",
+            settings.solver,
+            registry.names().collect::<Vec<_>>().join(", ")
+        )
+    })?;
+    let travel_times = TravelTimes::default();
+
+    let last_seed = settings
+        .first_seed
+        .checked_add(settings.runs)
+        .context("The seed range overflows u64")?;
+    (settings.first_seed..last_seed)
+        .map(|seed| sample(seed, settings, solver, &travel_times))
+        .collect()
+}
+
+fn sample(
+    seed: u64,
+    settings: &MonteCarloSettings,
+    solver: &dyn Solver,
+    travel_times: &TravelTimes,
+) -> Result<RunMetrics> {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut hospitals = Vec::new();
+    let mut buckets = Vec::new();
+    let mut beds = Vec::new();
+    let mut occupied = Vec::new();
+    for (index, (hospital, levels)) in hospitals::network(&mut rng).into_iter().enumerate() {
+        hospitals.push(HospitalSite {
+            id: index as i32 + 1,
+            name: hospital.name.to_string(),
+            latitude: hospital.latitude,
+            longitude: hospital.longitude,
+            bed_capacity: hospital.bed_capacity as u32,
+            occupied_beds: 0,
+        });
+        for level in levels {
+            let capacity = level.beds_capacity as u32;
+            let share = rng.random_range(settings.min_occupancy..=1.0);
+            let in_use = (capacity as f64 * share).round() as u32;
+            beds.push(capacity);
+            occupied.push(in_use);
+            hospitals[index].occupied_beds += in_use;
+            buckets.push(TriageBucket {
+                id: buckets.len() as i32 + 1,
+                hospital: index,
+                triage_level: level.level,
+                waiting_time_seconds: level.waiting_time_seconds,
+                capacity: capacity - in_use,
+            });
+        }
+    }
+
+    let count = settings
+        .patients
+        .unwrap_or_else(|| rng.random_range(patients::PATIENT_COUNT));
+    let patients = patients::generate(count, &mut rng)
+        .into_iter()
+        .enumerate()
+        .map(|(index, patient)| PatientDemand {
+            id: index as i32 + 1,
+            condition: patient.condition,
+            latitude: patient.latitude,
+            longitude: patient.longitude,
+        })
+        .collect();
+    let departure_hour = rng.random_range(0..24);
+
+    let problem = Problem {
+        hospitals,
+        buckets,
+        patients,
+        travel_times: None,
+        survival: SurvivalModel::default(),
+    };
+    let problem = travel_times.attach(problem, departure_hour)?;
+    let solution = solver
+        .solve(&problem)
+        .with_context(|| format!("The '{}' backend failed on seed {seed}", solver.name()))?;
+
+    let mut in_use = occupied;
+    let choices = solution.plan.choices(&problem);
+    for bucket in choices.iter().flatten() {
+        in_use[*bucket] += 1;
+    }
+    let share = |hospital: Option<usize>| {
+        let (used, total) = problem
+            .buckets
+            .iter()
+            .zip(in_use.iter().zip(&beds))
+            .filter(|(bucket, _)| hospital.is_none_or(|hospital| bucket.hospital == hospital))
+            .fold((0, 0), |(used, total), (_, (in_use, beds))| {
+                (used + in_use, total + beds)
+            });
+        used as f64 / total.max(1) as f64
+    };
+
+    let unassigned: Vec<TriageLevel> = choices
+        .iter()
+        .zip(&problem.patients)
+        .filter(|(bucket, _)| bucket.is_none())
+        .map(|(_, patient)| patient.condition)
+        .collect();
+    let survival: f64 = objective::survival_report(&problem, &solution.plan)
+        .iter()
+        .map(|patient| patient.survival_probability)
+        .sum();
+
+    Ok(RunMetrics {
+        seed,
+        patients: count,
+        unassigned: unassigned.len(),
+        critical_unassigned: unassigned
+            .iter()
+            .filter(|level| matches!(level, TriageLevel::Red | TriageLevel::Orange))
+            .count(),
+        utilization: share(None),
+        busiest_hospital: (0..problem.hospitals.len())
+            .map(|hospital| share(Some(hospital)))
+            .fold(0.0, f64::max),
+        mean_survival: survival / count.max(1) as f64,
+    })
+}
+
+type Metric = fn(&RunMetrics) -> f64;
+
+/// Prints the mean, 5th, 50th and 95th percentiles and maximum of every
+/// metric, followed by the seeds that left the most patients unassigned.
+pub fn print_report(settings: &MonteCarloSettings, runs: &[RunMetrics]) {
+    println!(
+        "Monte Carlo: {runs} scenarios solved with '{solver}', seeds {first}..{last}, bucket occupancy {min:.0}-100%",
+        runs = runs.len(),
+        solver = settings.solver,
+        first = settings.first_seed,
+        last = (settings.first_seed + settings.runs).saturating_sub(1),
+        min = settings.min_occupancy * 100.0,
+    );
+
+    println!(
+        "\n{:<28} {:>8} {:>8} {:>8} {:>8} {:>8}",
+        "metric", "mean", "p5", "p50", "p95", "max"
+    );
+    let metrics: [(&str, Metric, f64); 6] = [
+        ("patients", |run| run.patients as f64, 1.0),
+        ("unassigned patients", |run| run.unassigned as f64, 1.0),
+        (
+            "unassigned red/orange",
+            |run| run.critical_unassigned as f64,
+            1.0,
+        ),
+        ("network utilization (%)", |run| run.utilization, 100.0),
+        ("busiest hospital (%)", |run| run.busiest_hospital, 100.0),
+        ("expected survival (%)", |run| run.mean_survival, 100.0),
+    ];
+    for (name, metric, scale) in metrics {
+        let mut values: Vec<f64> = runs.iter().map(|run| metric(run) * scale).collect();
+        values.sort_by(f64::total_cmp);
+        let mean = values.iter().sum::<f64>() / values.len().max(1) as f64;
+        println!(
+            "{name:<28} {mean:>8.2} {:>8.2} {:>8.2} {:>8.2} {:>8.2}",
+            percentile(&values, 0.05),
+            percentile(&values, 0.50),
+            percentile(&values, 0.95),
+            percentile(&values, 1.0),
+        );
+    }
+
+    let failing = runs.iter().filter(|run| run.unassigned > 0).count();
+    println!(
+        "\n{failing} of {total} scenarios ({percent:.1}%) left at least one patient without a bed",
+        total = runs.len(),
+        percent = failing as f64 * 100.0 / runs.len().max(1) as f64,
+    );
+    let mut worst: Vec<&RunMetrics> = runs.iter().filter(|run| run.unassigned > 0).collect();
+    worst.sort_by_key(|run| {
+        (
+            std::cmp::Reverse(run.critical_unassigned),
+            std::cmp::Reverse(run.unassigned),
+        )
+    });
+    for run in worst.iter().take(5) {
+        println!(
+            "- seed {seed}: {unassigned} of {patients} unassigned ({critical} red/orange)",
+            seed = run.seed,
+            unassigned = run.unassigned,
+            patients = run.patients,
+            critical = run.critical_unassigned,
+        );
+    }
+}
+
+/// Nearest-rank percentile of sorted values, 0 when there are none.
+pub fn percentile(sorted: &[f64], share: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (share * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn env_or<T: FromStr>(name: &str, default: T) -> Result<T> {
+    Ok(optional_env(name)?.unwrap_or(default))
+}
+
+fn optional_env<T: FromStr>(name: &str) -> Result<Option<T>> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| anyhow!("Invalid {name} value '{value}'")),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(e) => Err(anyhow!(e)),
+    }
+}
@@ -1,25 +1,30 @@
 use anyhow::Result;
-use entity::{patient, prelude::Patient};
+use entity::{
+    TriageLevel,
+    patient::{self, PatientStatus},
+    prelude::Patient,
+};
 use rand::Rng;
 use sea_orm::{
     ActiveModelTrait, ActiveValue::Set, DatabaseConnection, EntityTrait, PaginatorTrait,
     TransactionTrait,
 };
-use std::{fmt, str::FromStr};
-
-pub const TRIAGE_CONDITIONS: &[&str] = &[
-    "Emergência (Vermelho)",
-    "Muito Urgente (Laranja)",
-    "Urgente (Amarelo)",
-    "Pouco Urgente (Verde)",
-    "Não Urgente (Azul)",
-];
+use std::{fmt, ops::RangeInclusive, str::FromStr};
 
 const LAT_MIN: f64 = 38.70;
 const LAT_MAX: f64 = 38.80;
 const LON_MIN: f64 = -9.28;
 const LON_MAX: f64 = -9.12;
 
+/// How many patients one seeding run adds.
+pub const PATIENT_COUNT: RangeInclusive<usize> = 5..=10;
+
+pub struct PatientSeed {
+    pub condition: TriageLevel,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
 pub struct PatientSeedReport {
     pub inserted: usize,
     pub total: usize,
@@ -60,28 +65,42 @@ impl FromStr for PatientSeedMode {
     }
 }
 
-pub async fn seed(db: &DatabaseConnection, mode: PatientSeedMode) -> Result<PatientSeedReport> {
-    let mut txn = db.begin().await?;
+/// `count` patients with a uniformly random triage level and position.
+pub fn generate(count: usize, rng: &mut impl Rng) -> Vec<PatientSeed> {
+    (0..count)
+        .map(|_| {
+            let condition_idx = rng.random_range(0..TriageLevel::ALL.len());
+            PatientSeed {
+                condition: TriageLevel::ALL[condition_idx],
+                latitude: rng.random_range(LAT_MIN..LAT_MAX),
+                longitude: rng.random_range(LON_MIN..LON_MAX),
+            }
+        })
+        .collect()
+}
+
+pub async fn seed(
+    db: &DatabaseConnection,
+    mode: PatientSeedMode,
+    rng: &mut impl Rng,
+) -> Result<PatientSeedReport> {
+    let txn = db.begin().await?;
 
     if matches!(mode, PatientSeedMode::Reset) {
-        Patient::delete_many().exec(&mut txn).await?;
+        Patient::delete_many().exec(&txn).await?;
     }
 
-    let mut rng = rand::rng();
-    let patient_count = rng.random_range(5..=10);
-
-    for _ in 0..patient_count {
-        let condition_idx = rng.random_range(0..TRIAGE_CONDITIONS.len());
-        let latitude = rng.random_range(LAT_MIN..LAT_MAX);
-        let longitude = rng.random_range(LON_MIN..LON_MAX);
+    let patient_count = rng.random_range(PATIENT_COUNT);
 
+    for patient in generate(patient_count, rng) {
         patient::ActiveModel {
-            condition: Set(TRIAGE_CONDITIONS[condition_idx].to_string()),
-            latitude: Set(latitude),
-            longitude: Set(longitude),
+            condition: Set(patient.condition),
+            latitude: Set(patient.latitude),
+            longitude: Set(patient.longitude),
+            status: Set(PatientStatus::Reported),
             ..Default::default()
         }
-        .insert(&mut txn)
+        .insert(&txn)
         .await?;
     }
 
@@ -0,0 +1,53 @@
+use scripts::monte_carlo::{MonteCarloSettings, percentile, run};
+
+fn settings(first_seed: u64, runs: u64) -> MonteCarloSettings {
+    MonteCarloSettings {
+        runs,
+        first_seed,
+        patients: None,
+        min_occupancy: 0.8,
+        solver: "greedy".to_string(),
+    }
+}
+
+#[test]
+fn the_same_seeds_give_the_same_metrics() {
+    let first = run(&settings(42, 5)).unwrap();
+    let second = run(&settings(42, 5)).unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(
+        first.iter().map(|run| run.seed).collect::<Vec<_>>(),
+        [42, 43, 44, 45, 46]
+    );
+    // Each run depends on its own seed only, not on where the range starts.
+    assert_eq!(run(&settings(44, 1)).unwrap(), first[2..3]);
+    assert_ne!(first[0], first[1]);
+}
+
+#[test]
+fn unknown_solvers_are_rejected() {
+    let mut settings = settings(0, 1);
+    settings.solver = "simplex".to_string();
+
+    let err = run(&settings).err().unwrap();
+    assert!(
+        err.to_string().starts_with("Unknown SOLVER 'simplex'"),
+        "{err}"
+    );
+}
+
+#[test]
+fn percentile_uses_the_nearest_rank() {
+    let values = [1.0, 2.0, 3.0, 4.0];
+
+    assert_eq!(percentile(&[], 0.5), 0.0);
+    assert_eq!(percentile(&[], 1.0), 0.0);
+    assert_eq!(percentile(&[7.0], 0.05), 7.0);
+    assert_eq!(percentile(&values, 0.0), 1.0);
+    assert_eq!(percentile(&values, 0.25), 1.0);
+    assert_eq!(percentile(&values, 0.26), 2.0);
+    assert_eq!(percentile(&values, 0.5), 2.0);
+    assert_eq!(percentile(&values, 0.95), 4.0);
+    assert_eq!(percentile(&values, 1.0), 4.0);
+}
@@ -0,0 +1,54 @@
+use scripts::resolve_seed;
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn reads_the_seed_flag_and_removes_it() {
+    let mut separate = args(&["--seed", "42", "monte-carlo"]);
+    assert_eq!(resolve_seed(&mut separate, None).unwrap(), Some(42));
+    assert_eq!(separate, ["monte-carlo"]);
+
+    let mut joined = args(&["monte-carlo", "--seed=7"]);
+    assert_eq!(resolve_seed(&mut joined, None).unwrap(), Some(7));
+    assert_eq!(joined, ["monte-carlo"]);
+}
+
+#[test]
+fn the_flag_wins_over_the_environment() {
+    let mut flag = args(&["--seed", "42"]);
+    assert_eq!(resolve_seed(&mut flag, Some("9")).unwrap(), Some(42));
+
+    let mut none = args(&["monte-carlo"]);
+    assert_eq!(resolve_seed(&mut none, Some(" 9 ")).unwrap(), Some(9));
+    assert_eq!(none, ["monte-carlo"]);
+    assert_eq!(resolve_seed(&mut none, None).unwrap(), None);
+}
+
+#[test]
+fn rejects_missing_and_invalid_values() {
+    let error = |input: &[&str], env_seed: Option<&str>| {
+        resolve_seed(&mut args(input), env_seed)
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert_eq!(error(&["--seed"], None), "Expected a value after '--seed'");
+    assert_eq!(
+        error(&["--seedling"], None),
+        "Expected a value after '--seedling'"
+    );
+    assert_eq!(
+        error(&["--seed=-1"], None),
+        "The seed must be a non-negative whole number, got '-1'"
+    );
+    assert_eq!(
+        error(&["--seed="], None),
+        "The seed must be a non-negative whole number, got ''"
+    );
+    assert_eq!(
+        error(&[], Some("forty-two")),
+        "The seed must be a non-negative whole number, got 'forty-two'"
+    );
+}
@@ -0,0 +1,270 @@
+//! Request and response bodies of the HTTP API.
+//!
+//! Triage levels and patient statuses are serialized as their database
+//! values, e.g. `"red"` and `"in_transport"`.
+
+use crate::{
+    optimize::{Diagnostics, ObjectiveConfig},
+    runs::Decision,
+};
+use entity::{TriageLevel, hospital, hospital_speciality, patient, patient::PatientStatus};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct Hospital {
+    pub id: i32,
+    pub location_name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub bed_capacity: i32,
+}
+
+/// Body of `POST /hospitals` and `PUT /hospitals/{id}`.
+#[derive(Clone, Debug, PartialEq, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct HospitalInput {
+    pub location_name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub bed_capacity: i32,
+}
+
+/// One `hospital_speciality` row: the beds a hospital reserves for a triage level.
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct TriageBucket {
+    pub id: i32,
+    pub hospital_id: i32,
+    pub triage_level: TriageLevel,
+    pub waiting_time_seconds: i64,
+    pub beds_capacity: i32,
+}
+
+/// Body of `POST /triage-buckets` and `PUT /triage-buckets/{id}`.
+#[derive(Clone, Debug, PartialEq, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TriageBucketInput {
+    pub hospital_id: i32,
+    pub triage_level: TriageLevel,
+    pub waiting_time_seconds: i64,
+    pub beds_capacity: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct Patient {
+    pub id: i32,
+    pub condition: TriageLevel,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub status: PatientStatus,
+    #[schema(value_type = String, format = DateTime)]
+    pub reported_at: DateTimeWithTimeZone,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub admitted_at: Option<DateTimeWithTimeZone>,
+    pub assigned_hospital_id: Option<i32>,
+}
+
+/// Body of `POST /patients` and `PUT /patients/{id}`. A new patient defaults
+/// to `reported` now; an update keeps the stored `reported_at` when omitted.
+#[derive(Clone, Debug, PartialEq, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PatientInput {
+    pub condition: TriageLevel,
+    pub latitude: f64,
+    pub longitude: f64,
+    #[serde(default)]
+    pub status: Option<PatientStatus>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub reported_at: Option<DateTimeWithTimeZone>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub admitted_at: Option<DateTimeWithTimeZone>,
+    #[serde(default)]
+    pub assigned_hospital_id: Option<i32>,
+}
+
+/// Query string of `GET /triage-buckets`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TriageBucketFilter {
+    pub hospital_id: Option<i32>,
+}
+
+/// Query string of `GET /patients`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PatientFilter {
+    pub status: Option<PatientStatus>,
+}
+
+/// Body of `POST /optimize`. Every field is optional; `{}` solves the current
+/// snapshot with the server's defaults.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct OptimizeRequest {
+    /// Backend name, as for the `SOLVER` variable.
+    pub solver: Option<String>,
+    /// Heuristic whose plan seeds the solver, as for `WARM_START`.
+    pub warm_start: Option<String>,
+    /// Survival curve overrides in the `SURVIVAL_CURVES` format.
+    pub survival_curves: Option<String>,
+    /// Criteria for the `multi-objective` backend, in the `OBJECTIVES_FILE`
+    /// layout. Selects that backend when `solver` is omitted.
+    pub objectives: Option<ObjectiveConfig>,
+    /// Store the run in `optimization_runs`; defaults to `true`.
+    pub persist: Option<bool>,
+    /// Re-optimize incrementally from this stored run: keep its assignments,
+    /// place new patients and change at most `max_disruption` of the rest.
+    /// Cannot be combined with `solver`, `warm_start` or `objectives`.
+    pub previous_run_id: Option<i32>,
+    /// Earlier assignments an incremental run may change; defaults to `0`.
+    pub max_disruption: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct OptimizeResponse {
+    /// Id of the stored run, `None` when `persist` was `false`.
+    pub run_id: Option<i32>,
+    pub input_hash: String,
+    pub diagnostics: SolveDiagnostics,
+    /// One entry per pending patient.
+    pub assignments: Vec<Assignment>,
+    /// Patients whose assignment in `previous_run_id` changed, for
+    /// incremental runs.
+    pub disrupted: Option<Vec<i32>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct SolveDiagnostics {
+    pub backend: String,
+    pub status: String,
+    /// Expected lives saved.
+    pub objective_value: f64,
+    pub objective_bound: Option<f64>,
+    pub objectives: Vec<ObjectiveValue>,
+    pub wall_time_ms: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct ObjectiveValue {
+    pub criterion: String,
+    pub value: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct Assignment {
+    pub patient_id: i32,
+    pub triage_level: TriageLevel,
+    pub assigned: bool,
+    pub hospital_id: Option<i32>,
+    pub triage_bucket_id: Option<i32>,
+    pub time_to_treatment_seconds: Option<f64>,
+    pub survival_probability: f64,
+    pub unassigned_reason: Option<String>,
+}
+
+/// `plan` event of `GET /plans/stream`: the plan for the current snapshot
+/// and how it differs from the previous one.
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct PlanUpdate {
+    /// Increases by one per published plan; also the event id.
+    pub sequence: u64,
+    pub input_hash: String,
+    pub diagnostics: SolveDiagnostics,
+    /// One entry per pending patient.
+    pub assignments: Vec<Assignment>,
+    /// Patients whose assignment differs from the previous plan. Empty for
+    /// the first plan.
+    pub changes: Vec<AssignmentChange>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct AssignmentChange {
+    pub patient_id: i32,
+    /// `None` when the patient was not pending in the previous plan.
+    pub previous: Option<Assignment>,
+    /// `None` when the patient is no longer pending.
+    pub current: Option<Assignment>,
+}
+
+/// Body of every error response.
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+impl From<hospital::Model> for Hospital {
+    fn from(model: hospital::Model) -> Self {
+        Hospital {
+            id: model.id,
+            location_name: model.location_name,
+            latitude: model.latitude,
+            longitude: model.longitude,
+            bed_capacity: model.bed_capacity,
+        }
+    }
+}
+
+impl From<hospital_speciality::Model> for TriageBucket {
+    fn from(model: hospital_speciality::Model) -> Self {
+        TriageBucket {
+            id: model.id,
+            hospital_id: model.hospital_id,
+            triage_level: model.triage_level,
+            waiting_time_seconds: model.waiting_time_seconds,
+            beds_capacity: model.beds_capacity,
+        }
+    }
+}
+
+impl From<patient::Model> for Patient {
+    fn from(model: patient::Model) -> Self {
+        Patient {
+            id: model.id,
+            condition: model.condition,
+            latitude: model.latitude,
+            longitude: model.longitude,
+            status: model.status,
+            reported_at: model.reported_at,
+            admitted_at: model.admitted_at,
+            assigned_hospital_id: model.assigned_hospital_id,
+        }
+    }
+}
+
+impl From<&Diagnostics> for SolveDiagnostics {
+    fn from(diagnostics: &Diagnostics) -> Self {
+        SolveDiagnostics {
+            backend: diagnostics.backend.to_string(),
+            status: diagnostics.status.label().to_string(),
+            objective_value: diagnostics.objective_value,
+            objective_bound: diagnostics.objective_bound,
+            objectives: diagnostics
+                .objectives
+                .iter()
+                .map(|objective| ObjectiveValue {
+                    criterion: objective.criterion.name().to_string(),
+                    value: objective.value,
+                })
+                .collect(),
+            wall_time_ms: diagnostics.wall_time.as_millis() as u64,
+        }
+    }
+}
+
+impl From<Decision> for Assignment {
+    fn from(decision: Decision) -> Self {
+        Assignment {
+            patient_id: decision.patient_id,
+            triage_level: decision.triage_level,
+            assigned: decision.bucket.is_some(),
+            hospital_id: decision.bucket.as_ref().map(|bucket| bucket.hospital_id),
+            triage_bucket_id: decision.bucket.as_ref().map(|bucket| bucket.speciality_id),
+            time_to_treatment_seconds: decision.time_to_treatment_seconds,
+            survival_probability: decision.survival_probability,
+            unassigned_reason: decision.unassigned_reason,
+        }
+    }
+}
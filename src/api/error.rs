@@ -0,0 +1,105 @@
+use super::dto::ErrorBody;
+use crate::optimize::{ProblemError, SolveError};
+use axum::{
+    Json,
+    extract::rejection::{JsonRejection, PathRejection, QueryRejection},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use sea_orm::DbErr;
+use std::fmt;
+
+/// Failure of an API call, rendered as `{"error": "<message>"}`.
+#[derive(Debug)]
+pub enum ApiError {
+    NotFound {
+        resource: &'static str,
+        id: i32,
+    },
+    BadRequest(String),
+    Database(DbErr),
+    /// The stored rows do not form a valid optimization problem.
+    Problem(ProblemError),
+    Solve {
+        backend: &'static str,
+        source: SolveError,
+    },
+    Internal(String),
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::NotFound { .. } => StatusCode::NOT_FOUND,
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Database(_)
+            | ApiError::Problem(_)
+            | ApiError::Solve { .. }
+            | ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::NotFound { resource, id } => write!(f, "{resource} {id} not found"),
+            ApiError::BadRequest(message) | ApiError::Internal(message) => f.write_str(message),
+            ApiError::Database(err) => write!(f, "database error: {err}"),
+            ApiError::Problem(err) => write!(f, "invalid optimization input: {err}"),
+            ApiError::Solve { backend, source } => {
+                write!(f, "the '{backend}' backend failed: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ApiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ApiError::Database(err) => Some(err),
+            ApiError::Problem(err) => Some(err),
+            ApiError::Solve { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<DbErr> for ApiError {
+    fn from(err: DbErr) -> Self {
+        ApiError::Database(err)
+    }
+}
+
+impl From<ProblemError> for ApiError {
+    fn from(err: ProblemError) -> Self {
+        ApiError::Problem(err)
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        ApiError::BadRequest(rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        ApiError::BadRequest(rejection.body_text())
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        ApiError::BadRequest(rejection.body_text())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorBody {
+            error: self.to_string(),
+        };
+        (self.status(), Json(body)).into_response()
+    }
+}
@@ -0,0 +1,153 @@
+use super::{
+    ApiError, ApiJson, ApiPath, SharedState,
+    dto::{ErrorBody, Hospital, HospitalInput},
+    validate_coordinates,
+};
+use axum::{Json, extract::State, http::StatusCode};
+use entity::{hospital, prelude::Hospital as HospitalEntity};
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{Set, Unchanged},
+    EntityTrait, ModelTrait, QueryOrder,
+};
+
+#[utoipa::path(
+    get,
+    path = "/hospitals",
+    tag = "hospitals",
+    responses((status = 200, description = "Hospitals ordered by id", body = [Hospital]))
+)]
+pub async fn list(State(state): State<SharedState>) -> Result<Json<Vec<Hospital>>, ApiError> {
+    let hospitals = HospitalEntity::find()
+        .order_by_asc(hospital::Column::Id)
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(hospitals.into_iter().map(Hospital::from).collect()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/hospitals/{id}",
+    tag = "hospitals",
+    params(("id" = i32, Path, description = "Hospital id")),
+    responses(
+        (status = 200, body = Hospital),
+        (status = 404, description = "Hospital not found", body = ErrorBody),
+    )
+)]
+pub async fn get(
+    State(state): State<SharedState>,
+    ApiPath(id): ApiPath<i32>,
+) -> Result<Json<Hospital>, ApiError> {
+    Ok(Json(find(&state, id).await?.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/hospitals",
+    tag = "hospitals",
+    request_body = HospitalInput,
+    responses(
+        (status = 201, body = Hospital),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+    )
+)]
+pub async fn create(
+    State(state): State<SharedState>,
+    ApiJson(input): ApiJson<HospitalInput>,
+) -> Result<(StatusCode, Json<Hospital>), ApiError> {
+    validate(&input)?;
+
+    let model = hospital::ActiveModel {
+        location_name: Set(input.location_name),
+        latitude: Set(input.latitude),
+        longitude: Set(input.longitude),
+        bed_capacity: Set(input.bed_capacity),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+    state.plans.invalidate();
+
+    Ok((StatusCode::CREATED, Json(model.into())))
+}
+
+#[utoipa::path(
+    put,
+    path = "/hospitals/{id}",
+    tag = "hospitals",
+    params(("id" = i32, Path, description = "Hospital id")),
+    request_body = HospitalInput,
+    responses(
+        (status = 200, body = Hospital),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 404, description = "Hospital not found", body = ErrorBody),
+    )
+)]
+pub async fn update(
+    State(state): State<SharedState>,
+    ApiPath(id): ApiPath<i32>,
+    ApiJson(input): ApiJson<HospitalInput>,
+) -> Result<Json<Hospital>, ApiError> {
+    validate(&input)?;
+    find(&state, id).await?;
+
+    let model = hospital::ActiveModel {
+        id: Unchanged(id),
+        location_name: Set(input.location_name),
+        latitude: Set(input.latitude),
+        longitude: Set(input.longitude),
+        bed_capacity: Set(input.bed_capacity),
+    }
+    .update(&state.db)
+    .await?;
+    state.plans.invalidate();
+
+    Ok(Json(model.into()))
+}
+
+/// Also deletes the hospital's triage buckets and occupancy records.
+#[utoipa::path(
+    delete,
+    path = "/hospitals/{id}",
+    tag = "hospitals",
+    params(("id" = i32, Path, description = "Hospital id")),
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 404, description = "Hospital not found", body = ErrorBody),
+    )
+)]
+pub async fn delete(
+    State(state): State<SharedState>,
+    ApiPath(id): ApiPath<i32>,
+) -> Result<StatusCode, ApiError> {
+    find(&state, id).await?.delete(&state.db).await?;
+    state.plans.invalidate();
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn find(state: &SharedState, id: i32) -> Result<hospital::Model, ApiError> {
+    HospitalEntity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or(ApiError::NotFound {
+            resource: "hospital",
+            id,
+        })
+}
+
+fn validate(input: &HospitalInput) -> Result<(), ApiError> {
+    if input.location_name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "location_name must not be empty".to_string(),
+        ));
+    }
+    if input.bed_capacity < 0 {
+        return Err(ApiError::BadRequest(format!(
+            "bed_capacity must not be negative, got {}",
+            input.bed_capacity
+        )));
+    }
+    validate_coordinates(input.latitude, input.longitude)
+}
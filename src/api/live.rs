@@ -0,0 +1,227 @@
+//! Live assignment plans for dispatchers.
+//!
+//! A background task re-solves the current snapshot with the server's
+//! default backend whenever the API changes a hospital, triage bucket or
+//! patient, and every refresh interval to pick up bed occupancy written
+//! elsewhere. With [`AppState::max_disruption`](super::AppState) set, it
+//! re-optimizes incrementally from the last plan instead. A plan is
+//! published only when the snapshot's input hash changed, and
+//! `GET /plans/stream` pushes each one as a Server-Sent Event. Live plans
+//! are not stored as optimization runs.
+
+use super::{
+    ApiError, SharedState,
+    dto::{Assignment, AssignmentChange, OptimizeRequest, PlanUpdate},
+    optimize::{snapshot, solve},
+};
+use crate::{
+    optimize::{AssignmentOutcome, AssignmentPlan, BucketRef, PatientAssignment, reoptimize},
+    runs,
+};
+use axum::{
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use chrono::{Local, Timelike};
+use futures_util::{Stream, StreamExt, stream};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::{Notify, watch};
+
+/// Latest published plan, shared by the replan task and the subscribers.
+pub struct PlanFeed {
+    latest: watch::Sender<Option<Arc<PlanUpdate>>>,
+    stale: Notify,
+    closed: watch::Sender<bool>,
+}
+
+impl Default for PlanFeed {
+    fn default() -> Self {
+        PlanFeed {
+            latest: watch::Sender::new(None),
+            stale: Notify::new(),
+            closed: watch::Sender::new(false),
+        }
+    }
+}
+
+impl PlanFeed {
+    /// Asks the replan task to solve the snapshot again.
+    pub fn invalidate(&self) {
+        self.stale.notify_one();
+    }
+
+    /// Stops the replan task and ends every open stream, so a graceful
+    /// shutdown does not wait on subscribers.
+    pub fn close(&self) {
+        self.closed.send_replace(true);
+    }
+
+    pub fn latest(&self) -> Option<Arc<PlanUpdate>> {
+        self.latest.borrow().clone()
+    }
+
+    /// The latest plan, then every new one, until the feed is closed.
+    pub fn updates(&self) -> impl Stream<Item = Arc<PlanUpdate>> + Send + use<> {
+        let mut latest = self.latest.subscribe();
+        latest.mark_changed();
+        let closed = self.closed.subscribe();
+
+        stream::unfold((latest, closed), |(mut latest, mut closed)| async move {
+            loop {
+                tokio::select! {
+                    _ = closed.wait_for(|closed| *closed) => return None,
+                    changed = latest.changed() => changed.ok()?,
+                }
+                let update = latest.borrow_and_update().clone();
+                if let Some(update) = update {
+                    return Some((update, (latest, closed)));
+                }
+            }
+        })
+    }
+}
+
+/// Keeps [`PlanFeed`] up to date until it is closed.
+pub async fn replan(state: SharedState, refresh: Duration) {
+    let mut closed = state.plans.closed.subscribe();
+    let mut ticks = tokio::time::interval(refresh);
+
+    loop {
+        tokio::select! {
+            _ = closed.wait_for(|closed| *closed) => return,
+            _ = ticks.tick() => {}
+            _ = state.plans.stale.notified() => {}
+        }
+
+        if let Err(err) = publish_next_plan(&state).await {
+            eprintln!("Live replan failed: {err}");
+        }
+    }
+}
+
+/// Solves the current snapshot and publishes the plan, unless the snapshot
+/// has the latest plan's input hash. Returns whether a plan was published.
+pub async fn publish_next_plan(state: &SharedState) -> Result<bool, ApiError> {
+    let previous = state.plans.latest();
+    let problem = snapshot(state, Local::now().hour())
+        .await?
+        .with_survival(state.survival.clone());
+    let input_hash = runs::input_hash(&problem);
+    if previous
+        .as_ref()
+        .is_some_and(|previous| previous.input_hash == input_hash)
+    {
+        return Ok(false);
+    }
+
+    let incremental = state
+        .max_disruption
+        .zip(previous.as_deref().map(previous_plan));
+
+    let solving = state.clone();
+    let (problem, solution) = tokio::task::spawn_blocking(move || {
+        let solution = match incremental {
+            Some((max_disruption, plan)) => reoptimize(&problem, &plan, max_disruption).solution,
+            None => solve(&solving, &problem, &OptimizeRequest::default())?,
+        };
+        Ok::<_, ApiError>((problem, solution))
+    })
+    .await
+    .map_err(|err| ApiError::Internal(format!("the solver task failed: {err}")))??;
+
+    let assignments: Vec<Assignment> = runs::plan_decisions(&problem, &solution.plan)
+        .into_iter()
+        .map(Assignment::from)
+        .collect();
+    let changes = previous
+        .as_deref()
+        .map(|previous| changes(&previous.assignments, &assignments))
+        .unwrap_or_default();
+
+    let update = PlanUpdate {
+        sequence: previous.map_or(1, |previous| previous.sequence + 1),
+        input_hash,
+        diagnostics: (&solution.diagnostics).into(),
+        assignments,
+        changes,
+    };
+    state.plans.latest.send_replace(Some(Arc::new(update)));
+    Ok(true)
+}
+
+/// The assigned patients of a published plan.
+fn previous_plan(update: &PlanUpdate) -> AssignmentPlan {
+    let assignments = update
+        .assignments
+        .iter()
+        .filter_map(|assignment| {
+            let bucket = BucketRef {
+                hospital_id: assignment.hospital_id?,
+                speciality_id: assignment.triage_bucket_id?,
+                triage_level: assignment.triage_level,
+            };
+            Some(PatientAssignment {
+                patient_id: assignment.patient_id,
+                outcome: AssignmentOutcome::Assigned(bucket),
+            })
+        })
+        .collect();
+
+    AssignmentPlan { assignments }
+}
+
+/// Patients that moved to another bucket, became pending or stopped being
+/// pending, ordered by patient id.
+pub fn changes(previous: &[Assignment], current: &[Assignment]) -> Vec<AssignmentChange> {
+    let mut before: HashMap<i32, &Assignment> = previous
+        .iter()
+        .map(|assignment| (assignment.patient_id, assignment))
+        .collect();
+
+    let mut changes: Vec<AssignmentChange> = current
+        .iter()
+        .filter_map(|assignment| {
+            let previous = before.remove(&assignment.patient_id);
+            let moved = previous
+                .is_none_or(|previous| previous.triage_bucket_id != assignment.triage_bucket_id);
+            moved.then(|| AssignmentChange {
+                patient_id: assignment.patient_id,
+                previous: previous.cloned(),
+                current: Some(assignment.clone()),
+            })
+        })
+        .collect();
+    changes.extend(before.into_values().map(|previous| AssignmentChange {
+        patient_id: previous.patient_id,
+        previous: Some(previous.clone()),
+        current: None,
+    }));
+    changes.sort_by_key(|change| change.patient_id);
+
+    changes
+}
+
+/// Streams the latest plan, then every new one, as `plan` events.
+#[utoipa::path(
+    get,
+    path = "/plans/stream",
+    tag = "optimization",
+    responses((
+        status = 200,
+        description = "Server-Sent Events named `plan`, one per published plan",
+        content_type = "text/event-stream",
+        body = PlanUpdate,
+    ))
+)]
+pub async fn stream(
+    State(state): State<SharedState>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let events = state.plans.updates().map(|update| {
+        Event::default()
+            .event("plan")
+            .id(update.sequence.to_string())
+            .json_data(&*update)
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
@@ -0,0 +1,119 @@
+//! HTTP API for the dispatch UI.
+//!
+//! CRUD endpoints for hospitals, triage buckets and patients over the
+//! `entity` tables, plus `POST /optimize`, which solves the current snapshot
+//! and returns the assignment plan as JSON, and `GET /plans/stream`, which
+//! pushes the [`live`] plan as it changes. Bodies are the [`dto`] types and
+//! errors are `{"error": "<message>"}` with a matching status code. The
+//! [`openapi`] document describing all of it is served at `/openapi.json`.
+
+pub mod dto;
+mod error;
+mod hospitals;
+pub mod live;
+pub mod openapi;
+pub(crate) mod optimize;
+pub(crate) mod patients;
+mod triage_buckets;
+
+pub use error::ApiError;
+pub use live::PlanFeed;
+
+use crate::{
+    optimize::{ObjectiveConfig, SolverRegistry, SurvivalModel},
+    travel::TravelTimes,
+};
+use axum::{
+    Json, Router,
+    extract::{FromRequest, FromRequestParts},
+    routing::{get, post},
+};
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+pub struct AppState {
+    pub db: DatabaseConnection,
+    pub registry: SolverRegistry,
+    /// Backend used when a request names none.
+    pub default_solver: String,
+    /// Criteria of the registered `multi-objective` backend, recorded with
+    /// its runs.
+    pub objectives: Option<ObjectiveConfig>,
+    /// Curves used when a request has no `survival_curves`.
+    pub survival: SurvivalModel,
+    pub travel_times: TravelTimes,
+    pub plans: PlanFeed,
+    /// When set, the live plan is re-optimized incrementally from the last
+    /// published one, changing at most this many earlier assignments.
+    pub max_disruption: Option<usize>,
+}
+
+pub type SharedState = Arc<AppState>;
+
+/// [`axum::Json`] whose rejections use the API's error body.
+#[derive(FromRequest)]
+#[from_request(via(axum::Json), rejection(ApiError))]
+pub struct ApiJson<T>(pub T);
+
+/// [`axum::extract::Query`] whose rejections use the API's error body.
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(ApiError))]
+pub struct ApiQuery<T>(pub T);
+
+/// [`axum::extract::Path`] whose rejections use the API's error body.
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Path), rejection(ApiError))]
+pub struct ApiPath<T>(pub T);
+
+pub fn router(state: SharedState) -> Router {
+    Router::new()
+        .route("/hospitals", get(hospitals::list).post(hospitals::create))
+        .route(
+            "/hospitals/{id}",
+            get(hospitals::get)
+                .put(hospitals::update)
+                .delete(hospitals::delete),
+        )
+        .route(
+            "/triage-buckets",
+            get(triage_buckets::list).post(triage_buckets::create),
+        )
+        .route(
+            "/triage-buckets/{id}",
+            get(triage_buckets::get)
+                .put(triage_buckets::update)
+                .delete(triage_buckets::delete),
+        )
+        .route("/patients", get(patients::list).post(patients::create))
+        .route(
+            "/patients/{id}",
+            get(patients::get)
+                .put(patients::update)
+                .delete(patients::delete),
+        )
+        .route("/optimize", post(optimize::optimize))
+        .route("/plans/stream", get(live::stream))
+        .route("/openapi.json", get(openapi_json))
+        .with_state(state)
+}
+
+/// This API's [`openapi`] document.
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    tag = "documentation",
+    responses((status = 200, description = "The OpenAPI document", content_type = "application/json"))
+)]
+async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(openapi::ApiDoc::openapi())
+}
+
+fn validate_coordinates(latitude: f64, longitude: f64) -> Result<(), ApiError> {
+    if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+        return Err(ApiError::BadRequest(format!(
+            "coordinates ({latitude}, {longitude}) are out of range"
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! OpenAPI 3 description of the HTTP API, generated from the handlers and
+//! the [`dto`](super::dto) types.
+
+use super::{hospitals, live, optimize, patients, triage_buckets};
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "hospitopt",
+        description = "Hospital, triage bucket and patient records, and patient-to-hospital assignment plans."
+    ),
+    paths(
+        hospitals::list,
+        hospitals::create,
+        hospitals::get,
+        hospitals::update,
+        hospitals::delete,
+        triage_buckets::list,
+        triage_buckets::create,
+        triage_buckets::get,
+        triage_buckets::update,
+        triage_buckets::delete,
+        patients::list,
+        patients::create,
+        patients::get,
+        patients::update,
+        patients::delete,
+        optimize::optimize,
+        live::stream,
+        super::openapi_json,
+    ),
+    tags(
+        (name = "hospitals"),
+        (name = "triage buckets", description = "Beds a hospital reserves for one triage level"),
+        (name = "patients"),
+        (name = "optimization", description = "Patient-to-hospital assignment plans"),
+        (name = "documentation"),
+    )
+)]
+pub struct ApiDoc;
+
+/// The document as pretty-printed JSON, e.g. for writing to disk.
+pub fn to_json() -> String {
+    ApiDoc::openapi()
+        .to_pretty_json()
+        .expect("the OpenAPI document serializes to JSON")
+}
@@ -0,0 +1,201 @@
+use super::{
+    ApiError, ApiJson, AppState, SharedState,
+    dto::{Assignment, ErrorBody, OptimizeRequest, OptimizeResponse},
+};
+use crate::{
+    occupancy,
+    optimize::{
+        self, MultiObjectiveSolver, Problem, Solution, Solver, SolverRegistry, SurvivalModel,
+        multi_objective,
+    },
+    runs,
+};
+use axum::{Json, extract::State};
+use chrono::{Local, Timelike, Utc};
+use entity::prelude::{Hospital, HospitalSpeciality, Patient};
+use sea_orm::EntityTrait;
+use serde_json::json;
+
+/// Solves the current snapshot (pending patients, live bed availability)
+/// and returns the plan, storing it as an optimization run unless
+/// `persist` is `false`.
+#[utoipa::path(
+    post,
+    path = "/optimize",
+    tag = "optimization",
+    request_body = OptimizeRequest,
+    responses(
+        (status = 200, description = "The assignment plan", body = OptimizeResponse),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "The snapshot is invalid or the backend failed", body = ErrorBody),
+    )
+)]
+pub async fn optimize(
+    State(state): State<SharedState>,
+    ApiJson(request): ApiJson<OptimizeRequest>,
+) -> Result<Json<OptimizeResponse>, ApiError> {
+    Ok(Json(run(&state, request).await?))
+}
+
+/// Solves the current snapshot as `request` asks.
+pub(crate) async fn run(
+    state: &SharedState,
+    request: OptimizeRequest,
+) -> Result<OptimizeResponse, ApiError> {
+    let survival = match &request.survival_curves {
+        Some(curves) => curves
+            .parse::<SurvivalModel>()
+            .map_err(|err| ApiError::BadRequest(err.to_string()))?,
+        None => state.survival.clone(),
+    };
+    if let Some(config) = &request.objectives {
+        config
+            .validate()
+            .map_err(|err| ApiError::BadRequest(format!("invalid objectives: {err}")))?;
+    }
+
+    let previous = match request.previous_run_id {
+        Some(_)
+            if request.solver.is_some()
+                || request.warm_start.is_some()
+                || request.objectives.is_some() =>
+        {
+            return Err(ApiError::BadRequest(
+                "previous_run_id selects the incremental backend and cannot be combined with \
+                 solver, warm_start or objectives"
+                    .to_string(),
+            ));
+        }
+        Some(run_id) => Some(runs::load_plan(&state.db, run_id).await?.ok_or(
+            ApiError::NotFound {
+                resource: "optimization run",
+                id: run_id,
+            },
+        )?),
+        None if request.max_disruption.is_some() => {
+            return Err(ApiError::BadRequest(
+                "max_disruption only applies with previous_run_id".to_string(),
+            ));
+        }
+        None => None,
+    };
+
+    let departure_hour = Local::now().hour();
+    let problem = snapshot(state, departure_hour)
+        .await?
+        .with_survival(survival);
+
+    let solving = state.clone();
+    let solving_request = request.clone();
+    let (problem, solution, disrupted) = tokio::task::spawn_blocking(move || match previous {
+        Some(previous) => {
+            let max_disruption = solving_request.max_disruption.unwrap_or(0);
+            let result = optimize::reoptimize(&problem, &previous, max_disruption);
+            Ok((problem, result.solution, Some(result.disrupted)))
+        }
+        None => {
+            let solution = solve(&solving, &problem, &solving_request)?;
+            Ok::<_, ApiError>((problem, solution, None))
+        }
+    })
+    .await
+    .map_err(|err| ApiError::Internal(format!("the solver task failed: {err}")))??;
+
+    let input_hash = runs::input_hash(&problem);
+    let decisions = runs::plan_decisions(&problem, &solution.plan);
+
+    let run_id = if request.persist.unwrap_or(true) {
+        let mut parameters = json!({
+            "mode": "assignment",
+            "solver": solution.diagnostics.backend,
+            "warm_start": request.warm_start,
+            "survival_curves": runs::survival_parameters(&problem.survival),
+            "travel_times": { "source": state.travel_times.source(), "departure_hour": departure_hour },
+        });
+        if solution.diagnostics.backend == multi_objective::BACKEND {
+            let objectives = request.objectives.as_ref().or(state.objectives.as_ref());
+            parameters["objectives"] = json!(objectives.cloned().unwrap_or_default());
+        }
+        if let Some(disrupted) = &disrupted {
+            parameters["mode"] = json!("incremental");
+            parameters["previous_run_id"] = json!(request.previous_run_id);
+            parameters["max_disruption"] = json!(request.max_disruption.unwrap_or(0));
+            parameters["disrupted"] = json!(disrupted);
+        }
+        let run = runs::record(
+            &state.db,
+            input_hash.clone(),
+            &solution.diagnostics,
+            parameters,
+            &decisions,
+        )
+        .await?;
+        Some(run.id)
+    } else {
+        None
+    };
+
+    Ok(OptimizeResponse {
+        run_id,
+        input_hash,
+        diagnostics: (&solution.diagnostics).into(),
+        assignments: decisions.into_iter().map(Assignment::from).collect(),
+        disrupted,
+    })
+}
+
+/// The pending patients against live bed availability, with travel times
+/// for ambulances leaving at `departure_hour`.
+pub(super) async fn snapshot(state: &AppState, departure_hour: u32) -> Result<Problem, ApiError> {
+    let hospitals = Hospital::find().all(&state.db).await?;
+    let specialities = HospitalSpeciality::find().all(&state.db).await?;
+    let patients = Patient::find_pending().all(&state.db).await?;
+    let availability = occupancy::available_beds_at(&state.db, Utc::now().fixed_offset()).await?;
+    let specialities = occupancy::live_specialities(&specialities, &availability);
+
+    let problem = Problem::from_models(&hospitals, &specialities, &patients)?
+        .with_occupied_beds(&availability);
+    Ok(state.travel_times.attach(problem, departure_hour)?)
+}
+
+pub(super) fn solve(
+    state: &AppState,
+    problem: &Problem,
+    request: &OptimizeRequest,
+) -> Result<Solution, ApiError> {
+    let custom = request.objectives.clone().map(MultiObjectiveSolver::new);
+    let solver: &dyn Solver = match (&request.solver, &custom) {
+        (None, Some(solver)) => solver,
+        (Some(name), Some(solver)) if solver.name().eq_ignore_ascii_case(name.trim()) => solver,
+        (name, _) => lookup(
+            &state.registry,
+            name.as_deref().unwrap_or(&state.default_solver),
+        )?,
+    };
+
+    let result = match &request.warm_start {
+        Some(name) => {
+            let heuristic = lookup(&state.registry, name)?;
+            let warm_start = heuristic.solve(problem).map_err(|source| ApiError::Solve {
+                backend: heuristic.name(),
+                source,
+            })?;
+            solver.solve_from(problem, &warm_start.plan)
+        }
+        None => solver.solve(problem),
+    };
+
+    result.map_err(|source| ApiError::Solve {
+        backend: solver.name(),
+        source,
+    })
+}
+
+fn lookup<'a>(registry: &'a SolverRegistry, name: &str) -> Result<&'a dyn Solver, ApiError> {
+    registry.get(name).ok_or_else(|| {
+        ApiError::BadRequest(format!(
+            "unknown backend '{name}', available: {}",
+            registry.names().collect::<Vec<_>>().join(", ")
+        ))
+    })
+}
@@ -0,0 +1,179 @@
+use super::{
+    ApiError, ApiJson, ApiPath, ApiQuery, SharedState,
+    dto::{ErrorBody, Patient, PatientFilter, PatientInput},
+    validate_coordinates,
+};
+use axum::{Json, extract::State, http::StatusCode};
+use chrono::Utc;
+use entity::{
+    patient::{self, PatientStatus},
+    prelude::{Hospital, Patient as PatientEntity},
+};
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{Set, Unchanged},
+    ColumnTrait, EntityTrait, ModelTrait, QueryFilter, QueryOrder,
+};
+
+#[utoipa::path(
+    get,
+    path = "/patients",
+    tag = "patients",
+    params(PatientFilter),
+    responses((status = 200, description = "Patients ordered by id", body = [Patient]))
+)]
+pub async fn list(
+    State(state): State<SharedState>,
+    ApiQuery(filter): ApiQuery<PatientFilter>,
+) -> Result<Json<Vec<Patient>>, ApiError> {
+    let mut query = PatientEntity::find().order_by_asc(patient::Column::Id);
+    if let Some(status) = filter.status {
+        query = query.filter(patient::Column::Status.eq(status));
+    }
+    let patients = query.all(&state.db).await?;
+
+    Ok(Json(patients.into_iter().map(Patient::from).collect()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/patients/{id}",
+    tag = "patients",
+    params(("id" = i32, Path, description = "Patient id")),
+    responses(
+        (status = 200, body = Patient),
+        (status = 404, description = "Patient not found", body = ErrorBody),
+    )
+)]
+pub async fn get(
+    State(state): State<SharedState>,
+    ApiPath(id): ApiPath<i32>,
+) -> Result<Json<Patient>, ApiError> {
+    Ok(Json(find(&state, id).await?.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/patients",
+    tag = "patients",
+    request_body = PatientInput,
+    responses(
+        (status = 201, body = Patient),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+    )
+)]
+pub async fn create(
+    State(state): State<SharedState>,
+    ApiJson(input): ApiJson<PatientInput>,
+) -> Result<(StatusCode, Json<Patient>), ApiError> {
+    let model = insert(&state, input).await?;
+    Ok((StatusCode::CREATED, Json(model.into())))
+}
+
+/// Validates and stores a new patient.
+pub(crate) async fn insert(
+    state: &SharedState,
+    input: PatientInput,
+) -> Result<patient::Model, ApiError> {
+    validate(state, &input).await?;
+
+    let model = patient::ActiveModel {
+        condition: Set(input.condition),
+        latitude: Set(input.latitude),
+        longitude: Set(input.longitude),
+        status: Set(input.status.unwrap_or(PatientStatus::Reported)),
+        reported_at: Set(input
+            .reported_at
+            .unwrap_or_else(|| Utc::now().fixed_offset())),
+        admitted_at: Set(input.admitted_at),
+        assigned_hospital_id: Set(input.assigned_hospital_id),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+    state.plans.invalidate();
+
+    Ok(model)
+}
+
+#[utoipa::path(
+    put,
+    path = "/patients/{id}",
+    tag = "patients",
+    params(("id" = i32, Path, description = "Patient id")),
+    request_body = PatientInput,
+    responses(
+        (status = 200, body = Patient),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 404, description = "Patient not found", body = ErrorBody),
+    )
+)]
+pub async fn update(
+    State(state): State<SharedState>,
+    ApiPath(id): ApiPath<i32>,
+    ApiJson(input): ApiJson<PatientInput>,
+) -> Result<Json<Patient>, ApiError> {
+    let existing = find(&state, id).await?;
+    validate(&state, &input).await?;
+
+    let model = patient::ActiveModel {
+        id: Unchanged(id),
+        condition: Set(input.condition),
+        latitude: Set(input.latitude),
+        longitude: Set(input.longitude),
+        status: Set(input.status.unwrap_or(existing.status)),
+        reported_at: Set(input.reported_at.unwrap_or(existing.reported_at)),
+        admitted_at: Set(input.admitted_at),
+        assigned_hospital_id: Set(input.assigned_hospital_id),
+    }
+    .update(&state.db)
+    .await?;
+    state.plans.invalidate();
+
+    Ok(Json(model.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/patients/{id}",
+    tag = "patients",
+    params(("id" = i32, Path, description = "Patient id")),
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 404, description = "Patient not found", body = ErrorBody),
+    )
+)]
+pub async fn delete(
+    State(state): State<SharedState>,
+    ApiPath(id): ApiPath<i32>,
+) -> Result<StatusCode, ApiError> {
+    find(&state, id).await?.delete(&state.db).await?;
+    state.plans.invalidate();
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(crate) async fn find(state: &SharedState, id: i32) -> Result<patient::Model, ApiError> {
+    PatientEntity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or(ApiError::NotFound {
+            resource: "patient",
+            id,
+        })
+}
+
+async fn validate(state: &SharedState, input: &PatientInput) -> Result<(), ApiError> {
+    validate_coordinates(input.latitude, input.longitude)?;
+
+    if let Some(hospital_id) = input.assigned_hospital_id
+        && Hospital::find_by_id(hospital_id)
+            .one(&state.db)
+            .await?
+            .is_none()
+    {
+        return Err(ApiError::BadRequest(format!(
+            "hospital {hospital_id} does not exist"
+        )));
+    }
+    Ok(())
+}
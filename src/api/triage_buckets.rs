@@ -0,0 +1,170 @@
+use super::{
+    ApiError, ApiJson, ApiPath, ApiQuery, SharedState,
+    dto::{ErrorBody, TriageBucket, TriageBucketFilter, TriageBucketInput},
+};
+use axum::{Json, extract::State, http::StatusCode};
+use entity::{
+    hospital_speciality,
+    prelude::{Hospital, HospitalSpeciality},
+};
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{Set, Unchanged},
+    ColumnTrait, EntityTrait, ModelTrait, QueryFilter, QueryOrder,
+};
+
+#[utoipa::path(
+    get,
+    path = "/triage-buckets",
+    tag = "triage buckets",
+    params(TriageBucketFilter),
+    responses((status = 200, description = "Triage buckets ordered by id", body = [TriageBucket]))
+)]
+pub async fn list(
+    State(state): State<SharedState>,
+    ApiQuery(filter): ApiQuery<TriageBucketFilter>,
+) -> Result<Json<Vec<TriageBucket>>, ApiError> {
+    let mut query = HospitalSpeciality::find().order_by_asc(hospital_speciality::Column::Id);
+    if let Some(hospital_id) = filter.hospital_id {
+        query = query.filter(hospital_speciality::Column::HospitalId.eq(hospital_id));
+    }
+    let buckets = query.all(&state.db).await?;
+
+    Ok(Json(buckets.into_iter().map(TriageBucket::from).collect()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/triage-buckets/{id}",
+    tag = "triage buckets",
+    params(("id" = i32, Path, description = "Triage bucket id")),
+    responses(
+        (status = 200, body = TriageBucket),
+        (status = 404, description = "Triage bucket not found", body = ErrorBody),
+    )
+)]
+pub async fn get(
+    State(state): State<SharedState>,
+    ApiPath(id): ApiPath<i32>,
+) -> Result<Json<TriageBucket>, ApiError> {
+    Ok(Json(find(&state, id).await?.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/triage-buckets",
+    tag = "triage buckets",
+    request_body = TriageBucketInput,
+    responses(
+        (status = 201, body = TriageBucket),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+    )
+)]
+pub async fn create(
+    State(state): State<SharedState>,
+    ApiJson(input): ApiJson<TriageBucketInput>,
+) -> Result<(StatusCode, Json<TriageBucket>), ApiError> {
+    validate(&state, &input).await?;
+
+    let model = hospital_speciality::ActiveModel {
+        hospital_id: Set(input.hospital_id),
+        triage_level: Set(input.triage_level),
+        waiting_time_seconds: Set(input.waiting_time_seconds),
+        beds_capacity: Set(input.beds_capacity),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+    state.plans.invalidate();
+
+    Ok((StatusCode::CREATED, Json(model.into())))
+}
+
+#[utoipa::path(
+    put,
+    path = "/triage-buckets/{id}",
+    tag = "triage buckets",
+    params(("id" = i32, Path, description = "Triage bucket id")),
+    request_body = TriageBucketInput,
+    responses(
+        (status = 200, body = TriageBucket),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 404, description = "Triage bucket not found", body = ErrorBody),
+    )
+)]
+pub async fn update(
+    State(state): State<SharedState>,
+    ApiPath(id): ApiPath<i32>,
+    ApiJson(input): ApiJson<TriageBucketInput>,
+) -> Result<Json<TriageBucket>, ApiError> {
+    find(&state, id).await?;
+    validate(&state, &input).await?;
+
+    let model = hospital_speciality::ActiveModel {
+        id: Unchanged(id),
+        hospital_id: Set(input.hospital_id),
+        triage_level: Set(input.triage_level),
+        waiting_time_seconds: Set(input.waiting_time_seconds),
+        beds_capacity: Set(input.beds_capacity),
+    }
+    .update(&state.db)
+    .await?;
+    state.plans.invalidate();
+
+    Ok(Json(model.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/triage-buckets/{id}",
+    tag = "triage buckets",
+    params(("id" = i32, Path, description = "Triage bucket id")),
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 404, description = "Triage bucket not found", body = ErrorBody),
+    )
+)]
+pub async fn delete(
+    State(state): State<SharedState>,
+    ApiPath(id): ApiPath<i32>,
+) -> Result<StatusCode, ApiError> {
+    find(&state, id).await?.delete(&state.db).await?;
+    state.plans.invalidate();
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn find(state: &SharedState, id: i32) -> Result<hospital_speciality::Model, ApiError> {
+    HospitalSpeciality::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or(ApiError::NotFound {
+            resource: "triage bucket",
+            id,
+        })
+}
+
+async fn validate(state: &SharedState, input: &TriageBucketInput) -> Result<(), ApiError> {
+    if input.waiting_time_seconds < 0 {
+        return Err(ApiError::BadRequest(format!(
+            "waiting_time_seconds must not be negative, got {}",
+            input.waiting_time_seconds
+        )));
+    }
+    if input.beds_capacity < 0 {
+        return Err(ApiError::BadRequest(format!(
+            "beds_capacity must not be negative, got {}",
+            input.beds_capacity
+        )));
+    }
+    if Hospital::find_by_id(input.hospital_id)
+        .one(&state.db)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::BadRequest(format!(
+            "hospital {} does not exist",
+            input.hospital_id
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,339 @@
+//! Joint ambulance dispatch and hospital allocation.
+//!
+//! Decides at once which available ambulance picks up which patient and
+//! which hospital triage bucket receives them. Each ambulance serves at most
+//! one patient per decision. The model first serves as many patients as
+//! possible, weighting more urgent triage levels higher, and then minimizes
+//! urgency-weighted time to treatment: travel to the patient, travel to the
+//! hospital, and the bucket's `waiting_time_seconds`.
+//!
+//! Because that cost splits into an ambulance→patient part and a
+//! patient→bucket part, the problem is solved exactly as a minimum-cost flow
+//! source → ambulance → patient → bucket → sink. To keep it separable, the
+//! pickup leg uses each ambulance's own speed while the transport leg uses
+//! the problem's travel-time matrix (or the model's default speed).
+
+use crate::{
+    geo::{Coordinate, TravelTimeModel},
+    optimize::{
+        AssignmentOutcome, AssignmentPlan, BucketRef, PatientAssignment, Problem, ProblemError,
+        UnassignedReason, flow::FlowNetwork,
+    },
+};
+use entity::{
+    TriageLevel,
+    ambulance::{self, AmbulanceType, CrewStatus},
+    hospital, hospital_speciality, patient,
+};
+use std::fmt;
+
+/// Bonus, in weighted seconds, for serving one unit of urgency weight. It
+/// dwarfs any realistic time to treatment so coverage always comes first.
+const SERVICE_BONUS_SECONDS: i64 = 10_000_000;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DispatchParams {
+    pub travel: TravelTimeModel,
+    /// Local hour (0-23) the ambulances leave, for congestion factors.
+    pub departure_hour: u32,
+}
+
+/// An ambulance whose crew can take a new patient.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AmbulanceUnit {
+    pub id: i32,
+    pub call_sign: String,
+    pub ambulance_type: AmbulanceType,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DispatchProblem {
+    pub problem: Problem,
+    pub ambulances: Vec<AmbulanceUnit>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DispatchPlan {
+    /// One entry per patient, in [`Problem::patients`] order.
+    pub assignments: Vec<DispatchAssignment>,
+    /// Sum over dispatched patients of urgency weight × time to treatment.
+    pub weighted_time_to_treatment_seconds: i64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DispatchAssignment {
+    pub patient_id: i32,
+    pub outcome: DispatchOutcome,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DispatchOutcome {
+    Dispatched(Dispatch),
+    Unserved(UnservedReason),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dispatch {
+    pub ambulance_id: i32,
+    pub bucket: BucketRef,
+    pub pickup_seconds: i64,
+    pub transport_seconds: i64,
+    pub waiting_seconds: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnservedReason {
+    /// No hospital offers a bucket for the patient's triage level.
+    NoMatchingTriage,
+    /// Every matching bucket is already full.
+    NoBed,
+    /// Every available ambulance went to a more urgent or closer patient.
+    NoAmbulance,
+}
+
+impl UnservedReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            UnservedReason::NoMatchingTriage => "no matching triage bucket",
+            UnservedReason::NoBed => "no free bed",
+            UnservedReason::NoAmbulance => "no ambulance available",
+        }
+    }
+}
+
+impl fmt::Display for UnservedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl Dispatch {
+    pub fn time_to_treatment_seconds(&self) -> i64 {
+        self.pickup_seconds + self.transport_seconds + self.waiting_seconds
+    }
+}
+
+/// Relative cost of one second of delay for each triage level.
+pub fn urgency_weight(level: TriageLevel) -> i64 {
+    match level {
+        TriageLevel::Red => 16,
+        TriageLevel::Orange => 8,
+        TriageLevel::Yellow => 4,
+        TriageLevel::Green => 2,
+        TriageLevel::Blue => 1,
+    }
+}
+
+impl DispatchProblem {
+    /// Builds the dispatch input, keeping only ambulances whose crew is
+    /// available and which can carry at least one patient.
+    pub fn from_models(
+        hospitals: &[hospital::Model],
+        specialities: &[hospital_speciality::Model],
+        patients: &[patient::Model],
+        ambulances: &[ambulance::Model],
+    ) -> Result<Self, ProblemError> {
+        let problem = Problem::from_models(hospitals, specialities, patients)?;
+
+        let mut ambulances: Vec<AmbulanceUnit> = ambulances
+            .iter()
+            .filter(|ambulance| {
+                ambulance.crew_status == CrewStatus::Available && ambulance.capacity > 0
+            })
+            .map(|ambulance| AmbulanceUnit {
+                id: ambulance.id,
+                call_sign: ambulance.call_sign.clone(),
+                ambulance_type: ambulance.ambulance_type,
+                latitude: ambulance.latitude,
+                longitude: ambulance.longitude,
+            })
+            .collect();
+        ambulances.sort_by_key(|ambulance| ambulance.id);
+
+        Ok(DispatchProblem {
+            problem,
+            ambulances,
+        })
+    }
+
+    fn pickup_seconds(&self, ambulance: usize, patient: usize, params: &DispatchParams) -> i64 {
+        let ambulance = &self.ambulances[ambulance];
+        let patient = &self.problem.patients[patient];
+        params
+            .travel
+            .travel_seconds(
+                Coordinate::new(ambulance.latitude, ambulance.longitude),
+                Coordinate::new(patient.latitude, patient.longitude),
+                Some(ambulance.ambulance_type),
+                params.departure_hour,
+            )
+            .round() as i64
+    }
+
+    fn transport_seconds(&self, patient: usize, bucket: usize, params: &DispatchParams) -> i64 {
+        let seconds = self
+            .problem
+            .travel_seconds(patient, bucket)
+            .unwrap_or_else(|| {
+                let patient = &self.problem.patients[patient];
+                let hospital = self.problem.hospital_of(bucket);
+                params.travel.travel_seconds(
+                    Coordinate::new(patient.latitude, patient.longitude),
+                    Coordinate::new(hospital.latitude, hospital.longitude),
+                    None,
+                    params.departure_hour,
+                )
+            });
+        seconds.round() as i64
+    }
+}
+
+pub fn solve(dispatch: &DispatchProblem, params: &DispatchParams) -> DispatchPlan {
+    let problem = &dispatch.problem;
+    let mut network = FlowNetwork::default();
+    let source = network.add_node();
+    let sink = network.add_node();
+
+    let ambulance_nodes: Vec<usize> = dispatch
+        .ambulances
+        .iter()
+        .map(|_| {
+            let node = network.add_node();
+            network.add_arc(source, node, 1, 0);
+            node
+        })
+        .collect();
+
+    let bucket_nodes: Vec<usize> = problem
+        .buckets
+        .iter()
+        .map(|bucket| {
+            let node = network.add_node();
+            network.add_arc(node, sink, bucket.capacity as i64, 0);
+            node
+        })
+        .collect();
+
+    let mut pickup_arcs: Vec<(usize, usize, usize)> = Vec::new();
+    let mut transport_arcs: Vec<(usize, usize, usize)> = Vec::new();
+
+    for (patient_idx, patient) in problem.patients.iter().enumerate() {
+        let weight = urgency_weight(patient.condition);
+        let arrival = network.add_node();
+        let departure = network.add_node();
+        network.add_arc(arrival, departure, 1, -weight * SERVICE_BONUS_SECONDS);
+
+        for (ambulance_idx, node) in ambulance_nodes.iter().enumerate() {
+            let cost = weight * dispatch.pickup_seconds(ambulance_idx, patient_idx, params);
+            let arc = network.add_arc(*node, arrival, 1, cost);
+            pickup_arcs.push((patient_idx, ambulance_idx, arc));
+        }
+
+        for bucket in problem.candidate_buckets(patient_idx) {
+            let seconds = dispatch.transport_seconds(patient_idx, bucket, params)
+                + problem.buckets[bucket].waiting_time_seconds;
+            let arc = network.add_arc(departure, bucket_nodes[bucket], 1, weight * seconds);
+            transport_arcs.push((patient_idx, bucket, arc));
+        }
+    }
+
+    network.min_cost_flow(source, sink);
+
+    let mut ambulance_of = vec![None; problem.patients.len()];
+    for (patient, ambulance, arc) in pickup_arcs {
+        if network.flow(arc) > 0 {
+            ambulance_of[patient] = Some(ambulance);
+        }
+    }
+
+    let mut bucket_of = vec![None; problem.patients.len()];
+    for (patient, bucket, arc) in transport_arcs {
+        if network.flow(arc) > 0 {
+            bucket_of[patient] = Some(bucket);
+        }
+    }
+
+    let bed_plan = AssignmentPlan::from_choices(problem, &bucket_of);
+    let mut weighted_total = 0i64;
+
+    let assignments = bed_plan
+        .assignments
+        .into_iter()
+        .enumerate()
+        .map(|(patient, assignment)| {
+            let outcome = match (
+                assignment.outcome,
+                ambulance_of[patient],
+                bucket_of[patient],
+            ) {
+                (AssignmentOutcome::Assigned(bucket_ref), Some(ambulance), Some(bucket)) => {
+                    let dispatch_row = Dispatch {
+                        ambulance_id: dispatch.ambulances[ambulance].id,
+                        bucket: bucket_ref,
+                        pickup_seconds: dispatch.pickup_seconds(ambulance, patient, params),
+                        transport_seconds: dispatch.transport_seconds(patient, bucket, params),
+                        waiting_seconds: problem.buckets[bucket].waiting_time_seconds,
+                    };
+                    weighted_total += urgency_weight(problem.patients[patient].condition)
+                        * dispatch_row.time_to_treatment_seconds();
+                    DispatchOutcome::Dispatched(dispatch_row)
+                }
+                (AssignmentOutcome::Unassigned(UnassignedReason::NoMatchingTriage), _, _) => {
+                    DispatchOutcome::Unserved(UnservedReason::NoMatchingTriage)
+                }
+                (AssignmentOutcome::Unassigned(UnassignedReason::CapacityExhausted), _, _) => {
+                    DispatchOutcome::Unserved(UnservedReason::NoBed)
+                }
+                _ => DispatchOutcome::Unserved(UnservedReason::NoAmbulance),
+            };
+
+            DispatchAssignment {
+                patient_id: assignment.patient_id,
+                outcome,
+            }
+        })
+        .collect();
+
+    DispatchPlan {
+        assignments,
+        weighted_time_to_treatment_seconds: weighted_total,
+    }
+}
+
+impl DispatchPlan {
+    /// The hospital side of the plan, in the shape the bed-only solvers return.
+    pub fn to_assignment_plan(&self) -> AssignmentPlan {
+        AssignmentPlan {
+            assignments: self
+                .assignments
+                .iter()
+                .map(|assignment| PatientAssignment {
+                    patient_id: assignment.patient_id,
+                    outcome: match &assignment.outcome {
+                        DispatchOutcome::Dispatched(dispatch) => {
+                            AssignmentOutcome::Assigned(dispatch.bucket.clone())
+                        }
+                        DispatchOutcome::Unserved(UnservedReason::NoMatchingTriage) => {
+                            AssignmentOutcome::Unassigned(UnassignedReason::NoMatchingTriage)
+                        }
+                        DispatchOutcome::Unserved(UnservedReason::NoBed) => {
+                            AssignmentOutcome::Unassigned(UnassignedReason::CapacityExhausted)
+                        }
+                        DispatchOutcome::Unserved(UnservedReason::NoAmbulance) => {
+                            AssignmentOutcome::Unassigned(UnassignedReason::NotSelected)
+                        }
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    pub fn dispatched_count(&self) -> usize {
+        self.assignments
+            .iter()
+            .filter(|assignment| matches!(assignment.outcome, DispatchOutcome::Dispatched(_)))
+            .count()
+    }
+}
@@ -0,0 +1,184 @@
+//! Great-circle distances and travel-time estimates.
+//!
+//! [`TravelTimeModel`] turns straight-line distances into driving times using
+//! a detour factor, a cruising speed per vehicle type and an hourly
+//! congestion multiplier. [`TravelTimeMatrix`] holds the resulting dense
+//! patient × hospital table that the optimizer consumes.
+
+use crate::optimize::Problem;
+use entity::ambulance::AmbulanceType;
+use std::collections::HashMap;
+
+const EARTH_RADIUS_KM: f64 = 6_371.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Coordinate {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinate {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Coordinate {
+            latitude,
+            longitude,
+        }
+    }
+}
+
+pub fn haversine_km(from: Coordinate, to: Coordinate) -> f64 {
+    let d_lat = (to.latitude - from.latitude).to_radians();
+    let d_lon = (to.longitude - from.longitude).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + from.latitude.to_radians().cos()
+            * to.latitude.to_radians().cos()
+            * (d_lon / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Travel-time multiplier for each hour of the day (0-23, local time).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CongestionProfile {
+    pub hourly_factors: [f64; 24],
+}
+
+impl CongestionProfile {
+    /// No congestion at any hour.
+    pub fn free_flow() -> Self {
+        CongestionProfile {
+            hourly_factors: [1.0; 24],
+        }
+    }
+
+    /// Typical Lisbon weekday: morning and evening rush hours into and out
+    /// of the city, light traffic overnight.
+    pub fn lisbon_weekday() -> Self {
+        CongestionProfile {
+            hourly_factors: [
+                0.85, 0.85, 0.85, 0.85, 0.85, 0.9, // 00-05
+                1.1, 1.4, 1.6, 1.4, 1.15, 1.1, // 06-11
+                1.15, 1.15, 1.1, 1.15, 1.3, 1.5, // 12-17
+                1.6, 1.4, 1.15, 1.0, 0.95, 0.9, // 18-23
+            ],
+        }
+    }
+
+    pub fn factor_at(&self, hour: u32) -> f64 {
+        self.hourly_factors[(hour % 24) as usize]
+    }
+}
+
+impl Default for CongestionProfile {
+    fn default() -> Self {
+        CongestionProfile::free_flow()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TravelTimeModel {
+    /// Free-flow speed used when no vehicle type is given or it has no entry
+    /// in `vehicle_speeds_kmh`.
+    pub default_speed_kmh: f64,
+    pub vehicle_speeds_kmh: HashMap<AmbulanceType, f64>,
+    /// Ratio between road distance and great-circle distance.
+    pub detour_factor: f64,
+    pub congestion: CongestionProfile,
+}
+
+impl Default for TravelTimeModel {
+    fn default() -> Self {
+        TravelTimeModel {
+            default_speed_kmh: 40.0,
+            vehicle_speeds_kmh: HashMap::from([
+                (AmbulanceType::Bls, 45.0),
+                (AmbulanceType::Als, 50.0),
+            ]),
+            detour_factor: 1.3,
+            congestion: CongestionProfile::free_flow(),
+        }
+    }
+}
+
+impl TravelTimeModel {
+    pub fn speed_kmh(&self, vehicle: Option<AmbulanceType>) -> f64 {
+        vehicle
+            .and_then(|vehicle| self.vehicle_speeds_kmh.get(&vehicle).copied())
+            .unwrap_or(self.default_speed_kmh)
+    }
+
+    /// Estimated seconds to drive from `from` to `to` when departing at
+    /// `hour` (0-23, local time).
+    pub fn travel_seconds(
+        &self,
+        from: Coordinate,
+        to: Coordinate,
+        vehicle: Option<AmbulanceType>,
+        hour: u32,
+    ) -> f64 {
+        let road_km = haversine_km(from, to) * self.detour_factor;
+        road_km / self.speed_kmh(vehicle) * 3_600.0 * self.congestion.factor_at(hour)
+    }
+}
+
+/// Dense, row-major table of travel times in seconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TravelTimeMatrix {
+    rows: usize,
+    columns: usize,
+    seconds: Vec<f64>,
+}
+
+impl TravelTimeMatrix {
+    pub fn from_fn(rows: usize, columns: usize, mut cell: impl FnMut(usize, usize) -> f64) -> Self {
+        let mut seconds = Vec::with_capacity(rows * columns);
+        for row in 0..rows {
+            for column in 0..columns {
+                seconds.push(cell(row, column));
+            }
+        }
+
+        TravelTimeMatrix {
+            rows,
+            columns,
+            seconds,
+        }
+    }
+
+    /// Patient × hospital matrix, indexed like [`Problem::patients`] and
+    /// [`Problem::hospitals`].
+    pub fn patients_to_hospitals(
+        problem: &Problem,
+        model: &TravelTimeModel,
+        vehicle: Option<AmbulanceType>,
+        hour: u32,
+    ) -> Self {
+        TravelTimeMatrix::from_fn(
+            problem.patients.len(),
+            problem.hospitals.len(),
+            |patient, hospital| {
+                let patient = &problem.patients[patient];
+                let hospital = &problem.hospitals[hospital];
+                model.travel_seconds(
+                    Coordinate::new(patient.latitude, patient.longitude),
+                    Coordinate::new(hospital.latitude, hospital.longitude),
+                    vehicle,
+                    hour,
+                )
+            },
+        )
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        assert!(row < self.rows && column < self.columns);
+        self.seconds[row * self.columns + column]
+    }
+}
@@ -0,0 +1,169 @@
+//! Conversions between the protobuf messages and the entity and API types.
+
+use super::proto;
+use crate::api::{ApiError, dto};
+use chrono::DateTime;
+use entity::{TriageLevel, hospital, hospital_speciality, patient, patient::PatientStatus};
+use prost_types::Timestamp;
+use sea_orm::prelude::DateTimeWithTimeZone;
+
+impl From<TriageLevel> for proto::TriageLevel {
+    fn from(level: TriageLevel) -> Self {
+        match level {
+            TriageLevel::Red => proto::TriageLevel::Red,
+            TriageLevel::Orange => proto::TriageLevel::Orange,
+            TriageLevel::Yellow => proto::TriageLevel::Yellow,
+            TriageLevel::Green => proto::TriageLevel::Green,
+            TriageLevel::Blue => proto::TriageLevel::Blue,
+        }
+    }
+}
+
+impl From<PatientStatus> for proto::PatientStatus {
+    fn from(status: PatientStatus) -> Self {
+        match status {
+            PatientStatus::Reported => proto::PatientStatus::Reported,
+            PatientStatus::Dispatched => proto::PatientStatus::Dispatched,
+            PatientStatus::InTransport => proto::PatientStatus::InTransport,
+            PatientStatus::Admitted => proto::PatientStatus::Admitted,
+            PatientStatus::Discharged => proto::PatientStatus::Discharged,
+            PatientStatus::Deceased => proto::PatientStatus::Deceased,
+        }
+    }
+}
+
+/// The triage level of a request field; unspecified and unknown values are
+/// rejected.
+pub(super) fn triage_level(value: i32) -> Result<TriageLevel, ApiError> {
+    match proto::TriageLevel::try_from(value) {
+        Ok(proto::TriageLevel::Red) => Ok(TriageLevel::Red),
+        Ok(proto::TriageLevel::Orange) => Ok(TriageLevel::Orange),
+        Ok(proto::TriageLevel::Yellow) => Ok(TriageLevel::Yellow),
+        Ok(proto::TriageLevel::Green) => Ok(TriageLevel::Green),
+        Ok(proto::TriageLevel::Blue) => Ok(TriageLevel::Blue),
+        Ok(proto::TriageLevel::Unspecified) | Err(_) => Err(ApiError::BadRequest(format!(
+            "triage level {value} is not one of red, orange, yellow, green or blue"
+        ))),
+    }
+}
+
+pub(super) fn date_time(timestamp: Timestamp) -> Result<DateTimeWithTimeZone, ApiError> {
+    u32::try_from(timestamp.nanos)
+        .ok()
+        .and_then(|nanos| DateTime::from_timestamp(timestamp.seconds, nanos))
+        .map(|date_time| date_time.fixed_offset())
+        .ok_or_else(|| ApiError::BadRequest(format!("timestamp {timestamp} is out of range")))
+}
+
+fn timestamp(date_time: DateTimeWithTimeZone) -> Timestamp {
+    Timestamp {
+        seconds: date_time.timestamp(),
+        nanos: date_time.timestamp_subsec_nanos() as i32,
+    }
+}
+
+impl From<hospital::Model> for proto::Hospital {
+    fn from(model: hospital::Model) -> Self {
+        proto::Hospital {
+            id: model.id,
+            location_name: model.location_name,
+            latitude: model.latitude,
+            longitude: model.longitude,
+            bed_capacity: model.bed_capacity,
+        }
+    }
+}
+
+impl From<hospital_speciality::Model> for proto::TriageBucket {
+    fn from(model: hospital_speciality::Model) -> Self {
+        proto::TriageBucket {
+            id: model.id,
+            hospital_id: model.hospital_id,
+            triage_level: proto::TriageLevel::from(model.triage_level).into(),
+            waiting_time_seconds: model.waiting_time_seconds,
+            beds_capacity: model.beds_capacity,
+        }
+    }
+}
+
+impl From<patient::Model> for proto::Patient {
+    fn from(model: patient::Model) -> Self {
+        proto::Patient {
+            id: model.id,
+            condition: proto::TriageLevel::from(model.condition).into(),
+            latitude: model.latitude,
+            longitude: model.longitude,
+            status: proto::PatientStatus::from(model.status).into(),
+            reported_at: Some(timestamp(model.reported_at)),
+            admitted_at: model.admitted_at.map(timestamp),
+            assigned_hospital_id: model.assigned_hospital_id,
+        }
+    }
+}
+
+impl From<dto::OptimizeResponse> for proto::OptimizeResponse {
+    fn from(response: dto::OptimizeResponse) -> Self {
+        proto::OptimizeResponse {
+            run_id: response.run_id,
+            input_hash: response.input_hash,
+            diagnostics: Some(response.diagnostics.into()),
+            assignments: response.assignments.into_iter().map(Into::into).collect(),
+            disrupted: response.disrupted.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<dto::PlanUpdate> for proto::PlanUpdate {
+    fn from(update: dto::PlanUpdate) -> Self {
+        proto::PlanUpdate {
+            sequence: update.sequence,
+            input_hash: update.input_hash,
+            diagnostics: Some(update.diagnostics.into()),
+            assignments: update.assignments.into_iter().map(Into::into).collect(),
+            changes: update
+                .changes
+                .into_iter()
+                .map(|change| proto::AssignmentChange {
+                    patient_id: change.patient_id,
+                    previous: change.previous.map(Into::into),
+                    current: change.current.map(Into::into),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<dto::SolveDiagnostics> for proto::Diagnostics {
+    fn from(diagnostics: dto::SolveDiagnostics) -> Self {
+        proto::Diagnostics {
+            backend: diagnostics.backend,
+            status: diagnostics.status,
+            objective_value: diagnostics.objective_value,
+            objective_bound: diagnostics.objective_bound,
+            objectives: diagnostics
+                .objectives
+                .into_iter()
+                .map(|objective| proto::ObjectiveValue {
+                    criterion: objective.criterion,
+                    value: objective.value,
+                })
+                .collect(),
+            wall_time_ms: diagnostics.wall_time_ms,
+        }
+    }
+}
+
+impl From<dto::Assignment> for proto::Assignment {
+    fn from(assignment: dto::Assignment) -> Self {
+        proto::Assignment {
+            patient_id: assignment.patient_id,
+            triage_level: proto::TriageLevel::from(assignment.triage_level).into(),
+            assigned: assignment.assigned,
+            hospital_id: assignment.hospital_id,
+            triage_bucket_id: assignment.triage_bucket_id,
+            time_to_treatment_seconds: assignment.time_to_treatment_seconds,
+            survival_probability: assignment.survival_probability,
+            unassigned_reason: assignment.unassigned_reason,
+        }
+    }
+}
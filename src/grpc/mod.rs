@@ -0,0 +1,135 @@
+//! gRPC service for computer-aided-dispatch systems.
+//!
+//! `hospitopt.v1.Dispatch`, described by `proto/hospitopt/v1/dispatch.proto`,
+//! lists hospitals and triage buckets, registers incidents as reported
+//! patients and returns hospital recommendations: `Optimize` solves the
+//! current snapshot once, like `POST /optimize`, and `OptimizeStream` follows
+//! the [live plan](crate::api::live) like `GET /plans/stream`. It shares the
+//! HTTP API's state and validation.
+
+mod convert;
+pub mod proto;
+
+use crate::api::{self, ApiError, SharedState, dto};
+use entity::{hospital, hospital_speciality, prelude::*};
+use futures_util::{StreamExt, stream::BoxStream};
+use proto::dispatch_server::{Dispatch, DispatchServer};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use tonic::{Request, Response, Status};
+
+pub struct DispatchService {
+    state: SharedState,
+}
+
+/// The service, ready to add to a [`tonic::transport::Server`].
+pub fn service(state: SharedState) -> DispatchServer<DispatchService> {
+    DispatchServer::new(DispatchService { state })
+}
+
+#[tonic::async_trait]
+impl Dispatch for DispatchService {
+    async fn list_hospitals(
+        &self,
+        _request: Request<proto::ListHospitalsRequest>,
+    ) -> Result<Response<proto::ListHospitalsResponse>, Status> {
+        let hospitals = Hospital::find()
+            .order_by_asc(hospital::Column::Id)
+            .all(&self.state.db)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(Response::new(proto::ListHospitalsResponse {
+            hospitals: hospitals.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn list_triage_buckets(
+        &self,
+        request: Request<proto::ListTriageBucketsRequest>,
+    ) -> Result<Response<proto::ListTriageBucketsResponse>, Status> {
+        let mut query = HospitalSpeciality::find().order_by_asc(hospital_speciality::Column::Id);
+        if let Some(hospital_id) = request.into_inner().hospital_id {
+            query = query.filter(hospital_speciality::Column::HospitalId.eq(hospital_id));
+        }
+        let buckets = query.all(&self.state.db).await.map_err(ApiError::from)?;
+
+        Ok(Response::new(proto::ListTriageBucketsResponse {
+            triage_buckets: buckets.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_patient(
+        &self,
+        request: Request<proto::GetPatientRequest>,
+    ) -> Result<Response<proto::Patient>, Status> {
+        let patient = api::patients::find(&self.state, request.into_inner().id).await?;
+        Ok(Response::new(patient.into()))
+    }
+
+    async fn report_incident(
+        &self,
+        request: Request<proto::ReportIncidentRequest>,
+    ) -> Result<Response<proto::Patient>, Status> {
+        let request = request.into_inner();
+        let input = dto::PatientInput {
+            condition: convert::triage_level(request.condition)?,
+            latitude: request.latitude,
+            longitude: request.longitude,
+            status: None,
+            reported_at: request.reported_at.map(convert::date_time).transpose()?,
+            admitted_at: None,
+            assigned_hospital_id: None,
+        };
+        let patient = api::patients::insert(&self.state, input).await?;
+
+        Ok(Response::new(patient.into()))
+    }
+
+    async fn optimize(
+        &self,
+        request: Request<proto::OptimizeRequest>,
+    ) -> Result<Response<proto::OptimizeResponse>, Status> {
+        let request = request.into_inner();
+        let request = dto::OptimizeRequest {
+            solver: request.solver,
+            warm_start: request.warm_start,
+            survival_curves: request.survival_curves,
+            objectives: None,
+            persist: request.persist,
+            previous_run_id: request.previous_run_id,
+            max_disruption: request.max_disruption.map(|limit| limit as usize),
+        };
+        let response = api::optimize::run(&self.state, request).await?;
+
+        Ok(Response::new(response.into()))
+    }
+
+    type OptimizeStreamStream = BoxStream<'static, Result<proto::PlanUpdate, Status>>;
+
+    async fn optimize_stream(
+        &self,
+        _request: Request<proto::OptimizeStreamRequest>,
+    ) -> Result<Response<Self::OptimizeStreamStream>, Status> {
+        let updates = self
+            .state
+            .plans
+            .updates()
+            .map(|update| Ok((*update).clone().into()));
+
+        Ok(Response::new(updates.boxed()))
+    }
+}
+
+impl From<ApiError> for Status {
+    fn from(err: ApiError) -> Self {
+        let message = err.to_string();
+        match err {
+            ApiError::NotFound { .. } => Status::not_found(message),
+            ApiError::BadRequest(_) => Status::invalid_argument(message),
+            ApiError::Database(_)
+            | ApiError::Problem(_)
+            | ApiError::Solve { .. }
+            | ApiError::Internal(_) => Status::internal(message),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Messages of `proto/hospitopt/v1/dispatch.proto`, declared by hand like the
+//! PBF reader's, plus the generated `dispatch_server` and `dispatch_client`.
+//! `tests/grpc_proto.rs` checks their tags against the proto file.
+
+use prost_types::Timestamp;
+
+include!(concat!(env!("OUT_DIR"), "/hospitopt.v1.Dispatch.rs"));
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum TriageLevel {
+    Unspecified = 0,
+    Red = 1,
+    Orange = 2,
+    Yellow = 3,
+    Green = 4,
+    Blue = 5,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum PatientStatus {
+    Unspecified = 0,
+    Reported = 1,
+    Dispatched = 2,
+    InTransport = 3,
+    Admitted = 4,
+    Discharged = 5,
+    Deceased = 6,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Hospital {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub location_name: String,
+    #[prost(double, tag = "3")]
+    pub latitude: f64,
+    #[prost(double, tag = "4")]
+    pub longitude: f64,
+    #[prost(int32, tag = "5")]
+    pub bed_capacity: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TriageBucket {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub hospital_id: i32,
+    #[prost(enumeration = "TriageLevel", tag = "3")]
+    pub triage_level: i32,
+    #[prost(int64, tag = "4")]
+    pub waiting_time_seconds: i64,
+    #[prost(int32, tag = "5")]
+    pub beds_capacity: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Patient {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(enumeration = "TriageLevel", tag = "2")]
+    pub condition: i32,
+    #[prost(double, tag = "3")]
+    pub latitude: f64,
+    #[prost(double, tag = "4")]
+    pub longitude: f64,
+    #[prost(enumeration = "PatientStatus", tag = "5")]
+    pub status: i32,
+    #[prost(message, optional, tag = "6")]
+    pub reported_at: Option<Timestamp>,
+    #[prost(message, optional, tag = "7")]
+    pub admitted_at: Option<Timestamp>,
+    #[prost(int32, optional, tag = "8")]
+    pub assigned_hospital_id: Option<i32>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListHospitalsRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListHospitalsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub hospitals: Vec<Hospital>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListTriageBucketsRequest {
+    #[prost(int32, optional, tag = "1")]
+    pub hospital_id: Option<i32>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListTriageBucketsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub triage_buckets: Vec<TriageBucket>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetPatientRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ReportIncidentRequest {
+    #[prost(enumeration = "TriageLevel", tag = "1")]
+    pub condition: i32,
+    #[prost(double, tag = "2")]
+    pub latitude: f64,
+    #[prost(double, tag = "3")]
+    pub longitude: f64,
+    #[prost(message, optional, tag = "4")]
+    pub reported_at: Option<Timestamp>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct OptimizeRequest {
+    #[prost(string, optional, tag = "1")]
+    pub solver: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub warm_start: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub survival_curves: Option<String>,
+    #[prost(bool, optional, tag = "4")]
+    pub persist: Option<bool>,
+    #[prost(int32, optional, tag = "5")]
+    pub previous_run_id: Option<i32>,
+    #[prost(uint32, optional, tag = "6")]
+    pub max_disruption: Option<u32>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct OptimizeResponse {
+    #[prost(int32, optional, tag = "1")]
+    pub run_id: Option<i32>,
+    #[prost(string, tag = "2")]
+    pub input_hash: String,
+    #[prost(message, optional, tag = "3")]
+    pub diagnostics: Option<Diagnostics>,
+    #[prost(message, repeated, tag = "4")]
+    pub assignments: Vec<Assignment>,
+    #[prost(int32, repeated, tag = "5")]
+    pub disrupted: Vec<i32>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct OptimizeStreamRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PlanUpdate {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    #[prost(string, tag = "2")]
+    pub input_hash: String,
+    #[prost(message, optional, tag = "3")]
+    pub diagnostics: Option<Diagnostics>,
+    #[prost(message, repeated, tag = "4")]
+    pub assignments: Vec<Assignment>,
+    #[prost(message, repeated, tag = "5")]
+    pub changes: Vec<AssignmentChange>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Diagnostics {
+    #[prost(string, tag = "1")]
+    pub backend: String,
+    #[prost(string, tag = "2")]
+    pub status: String,
+    #[prost(double, tag = "3")]
+    pub objective_value: f64,
+    #[prost(double, optional, tag = "4")]
+    pub objective_bound: Option<f64>,
+    #[prost(message, repeated, tag = "5")]
+    pub objectives: Vec<ObjectiveValue>,
+    #[prost(uint64, tag = "6")]
+    pub wall_time_ms: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ObjectiveValue {
+    #[prost(string, tag = "1")]
+    pub criterion: String,
+    #[prost(double, tag = "2")]
+    pub value: f64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Assignment {
+    #[prost(int32, tag = "1")]
+    pub patient_id: i32,
+    #[prost(enumeration = "TriageLevel", tag = "2")]
+    pub triage_level: i32,
+    #[prost(bool, tag = "3")]
+    pub assigned: bool,
+    #[prost(int32, optional, tag = "4")]
+    pub hospital_id: Option<i32>,
+    #[prost(int32, optional, tag = "5")]
+    pub triage_bucket_id: Option<i32>,
+    #[prost(double, optional, tag = "6")]
+    pub time_to_treatment_seconds: Option<f64>,
+    #[prost(double, tag = "7")]
+    pub survival_probability: f64,
+    #[prost(string, optional, tag = "8")]
+    pub unassigned_reason: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AssignmentChange {
+    #[prost(int32, tag = "1")]
+    pub patient_id: i32,
+    #[prost(message, optional, tag = "2")]
+    pub previous: Option<Assignment>,
+    #[prost(message, optional, tag = "3")]
+    pub current: Option<Assignment>,
+}
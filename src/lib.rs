@@ -0,0 +1,12 @@
+pub mod api;
+pub mod dispatch;
+pub mod geo;
+pub mod grpc;
+pub mod occupancy;
+pub mod optimize;
+#[cfg(feature = "osm")]
+pub mod routing;
+pub mod runs;
+pub mod scenario;
+pub mod simulation;
+pub mod travel;
@@ -1,27 +1,477 @@
-use anyhow::{Context, Result};
-use entity::prelude::*;
-use sea_orm::{Database, DatabaseConnection, DbErr, EntityTrait};
+use anyhow::{Context, Result, anyhow};
+use chrono::{Local, Timelike, Utc};
+use entity::{ambulance, hospital, hospital_speciality, patient, prelude::*};
+#[cfg(feature = "osm")]
+use hospitopt_rs::routing::{RoadGraph, RoutingProfile};
+use hospitopt_rs::{
+    api::{self, AppState, PlanFeed},
+    dispatch::{self, DispatchOutcome, DispatchParams, DispatchPlan, DispatchProblem},
+    grpc,
+    occupancy::{self, BedAvailability},
+    optimize::{
+        AssignmentOutcome, AssignmentPlan, DEFAULT_SOLVER, Diagnostics, MultiObjectiveSolver,
+        ObjectiveConfig, Problem, Solution, SolveStatus, Solver, SolverRegistry, SurvivalModel,
+        multi_objective, objective,
+    },
+    runs,
+    scenario::{self, Scenario, ScenarioParameters},
+    simulation::{self, SimulationConfig, SimulationReport},
+    travel::TravelTimes,
+};
+use sea_orm::{Database, DatabaseConnection, EntityTrait};
+use serde_json::json;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tonic::transport::server::TcpIncoming;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None => optimize_once(&connect().await?).await,
+        Some("serve") => serve(connect().await?).await,
+        Some("openapi") => write_openapi(args.get(1).map(String::as_str)),
+        Some("export-scenario") => {
+            export_scenario(&connect().await?, args.get(1).map(String::as_str)).await
+        }
+        Some("replay") => match args.get(1) {
+            Some(path) => replay(path),
+            None => Err(anyhow!("Usage: hospitopt-rs replay SCENARIO")),
+        },
+        Some("simulate") => {
+            let scenario = match args.get(1) {
+                Some(path) => Scenario::load(path)
+                    .with_context(|| format!("Failed to load scenario '{path}'"))?,
+                None => scenario::export(&connect().await?, Local::now().fixed_offset())
+                    .await
+                    .context("Failed to read the snapshot")?,
+            };
+            simulate(&scenario)
+        }
+        Some(other) => Err(anyhow!(
+            "Unknown command '{other}'. Expected 'serve', 'openapi [PATH]', 'export-scenario [PATH]', 'replay SCENARIO', 'simulate [SCENARIO]' or no command."
+        )),
+    }
+}
+
+async fn connect() -> Result<DatabaseConnection> {
     let database_url =
         std::env::var("DATABASE_URL").context("DATABASE_URL environment variable is not set")?;
 
-    let db = Database::connect(&database_url)
+    Database::connect(&database_url)
         .await
-        .context("Failed to connect to the database")?;
+        .context("Failed to connect to the database")
+}
 
-    print_hospitals(&db).await?;
-    print_hospital_specialities(&db).await?;
-    print_patients(&db).await?;
+/// Writes the HTTP API's OpenAPI document to `path`, or stdout without one.
+fn write_openapi(path: Option<&str>) -> Result<()> {
+    let document = api::openapi::to_json();
+    match path {
+        Some(path) => std::fs::write(path, document + "\n")
+            .with_context(|| format!("Failed to write the OpenAPI document to '{path}'")),
+        None => {
+            println!("{document}");
+            Ok(())
+        }
+    }
+}
 
-    Ok(())
+/// Serves the HTTP API on `BIND_ADDRESS` and the gRPC service on
+/// `GRPC_ADDRESS` until interrupted.
+async fn serve(db: DatabaseConnection) -> Result<()> {
+    let address = optional_env("BIND_ADDRESS")?.unwrap_or_else(|| "127.0.0.1:8080".to_string());
+    let grpc_address =
+        optional_env("GRPC_ADDRESS")?.unwrap_or_else(|| "127.0.0.1:50051".to_string());
+    let objectives = objectives_file()?;
+    let registry = solver_registry(objectives.as_ref());
+    let default_solver = optional_env("SOLVER")?.unwrap_or_else(|| DEFAULT_SOLVER.to_string());
+    lookup_solver(&registry, "SOLVER", &default_solver)?;
+
+    let state = AppState {
+        db,
+        registry,
+        default_solver,
+        objectives,
+        survival: survival_model()?,
+        travel_times: load_travel_times()?,
+        plans: PlanFeed::default(),
+        max_disruption: max_disruption()?,
+    };
+    let state = Arc::new(state);
+    tokio::spawn(api::live::replan(state.clone(), plan_refresh()?));
+
+    let listener = tokio::net::TcpListener::bind(&address)
+        .await
+        .with_context(|| format!("Failed to bind BIND_ADDRESS '{address}'"))?;
+    let grpc_listener = tokio::net::TcpListener::bind(&grpc_address)
+        .await
+        .with_context(|| format!("Failed to bind GRPC_ADDRESS '{grpc_address}'"))?;
+    println!("Listening on http://{address} (HTTP) and {grpc_address} (gRPC)");
+
+    let (shutdown, _) = tokio::sync::watch::channel(false);
+    let stopped = |mut receiver: tokio::sync::watch::Receiver<bool>| async move {
+        receiver.wait_for(|stopped| *stopped).await.ok();
+    };
+
+    let http = axum::serve(listener, api::router(state.clone()))
+        .with_graceful_shutdown(stopped(shutdown.subscribe()));
+    let grpc = tonic::transport::Server::builder()
+        .add_service(grpc::service(state.clone()))
+        .serve_with_incoming_shutdown(
+            TcpIncoming::from(grpc_listener),
+            stopped(shutdown.subscribe()),
+        );
+    tokio::spawn(async move {
+        tokio::signal::ctrl_c().await.ok();
+        state.plans.close();
+        shutdown.send_replace(true);
+    });
+
+    let (http, grpc) = tokio::join!(http, grpc);
+    http.context("The HTTP server failed")?;
+    grpc.context("The gRPC server failed")
 }
 
-async fn print_hospitals(db: &DatabaseConnection) -> Result<(), DbErr> {
+/// Solves the current snapshot once, prints the plan and stores the run.
+async fn optimize_once(db: &DatabaseConnection) -> Result<()> {
+    let parameters = with_env(ScenarioParameters::default())?;
+    let dispatch = is_dispatch(&parameters)?;
+
     let hospitals = Hospital::find().all(db).await?;
+    let specialities = HospitalSpeciality::find().all(db).await?;
+    let patients = Patient::find_pending().all(db).await?;
+
+    print_hospitals(&hospitals);
+    print_hospital_specialities(&specialities);
+    print_patients(&patients);
+
+    let now = Utc::now().fixed_offset();
+    let availability = occupancy::available_beds_at(db, now)
+        .await
+        .context("Failed to load bed occupancy")?;
+    print_availability(&availability);
+
+    let snapshot = Snapshot {
+        specialities: occupancy::live_specialities(&specialities, &availability),
+        availability,
+        hospitals,
+        patients,
+        ambulances: if dispatch {
+            Ambulance::find().all(db).await?
+        } else {
+            Vec::new()
+        },
+        departure_hour: Local::now().hour(),
+    };
+    let run = solve_snapshot(&snapshot, &parameters)?;
+
+    let stored = runs::record(
+        db,
+        run.input_hash,
+        &run.diagnostics,
+        run.parameters,
+        &run.decisions,
+    )
+    .await
+    .context("Failed to store the optimization run")?;
+    println!("\nStored as optimization run #{}", stored.id);
+
+    Ok(())
+}
+
+/// Solves a scenario file like the database snapshot it describes, without
+/// storing the run.
+fn replay(path: &str) -> Result<()> {
+    let scenario =
+        Scenario::load(path).with_context(|| format!("Failed to load scenario '{path}'"))?;
+    let parameters = with_env(scenario.parameters.clone())?;
+    let dispatch = is_dispatch(&parameters)?;
+
+    println!(
+        "\n== Scenario '{path}' taken at {taken_at} ==",
+        taken_at = scenario.taken_at,
+    );
+    if let Some(description) = &scenario.description {
+        println!("{description}");
+    }
+    let snapshot = Snapshot {
+        hospitals: scenario.hospitals(),
+        specialities: scenario.specialities(),
+        availability: scenario.availability(),
+        patients: scenario.patients(),
+        ambulances: if dispatch {
+            scenario.ambulances()
+        } else {
+            Vec::new()
+        },
+        departure_hour: scenario.departure_hour(),
+    };
+    print_hospitals(&snapshot.hospitals);
+    print_hospital_specialities(&snapshot.specialities);
+    print_patients(&snapshot.patients);
+
+    let run = solve_snapshot(&snapshot, &parameters)?;
+    println!("\nInput hash {}", run.input_hash);
+
+    Ok(())
+}
+
+/// Writes the database snapshot and the parameters a run would use now to
+/// `path`, or stdout as TOML without one.
+async fn export_scenario(db: &DatabaseConnection, path: Option<&str>) -> Result<()> {
+    let mut scenario = scenario::export(db, Local::now().fixed_offset())
+        .await
+        .context("Failed to read the snapshot")?;
+    let parameters = with_env(ScenarioParameters::default())?;
+    is_dispatch(&parameters)?;
+    scenario.parameters = ScenarioParameters {
+        mode: parameters.mode.or_else(|| Some("assignment".to_string())),
+        solver: parameters
+            .solver
+            .or_else(|| Some(DEFAULT_SOLVER.to_string())),
+        departure_hour: scenario.parameters.departure_hour,
+        ..parameters
+    };
+
+    match path {
+        Some(path) => scenario
+            .save(path)
+            .with_context(|| format!("Failed to write the scenario to '{path}'")),
+        None => {
+            print!("{}", scenario.to_toml()?);
+            Ok(())
+        }
+    }
+}
+
+/// Plays out a shift from `scenario` with the backend its parameters select
+/// and prints the metrics.
+fn simulate(scenario: &Scenario) -> Result<()> {
+    let parameters = with_env(scenario.parameters.clone())?;
+    if is_dispatch(&parameters)? {
+        return Err(anyhow!(
+            "The simulation assigns beds only; unset OPTIMIZATION_MODE=dispatch"
+        ));
+    }
+    let registry = solver_registry(parameters.objectives.as_ref());
+    let solver = lookup_solver(
+        &registry,
+        "SOLVER",
+        parameters.solver.as_deref().unwrap_or(DEFAULT_SOLVER),
+    )?;
+    let survival = parse_survival(parameters.survival_curves.as_deref())?;
+    let config = simulation_config()?;
+
+    println!(
+        "\n== Simulating {hours}h from {hour}:00 with {rate} arrivals per hour (seed {seed}) ==",
+        hours = config.duration.as_secs_f64() / 3_600.0,
+        hour = scenario.departure_hour(),
+        rate = config.arrivals_per_hour,
+        seed = config.seed,
+    );
+    let report = simulation::simulate(scenario, solver, &load_travel_times()?, &survival, &config)
+        .context("The simulation failed")?;
+    print_simulation(&report);
+
+    Ok(())
+}
+
+/// `SIMULATION_HOURS` (12), `ARRIVALS_PER_HOUR` (8) and `SIMULATION_SEED`
+/// (random) over the library defaults.
+fn simulation_config() -> Result<SimulationConfig> {
+    let positive = |name: &str, default: f64| -> Result<f64> {
+        let Some(value) = optional_env(name)? else {
+            return Ok(default);
+        };
+        match value.trim().parse::<f64>() {
+            Ok(number) if number > 0.0 && number.is_finite() => Ok(number),
+            _ => Err(anyhow!("{name} must be a positive number, got '{value}'")),
+        }
+    };
+    let defaults = SimulationConfig::default();
+    let seed = match optional_env("SIMULATION_SEED")? {
+        Some(seed) => seed.trim().parse().map_err(|_| {
+            anyhow!("SIMULATION_SEED must be a non-negative whole number, got '{seed}'")
+        })?,
+        None => rand::random(),
+    };
+
+    Ok(SimulationConfig {
+        duration: Duration::from_secs_f64(
+            positive(
+                "SIMULATION_HOURS",
+                defaults.duration.as_secs_f64() / 3_600.0,
+            )? * 3_600.0,
+        ),
+        arrivals_per_hour: positive("ARRIVALS_PER_HOUR", defaults.arrivals_per_hour)?,
+        seed,
+        ..defaults
+    })
+}
+
+/// Rows to solve, with live bed availability as the buckets' capacity.
+struct Snapshot {
+    hospitals: Vec<hospital::Model>,
+    specialities: Vec<hospital_speciality::Model>,
+    /// Occupancy behind the live capacities, for the occupancy criteria.
+    availability: Vec<BedAvailability>,
+    patients: Vec<patient::Model>,
+    /// Only loaded in dispatch mode.
+    ambulances: Vec<ambulance::Model>,
+    departure_hour: u32,
+}
+
+/// A solved snapshot, in the shape `optimization_runs` stores it.
+struct Run {
+    input_hash: String,
+    diagnostics: Diagnostics,
+    parameters: serde_json::Value,
+    decisions: Vec<runs::Decision>,
+}
+
+/// Solves `snapshot` with the backend `parameters` select and prints the plan.
+fn solve_snapshot(snapshot: &Snapshot, parameters: &ScenarioParameters) -> Result<Run> {
+    let travel_times = load_travel_times()?;
+    let departure_hour = snapshot.departure_hour;
+    let survival = parse_survival(parameters.survival_curves.as_deref())?;
+
+    if is_dispatch(parameters)? {
+        let mut dispatch_problem = DispatchProblem::from_models(
+            &snapshot.hospitals,
+            &snapshot.specialities,
+            &snapshot.patients,
+            &snapshot.ambulances,
+        )
+        .context("Failed to build the dispatch problem")?;
+        dispatch_problem.problem = travel_times
+            .attach(dispatch_problem.problem, departure_hour)
+            .context("Failed to attach travel times")?
+            .with_occupied_beds(&snapshot.availability)
+            .with_survival(survival);
+        let params = DispatchParams {
+            travel: travel_times.model.clone(),
+            departure_hour,
+        };
+        let started = Instant::now();
+        let plan = dispatch::solve(&dispatch_problem, &params);
+        let wall_time = started.elapsed();
+        print_dispatch_plan(&dispatch_problem, &plan);
+
+        let decisions = runs::dispatch_decisions(&dispatch_problem, &plan);
+        // Like multi-objective, the model does not maximize expected lives,
+        // so the status does not claim them optimal. What it minimized is
+        // stored in the parameters.
+        let diagnostics = Diagnostics {
+            backend: "dispatch",
+            status: SolveStatus::Feasible,
+            objective_value: decisions
+                .iter()
+                .map(|decision| decision.survival_probability)
+                .sum(),
+            objective_bound: None,
+            objectives: Vec::new(),
+            wall_time,
+        };
+        let parameters = json!({
+            "mode": "dispatch",
+            "weighted_time_to_treatment_seconds": plan.weighted_time_to_treatment_seconds,
+            "survival_curves": runs::survival_parameters(&dispatch_problem.problem.survival),
+            "travel_times": { "source": travel_times.source(), "departure_hour": departure_hour },
+        });
+        return Ok(Run {
+            input_hash: runs::dispatch_input_hash(&dispatch_problem),
+            diagnostics,
+            parameters,
+            decisions,
+        });
+    }
+
+    let problem = Problem::from_models(
+        &snapshot.hospitals,
+        &snapshot.specialities,
+        &snapshot.patients,
+    )
+    .context("Failed to build the optimization problem")?;
+    let problem = travel_times
+        .attach(problem, departure_hour)
+        .context("Failed to attach travel times")?
+        .with_occupied_beds(&snapshot.availability)
+        .with_survival(survival);
+    let registry = solver_registry(parameters.objectives.as_ref());
+    let solver_name = parameters.solver.as_deref().unwrap_or(DEFAULT_SOLVER);
+    let solver = lookup_solver(&registry, "SOLVER", solver_name)?;
+
+    let warm_start = &parameters.warm_start;
+
+    let solution = match warm_start {
+        Some(name) => {
+            let heuristic = lookup_solver(&registry, "WARM_START", name)?;
+            let warm_start = heuristic
+                .solve(&problem)
+                .with_context(|| format!("The '{}' backend failed", heuristic.name()))?;
+            print_diagnostics(&warm_start);
+            solver.solve_from(&problem, &warm_start.plan)
+        }
+        None => solver.solve(&problem),
+    }
+    .with_context(|| format!("The '{}' backend failed", solver.name()))?;
+
+    print_diagnostics(&solution);
+    print_plan(&problem, &solution.plan);
+
+    let mut run_parameters = json!({
+        "mode": "assignment",
+        "solver": solver.name(),
+        "warm_start": warm_start,
+        "survival_curves": runs::survival_parameters(&problem.survival),
+        "travel_times": { "source": travel_times.source(), "departure_hour": departure_hour },
+    });
+    if solver.name() == multi_objective::BACKEND {
+        run_parameters["objectives"] = json!(parameters.objectives.clone().unwrap_or_default());
+    }
+    Ok(Run {
+        input_hash: runs::input_hash(&problem),
+        decisions: runs::plan_decisions(&problem, &solution.plan),
+        diagnostics: solution.diagnostics,
+        parameters: run_parameters,
+    })
+}
+
+/// Fills what `parameters` leave unset from `OPTIMIZATION_MODE`, `SOLVER`,
+/// `WARM_START`, `SURVIVAL_CURVES` and `OBJECTIVES_FILE`.
+fn with_env(parameters: ScenarioParameters) -> Result<ScenarioParameters> {
+    let objectives = match parameters.objectives {
+        Some(objectives) => Some(objectives),
+        None => objectives_file()?,
+    };
+
+    Ok(ScenarioParameters {
+        mode: parameters.mode.or(optional_env("OPTIMIZATION_MODE")?),
+        solver: parameters.solver.or(optional_env("SOLVER")?),
+        warm_start: parameters.warm_start.or(optional_env("WARM_START")?),
+        survival_curves: parameters
+            .survival_curves
+            .or(optional_env("SURVIVAL_CURVES")?),
+        objectives,
+        departure_hour: parameters.departure_hour,
+    })
+}
+
+fn is_dispatch(parameters: &ScenarioParameters) -> Result<bool> {
+    match parameters.mode.as_deref().map(str::trim) {
+        None | Some("assignment") => Ok(false),
+        Some("dispatch") => Ok(true),
+        Some(other) => Err(anyhow!(
+            "Unsupported OPTIMIZATION_MODE '{other}'. Expected 'assignment' or 'dispatch'."
+        )),
+    }
+}
+
+fn print_hospitals(hospitals: &[hospital::Model]) {
     if hospitals.is_empty() {
         println!("(no hospitals found)");
     } else {
@@ -30,11 +480,9 @@ async fn print_hospitals(db: &DatabaseConnection) -> Result<(), DbErr> {
             println!("{:#?}", hospital);
         }
     }
-    Ok(())
 }
 
-async fn print_hospital_specialities(db: &DatabaseConnection) -> Result<(), DbErr> {
-    let specialities = HospitalSpeciality::find().all(db).await?;
+fn print_hospital_specialities(specialities: &[hospital_speciality::Model]) {
     if specialities.is_empty() {
         println!("(no hospital specialities found)");
     } else {
@@ -46,11 +494,23 @@ async fn print_hospital_specialities(db: &DatabaseConnection) -> Result<(), DbEr
             println!("{:#?}", speciality);
         }
     }
-    Ok(())
 }
 
-async fn print_patients(db: &DatabaseConnection) -> Result<(), DbErr> {
-    let patients = Patient::find().all(db).await?;
+fn print_availability(availability: &[BedAvailability]) {
+    let capacity: i32 = availability.iter().map(|entry| entry.capacity).sum();
+    let available: i32 = availability.iter().map(|entry| entry.available).sum();
+    let recorded = availability
+        .iter()
+        .filter(|entry| entry.recorded_at.is_some())
+        .count();
+
+    println!(
+        "\n== Bed availability: {available} of {capacity} beds free ({recorded} of {buckets} buckets with recorded occupancy) ==",
+        buckets = availability.len(),
+    );
+}
+
+fn print_patients(patients: &[patient::Model]) {
     if patients.is_empty() {
         println!("(no patients found)");
     } else {
@@ -59,5 +519,255 @@ async fn print_patients(db: &DatabaseConnection) -> Result<(), DbErr> {
             println!("{:#?}", patient);
         }
     }
-    Ok(())
+}
+
+/// The travel-time source selected by `OSM_PBF`: road routing over the
+/// extract when the `osm` feature is enabled, great-circle estimates otherwise.
+fn load_travel_times() -> Result<TravelTimes> {
+    let travel_times = TravelTimes::default();
+
+    #[cfg(feature = "osm")]
+    if let Some(path) = optional_env("OSM_PBF")? {
+        let profile = RoutingProfile::default();
+        let graph = RoadGraph::from_pbf(&path, &profile)
+            .with_context(|| format!("Failed to load the road network from '{path}'"))?;
+        println!(
+            "\n== Road network '{path}': {nodes} nodes, {edges} edges ==",
+            nodes = graph.node_count(),
+            edges = graph.edge_count(),
+        );
+        return Ok(travel_times.with_roads(graph, profile));
+    }
+    #[cfg(not(feature = "osm"))]
+    if optional_env("OSM_PBF")?.is_some() {
+        return Err(anyhow!(
+            "OSM_PBF is set but road routing needs the 'osm' feature (cargo run --features osm)"
+        ));
+    }
+
+    Ok(travel_times)
+}
+
+/// How often the live plan re-reads the snapshot: `PLAN_REFRESH_SECONDS`,
+/// 30 by default.
+fn plan_refresh() -> Result<Duration> {
+    let Some(seconds) = optional_env("PLAN_REFRESH_SECONDS")? else {
+        return Ok(Duration::from_secs(30));
+    };
+    match seconds.trim().parse::<u64>() {
+        Ok(seconds) if seconds > 0 => Ok(Duration::from_secs(seconds)),
+        _ => Err(anyhow!(
+            "PLAN_REFRESH_SECONDS must be a positive whole number of seconds, got '{seconds}'"
+        )),
+    }
+}
+
+/// `MAX_DISRUPTION`: re-optimize the live plan incrementally, changing at
+/// most this many earlier assignments.
+fn max_disruption() -> Result<Option<usize>> {
+    optional_env("MAX_DISRUPTION")?
+        .map(|limit| {
+            limit.trim().parse().map_err(|_| {
+                anyhow!("MAX_DISRUPTION must be a non-negative whole number, got '{limit}'")
+            })
+        })
+        .transpose()
+}
+
+fn survival_model() -> Result<SurvivalModel> {
+    parse_survival(optional_env("SURVIVAL_CURVES")?.as_deref())
+}
+
+fn parse_survival(curves: Option<&str>) -> Result<SurvivalModel> {
+    match curves {
+        Some(curves) => curves.parse().context("Invalid SURVIVAL_CURVES"),
+        None => Ok(SurvivalModel::default()),
+    }
+}
+
+/// The `multi-objective` configuration from `OBJECTIVES_FILE`, when set.
+fn objectives_file() -> Result<Option<ObjectiveConfig>> {
+    optional_env("OBJECTIVES_FILE")?
+        .map(|path| {
+            ObjectiveConfig::load(&path)
+                .with_context(|| format!("Failed to load OBJECTIVES_FILE '{path}'"))
+        })
+        .transpose()
+}
+
+/// Every compiled-in backend, with `multi-objective` configured from
+/// `objectives` when given.
+fn solver_registry(objectives: Option<&ObjectiveConfig>) -> SolverRegistry {
+    let mut registry = SolverRegistry::default();
+    if let Some(config) = objectives {
+        registry.register(MultiObjectiveSolver::new(config.clone()));
+    }
+    registry
+}
+
+fn optional_env(name: &str) -> Result<Option<String>> {
+    match std::env::var(name) {
+        Ok(value) => Ok(Some(value)),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(e) => Err(anyhow!(e)),
+    }
+}
+
+fn lookup_solver<'a>(
+    registry: &'a SolverRegistry,
+    variable: &str,
+    name: &str,
+) -> Result<&'a dyn Solver> {
+    registry.get(name).with_context(|| {
+        format!(
+            "Unknown {variable} '{name}'. Available backends: {}",
+            registry.names().collect::<Vec<_>>().join(", ")
+        )
+    })
+}
+
+fn print_diagnostics(solution: &Solution) {
+    let diagnostics = &solution.diagnostics;
+    let bound = diagnostics
+        .objective_bound
+        .map_or_else(|| "n/a".to_string(), |bound| format!("{bound:.3}"));
+
+    println!(
+        "\n== Solver '{backend}': {status} in {elapsed:?}, expected lives saved {value:.3} (bound {bound}) ==",
+        backend = diagnostics.backend,
+        status = diagnostics.status,
+        elapsed = diagnostics.wall_time,
+        value = diagnostics.objective_value,
+    );
+    for objective in &diagnostics.objectives {
+        println!("   {objective}");
+    }
+}
+
+fn print_plan(problem: &Problem, plan: &AssignmentPlan) {
+    println!(
+        "\n== Assignment Plan ({assigned} assigned, {unassigned} unassigned) ==",
+        assigned = plan.assigned_count(),
+        unassigned = plan.unassigned_count(),
+    );
+
+    let survival = objective::survival_report(problem, plan);
+    for (assignment, estimate) in plan.assignments.iter().zip(&survival) {
+        match &assignment.outcome {
+            AssignmentOutcome::Assigned(bucket) => {
+                let hospital = problem
+                    .hospitals
+                    .iter()
+                    .find(|hospital| hospital.id == bucket.hospital_id)
+                    .map_or("?", |hospital| hospital.name.as_str());
+                println!(
+                    "- patient {id}: {hospital} ({level}), treated in ~{minutes}m, survival {percent:.1}%",
+                    id = assignment.patient_id,
+                    level = bucket.triage_level,
+                    minutes = estimate.time_to_treatment_seconds.unwrap_or_default() as i64 / 60,
+                    percent = estimate.survival_probability * 100.0,
+                );
+            }
+            AssignmentOutcome::Unassigned(reason) => {
+                println!(
+                    "- patient {id}: unassigned ({reason})",
+                    id = assignment.patient_id,
+                );
+            }
+        }
+    }
+}
+
+fn print_simulation(report: &SimulationReport) {
+    println!(
+        "\n== Solver '{solver}': {decisions} decisions in {elapsed:?}, {unfinished} patients still in progress at the end ==",
+        solver = report.solver,
+        decisions = report.decisions,
+        elapsed = report.solver_time,
+        unfinished = report.unfinished,
+    );
+
+    println!("\n== Triage levels ==");
+    for level in &report.levels {
+        let minutes = level.mean_time_to_treatment_seconds.map_or_else(
+            || "n/a".to_string(),
+            |seconds| format!("{:.1}m", seconds / 60.0),
+        );
+        let survival = level
+            .mean_survival_probability
+            .map_or_else(|| "n/a".to_string(), |p| format!("{:.1}%", p * 100.0));
+        println!(
+            "- {name}: {arrivals} arrived, {treated} treated, {turned_away} turned away, mean time to treatment {minutes}, mean survival {survival}",
+            name = level.triage_level,
+            arrivals = level.arrivals,
+            treated = level.treated,
+            turned_away = level.turned_away,
+        );
+    }
+
+    println!("\n== Bed utilization ==");
+    for hospital in &report.hospitals {
+        println!(
+            "- {name}: {percent:.1}% of {beds} beds, peak {peak}",
+            name = hospital.name,
+            percent = hospital.utilization * 100.0,
+            beds = hospital.beds,
+            peak = hospital.peak_occupied,
+        );
+    }
+}
+
+fn print_dispatch_plan(dispatch_problem: &DispatchProblem, plan: &DispatchPlan) {
+    let problem = &dispatch_problem.problem;
+    let survival: Vec<f64> = plan
+        .assignments
+        .iter()
+        .zip(&problem.patients)
+        .map(|(assignment, patient)| match &assignment.outcome {
+            DispatchOutcome::Dispatched(dispatch) => problem.survival.probability(
+                patient.condition,
+                dispatch.time_to_treatment_seconds() as f64,
+            ),
+            DispatchOutcome::Unserved(_) => 0.0,
+        })
+        .collect();
+
+    println!(
+        "\n== Dispatch Plan ({dispatched} of {patients} patients, {ambulances} ambulances available, weighted time to treatment {weighted}s, expected lives saved {lives:.3}) ==",
+        dispatched = plan.dispatched_count(),
+        patients = plan.assignments.len(),
+        ambulances = dispatch_problem.ambulances.len(),
+        weighted = plan.weighted_time_to_treatment_seconds,
+        lives = survival.iter().sum::<f64>(),
+    );
+
+    for (assignment, probability) in plan.assignments.iter().zip(&survival) {
+        match &assignment.outcome {
+            DispatchOutcome::Dispatched(dispatch) => {
+                let ambulance = dispatch_problem
+                    .ambulances
+                    .iter()
+                    .find(|ambulance| ambulance.id == dispatch.ambulance_id)
+                    .map_or("?", |ambulance| ambulance.call_sign.as_str());
+                let hospital = problem
+                    .hospitals
+                    .iter()
+                    .find(|hospital| hospital.id == dispatch.bucket.hospital_id)
+                    .map_or("?", |hospital| hospital.name.as_str());
+                println!(
+                    "- patient {id}: {ambulance} → {hospital} ({level}), treated in ~{minutes}m, survival {percent:.1}%",
+                    id = assignment.patient_id,
+                    level = dispatch.bucket.triage_level,
+                    minutes = dispatch.time_to_treatment_seconds() / 60,
+                    percent = probability * 100.0,
+                );
+            }
+            DispatchOutcome::Unserved(reason) => {
+                println!(
+                    "- patient {id}: unserved ({reason})",
+                    id = assignment.patient_id,
+                );
+            }
+        }
+    }
 }
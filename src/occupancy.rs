@@ -0,0 +1,175 @@
+//! Live bed availability.
+//!
+//! `hospital_speciality.beds_capacity` is the nominal number of beds per
+//! triage bucket. The `bed_occupancy` table logs how many of them are in use
+//! over time. [`available_beds_at`] combines both: for every bucket, its
+//! capacity minus the latest occupancy recorded for the bucket's hospital
+//! and triage level at or before the requested time.
+//!
+//! Patients dispatched or in transport to a hospital are not in its recorded
+//! occupancy until they are admitted, but their beds are spoken for. They are
+//! counted as occupied too, so the optimizer does not hand the same bed to a
+//! new patient. Patient statuses are not versioned, so for a past time only
+//! patients that are still en route now and were reported by then count.
+
+use entity::{
+    TriageLevel, bed_occupancy, hospital_speciality, patient,
+    prelude::{BedOccupancy, HospitalSpeciality, Patient},
+};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    prelude::DateTimeWithTimeZone,
+};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BedAvailability {
+    pub speciality_id: i32,
+    pub hospital_id: i32,
+    pub triage_level: TriageLevel,
+    /// Nominal `beds_capacity` of the bucket.
+    pub capacity: i32,
+    /// Beds of this bucket counted as in use.
+    pub occupied: i32,
+    pub available: i32,
+    /// When the occupancy used was recorded; `None` if nothing was recorded
+    /// yet, in which case the bucket counts as empty.
+    pub recorded_at: Option<DateTimeWithTimeZone>,
+}
+
+/// Availability of every triage bucket at `at`, ordered by speciality id,
+/// with beds reserved for the patients en route.
+pub async fn available_beds_at<C: ConnectionTrait>(
+    db: &C,
+    at: DateTimeWithTimeZone,
+) -> Result<Vec<BedAvailability>, DbErr> {
+    let specialities = HospitalSpeciality::find().all(db).await?;
+    // Only the latest row per hospital and triage level, not the whole log.
+    // The columns are qualified since `triage_level` is selected as text.
+    let occupancy = BedOccupancy::find()
+        .distinct_on([
+            (BedOccupancy, bed_occupancy::Column::HospitalId),
+            (BedOccupancy, bed_occupancy::Column::TriageLevel),
+        ])
+        .filter(bed_occupancy::Column::RecordedAt.lte(at))
+        .order_by_asc(bed_occupancy::Column::HospitalId)
+        .order_by_asc(bed_occupancy::Column::TriageLevel)
+        .order_by_desc(bed_occupancy::Column::RecordedAt)
+        .order_by_desc(bed_occupancy::Column::Id)
+        .all(db)
+        .await?;
+    let en_route = Patient::find_en_route()
+        .filter(patient::Column::ReportedAt.lte(at))
+        .all(db)
+        .await?;
+
+    let mut availability = availability_at(&specialities, &occupancy, at);
+    reserve_en_route(&mut availability, &en_route, at);
+    Ok(availability)
+}
+
+/// Pure counterpart of [`available_beds_at`] for rows already in memory.
+///
+/// When a hospital has several buckets for the same triage level, the
+/// occupied beds fill them in id order.
+pub fn availability_at(
+    specialities: &[hospital_speciality::Model],
+    occupancy: &[bed_occupancy::Model],
+    at: DateTimeWithTimeZone,
+) -> Vec<BedAvailability> {
+    let mut latest: HashMap<(i32, TriageLevel), &bed_occupancy::Model> = HashMap::new();
+    for record in occupancy.iter().filter(|record| record.recorded_at <= at) {
+        let key = (record.hospital_id, record.triage_level);
+        let newer = latest.get(&key).is_none_or(|current| {
+            (record.recorded_at, record.id) > (current.recorded_at, current.id)
+        });
+        if newer {
+            latest.insert(key, record);
+        }
+    }
+
+    let mut specialities: Vec<&hospital_speciality::Model> = specialities.iter().collect();
+    specialities.sort_by_key(|speciality| speciality.id);
+
+    let mut unplaced: HashMap<(i32, TriageLevel), i32> = latest
+        .iter()
+        .map(|(key, record)| (*key, record.occupied_beds.max(0)))
+        .collect();
+
+    specialities
+        .into_iter()
+        .map(|speciality| {
+            let key = (speciality.hospital_id, speciality.triage_level);
+            let capacity = speciality.beds_capacity.max(0);
+            let remaining = unplaced.entry(key).or_default();
+            let occupied = (*remaining).min(capacity);
+            *remaining -= occupied;
+
+            BedAvailability {
+                speciality_id: speciality.id,
+                hospital_id: speciality.hospital_id,
+                triage_level: speciality.triage_level,
+                capacity: speciality.beds_capacity,
+                occupied,
+                available: capacity - occupied,
+                recorded_at: latest.get(&key).map(|record| record.recorded_at),
+            }
+        })
+        .collect()
+}
+
+/// Counts a bed as occupied for every [en-route](patient::Entity::find_en_route)
+/// patient in `patients` that was reported and not yet admitted at `at`, in
+/// the first bucket of their assigned hospital and triage level that has one
+/// free. Patients without a free bed there are skipped, like occupancy above
+/// capacity.
+pub fn reserve_en_route(
+    availability: &mut [BedAvailability],
+    patients: &[patient::Model],
+    at: DateTimeWithTimeZone,
+) {
+    for patient in patients.iter().filter(|patient| {
+        patient::PatientStatus::EN_ROUTE.contains(&patient.status)
+            && patient.reported_at <= at
+            && patient
+                .admitted_at
+                .is_none_or(|admitted_at| admitted_at > at)
+    }) {
+        let Some(hospital_id) = patient.assigned_hospital_id else {
+            continue;
+        };
+        let free = availability.iter_mut().find(|entry| {
+            entry.hospital_id == hospital_id
+                && entry.triage_level == patient.condition
+                && entry.available > 0
+        });
+        if let Some(entry) = free {
+            entry.occupied += 1;
+            entry.available -= 1;
+        }
+    }
+}
+
+/// Copies of `specialities` whose `beds_capacity` is the available count, so
+/// the optimizer plans against live availability. Buckets missing from
+/// `availability` keep their nominal capacity.
+pub fn live_specialities(
+    specialities: &[hospital_speciality::Model],
+    availability: &[BedAvailability],
+) -> Vec<hospital_speciality::Model> {
+    let available: HashMap<i32, i32> = availability
+        .iter()
+        .map(|entry| (entry.speciality_id, entry.available))
+        .collect();
+
+    specialities
+        .iter()
+        .map(|speciality| hospital_speciality::Model {
+            beds_capacity: available
+                .get(&speciality.id)
+                .copied()
+                .unwrap_or(speciality.beds_capacity),
+            ..speciality.clone()
+        })
+        .collect()
+}
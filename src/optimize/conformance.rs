@@ -0,0 +1,267 @@
+//! Behaviour every [`Solver`] backend must exhibit.
+//!
+//! [`check`] runs a backend against a fixed set of small instances and
+//! reports the first rule it breaks. `tests/solver_conformance.rs` runs it for
+//! every backend in the default [`SolverRegistry`](super::SolverRegistry);
+//! out-of-tree backends can call it from their own tests.
+
+use super::{
+    AssignmentOutcome, Problem, Solution, SolveStatus, Solver, UnassignedReason, objective,
+};
+use chrono::DateTime;
+use entity::{
+    TriageLevel::{self, Blue, Orange, Red, Yellow},
+    hospital, hospital_speciality,
+    patient::{self, PatientStatus},
+};
+use std::fmt;
+
+const TOLERANCE: f64 = 1e-6;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConformanceFailure {
+    pub case: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for ConformanceFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "case '{}': {}", self.case, self.message)
+    }
+}
+
+impl std::error::Error for ConformanceFailure {}
+
+/// Triage buckets of one hospital as `(level, waiting seconds, beds)`.
+type HospitalFixture<'a> = &'a [(TriageLevel, i64, i32)];
+
+/// The named instances [`check`] solves.
+pub fn cases() -> Vec<(&'static str, Problem)> {
+    vec![
+        ("empty", instance(&[], &[])),
+        (
+            "no-matching-triage",
+            instance(&[&[(Red, 60, 2)]], &[Red, Blue]),
+        ),
+        (
+            "over-capacity",
+            instance(&[&[(Red, 60, 2)]], &[Red, Red, Red, Red]),
+        ),
+        (
+            "zero-capacity",
+            instance(&[&[(Red, 30, 0)], &[(Red, 600, 1)]], &[Red, Red]),
+        ),
+        (
+            "mixed-network",
+            instance(
+                &[
+                    &[(Red, 90, 1), (Orange, 600, 1), (Yellow, 3_600, 2)],
+                    &[(Red, 300, 2), (Orange, 240, 1), (Yellow, 5_400, 1)],
+                    &[(Red, 30, 1), (Yellow, 2_700, 1)],
+                ],
+                &[Red, Yellow, Orange, Red, Yellow, Red, Orange, Yellow],
+            ),
+        ),
+    ]
+}
+
+/// Solves every case in [`cases`], cold and warm-started from the
+/// [`FirstFitSolver`](super::FirstFitSolver) plan, and validates the returned solutions.
+pub fn check(solver: &dyn Solver) -> Result<(), ConformanceFailure> {
+    for (case, problem) in cases() {
+        let fail = |message: String| ConformanceFailure { case, message };
+
+        let solution = solver
+            .solve(&problem)
+            .map_err(|err| fail(format!("solve failed: {err}")))?;
+        validate(solver, &problem, &solution).map_err(fail)?;
+
+        let warm_start = super::optimize(&problem);
+        let solution = solver
+            .solve_from(&problem, &warm_start)
+            .map_err(|err| fail(format!("warm-started solve failed: {err}")))?;
+        validate(solver, &problem, &solution)
+            .map_err(|message| fail(format!("warm-started: {message}")))?;
+    }
+    Ok(())
+}
+
+fn validate(solver: &dyn Solver, problem: &Problem, solution: &Solution) -> Result<(), String> {
+    let plan = &solution.plan;
+    let diagnostics = &solution.diagnostics;
+
+    if diagnostics.backend != solver.name() {
+        return Err(format!(
+            "diagnostics name backend '{}' instead of '{}'",
+            diagnostics.backend,
+            solver.name()
+        ));
+    }
+
+    if !matches!(
+        diagnostics.status,
+        SolveStatus::Optimal | SolveStatus::Feasible
+    ) {
+        return Err(format!(
+            "status {} on a feasible instance",
+            diagnostics.status
+        ));
+    }
+
+    let plan_ids: Vec<i32> = plan.assignments.iter().map(|a| a.patient_id).collect();
+    let problem_ids: Vec<i32> = problem.patients.iter().map(|p| p.id).collect();
+    if plan_ids != problem_ids {
+        return Err(format!(
+            "plan covers patients {plan_ids:?}, expected {problem_ids:?}"
+        ));
+    }
+
+    let choices = plan.choices(problem);
+    let mut loads = vec![0u32; problem.buckets.len()];
+
+    for (patient, (assignment, choice)) in plan.assignments.iter().zip(&choices).enumerate() {
+        if let AssignmentOutcome::Assigned(bucket_ref) = &assignment.outcome {
+            let bucket = choice.ok_or_else(|| {
+                format!(
+                    "patient {} assigned to unknown bucket {}",
+                    assignment.patient_id, bucket_ref.speciality_id
+                )
+            })?;
+            if !problem.candidate_buckets(patient).any(|b| b == bucket) {
+                return Err(format!(
+                    "patient {} assigned to bucket {} of another triage level",
+                    assignment.patient_id, bucket_ref.speciality_id
+                ));
+            }
+            loads[bucket] += 1;
+        }
+    }
+
+    for (bucket, load) in problem.buckets.iter().zip(&loads) {
+        if *load > bucket.capacity {
+            return Err(format!(
+                "bucket {} holds {load} patients but has {} beds",
+                bucket.id, bucket.capacity
+            ));
+        }
+    }
+
+    for (patient, assignment) in plan.assignments.iter().enumerate() {
+        let AssignmentOutcome::Unassigned(reason) = assignment.outcome else {
+            continue;
+        };
+        let mut candidates = problem.candidate_buckets(patient).peekable();
+        let has_candidates = candidates.peek().is_some();
+        let has_room = candidates.any(|b| loads[b] < problem.buckets[b].capacity);
+
+        let consistent = match reason {
+            UnassignedReason::NoMatchingTriage => !has_candidates,
+            UnassignedReason::CapacityExhausted => has_candidates && !has_room,
+            UnassignedReason::NotSelected => has_room,
+        };
+        if !consistent {
+            return Err(format!(
+                "patient {} reported as '{reason}' inconsistently with the plan",
+                assignment.patient_id
+            ));
+        }
+    }
+
+    let plan_value = objective::score_to_lives(objective::total_score(problem, &choices));
+    if (plan_value - diagnostics.objective_value).abs() > TOLERANCE {
+        return Err(format!(
+            "reported objective {} but the plan is worth {plan_value}",
+            diagnostics.objective_value
+        ));
+    }
+
+    if let Some(bound) = diagnostics.objective_bound
+        && bound + TOLERANCE < diagnostics.objective_value
+    {
+        return Err(format!(
+            "bound {bound} is below the objective {}",
+            diagnostics.objective_value
+        ));
+    }
+
+    if diagnostics.status == SolveStatus::Optimal {
+        let optimum = objective::score_to_lives(best_score(problem));
+        if (optimum - diagnostics.objective_value).abs() > TOLERANCE {
+            return Err(format!(
+                "claimed optimal with {} but the optimum is {optimum}",
+                diagnostics.objective_value
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Exhaustive optimum, only usable on the tiny instances above.
+fn best_score(problem: &Problem) -> i64 {
+    fn search(problem: &Problem, patient: usize, remaining: &mut [u32]) -> i64 {
+        if patient == problem.patients.len() {
+            return 0;
+        }
+
+        let mut best = search(problem, patient + 1, remaining);
+        for bucket in problem.candidate_buckets(patient) {
+            if remaining[bucket] == 0 {
+                continue;
+            }
+            remaining[bucket] -= 1;
+            let score = objective::lives_saved_score(problem, patient, bucket)
+                + search(problem, patient + 1, remaining);
+            remaining[bucket] += 1;
+            best = best.max(score);
+        }
+        best
+    }
+
+    let mut remaining: Vec<u32> = problem.buckets.iter().map(|b| b.capacity).collect();
+    search(problem, 0, &mut remaining)
+}
+
+fn instance(hospitals: &[HospitalFixture<'_>], patients: &[TriageLevel]) -> Problem {
+    let mut hospital_rows = Vec::new();
+    let mut speciality_rows = Vec::new();
+
+    for (idx, buckets) in hospitals.iter().enumerate() {
+        let hospital_id = idx as i32 + 1;
+        hospital_rows.push(hospital::Model {
+            id: hospital_id,
+            location_name: format!("Hospital {hospital_id}"),
+            latitude: 38.72 + idx as f64 * 0.01,
+            longitude: -9.14 - idx as f64 * 0.01,
+            bed_capacity: buckets.iter().map(|(_, _, beds)| beds).sum(),
+        });
+
+        for (level, wait, beds) in buckets.iter() {
+            speciality_rows.push(hospital_speciality::Model {
+                id: speciality_rows.len() as i32 + 1,
+                hospital_id,
+                triage_level: *level,
+                waiting_time_seconds: *wait,
+                beds_capacity: *beds,
+            });
+        }
+    }
+
+    let patient_rows: Vec<patient::Model> = patients
+        .iter()
+        .enumerate()
+        .map(|(idx, condition)| patient::Model {
+            id: idx as i32 + 1,
+            condition: *condition,
+            latitude: 38.73,
+            longitude: -9.15,
+            status: PatientStatus::Reported,
+            reported_at: DateTime::UNIX_EPOCH.fixed_offset(),
+            admitted_at: None,
+            assigned_hospital_id: None,
+        })
+        .collect();
+
+    Problem::from_models(&hospital_rows, &speciality_rows, &patient_rows)
+        .expect("conformance fixtures are well formed")
+}
@@ -0,0 +1,175 @@
+//! Google OR-Tools CP-SAT backend.
+//!
+//! Every candidate patient/bucket pair becomes a boolean variable. Each
+//! patient takes at most one bucket, each bucket holds at most
+//! `beds_capacity` patients, and the model maximizes the lives-saved score
+//! from [`objective`](super::objective).
+
+use super::{
+    AssignmentPlan, Diagnostics, Problem, Solution, SolveError, SolveStatus, Solver,
+    objective::{self, lives_saved_score},
+};
+use ::cp_sat::{
+    builder::{BoolVar, CpModelBuilder, LinearExpr},
+    proto::{CpSolverStatus, SatParameters},
+};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CpSatParams {
+    pub time_limit: Option<Duration>,
+    /// Parallel search workers; `None` lets CP-SAT decide.
+    pub workers: Option<i32>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CpSatSolver {
+    pub params: CpSatParams,
+}
+
+impl Solver for CpSatSolver {
+    fn name(&self) -> &'static str {
+        "cp-sat"
+    }
+
+    fn solve(&self, problem: &Problem) -> Result<Solution, SolveError> {
+        self.solve_with_hint(problem, None)
+    }
+
+    /// Seeds CP-SAT's search with the warm-start plan as a solution hint.
+    fn solve_from(
+        &self,
+        problem: &Problem,
+        warm_start: &AssignmentPlan,
+    ) -> Result<Solution, SolveError> {
+        self.solve_with_hint(problem, Some(&warm_start.choices(problem)))
+    }
+}
+
+impl CpSatSolver {
+    fn solve_with_hint(
+        &self,
+        problem: &Problem,
+        hint: Option<&[Option<usize>]>,
+    ) -> Result<Solution, SolveError> {
+        let started = Instant::now();
+        let ModelOutcome {
+            choices,
+            status,
+            bound,
+        } = solve_model(problem, &self.params, hint)?;
+
+        Ok(Solution {
+            plan: AssignmentPlan::from_choices(problem, &choices),
+            diagnostics: Diagnostics {
+                backend: self.name(),
+                status,
+                objective_value: objective::score_to_lives(objective::total_score(
+                    problem, &choices,
+                )),
+                objective_bound: bound.map(objective::score_to_lives),
+                objectives: Vec::new(),
+                wall_time: started.elapsed(),
+            },
+        })
+    }
+}
+
+struct ModelOutcome {
+    choices: Vec<Option<usize>>,
+    status: SolveStatus,
+    /// Best bound on the score, only when CP-SAT found a solution.
+    bound: Option<i64>,
+}
+
+fn solve_model(
+    problem: &Problem,
+    params: &CpSatParams,
+    hint: Option<&[Option<usize>]>,
+) -> Result<ModelOutcome, SolveError> {
+    let mut model = CpModelBuilder::default();
+    let mut variables: Vec<(usize, usize, BoolVar)> = Vec::new();
+    let mut objective_expr = LinearExpr::default();
+
+    for patient in 0..problem.patients.len() {
+        let mut patient_vars = Vec::new();
+
+        for bucket in problem.candidate_buckets(patient) {
+            let var = model.new_bool_var();
+            objective_expr += (lives_saved_score(problem, patient, bucket), var);
+            patient_vars.push(var);
+            variables.push((patient, bucket, var));
+        }
+
+        if patient_vars.len() > 1 {
+            model.add_at_most_one(patient_vars);
+        }
+    }
+
+    for (bucket_idx, bucket) in problem.buckets.iter().enumerate() {
+        let load: LinearExpr = variables
+            .iter()
+            .filter(|(_, bucket, _)| *bucket == bucket_idx)
+            .map(|(_, _, var)| *var)
+            .collect();
+        model.add_le(load, bucket.capacity as i64);
+    }
+
+    model.maximize(objective_expr);
+
+    if let Some(hint) = hint {
+        for (patient, bucket, var) in &variables {
+            model.add_hint(*var, i64::from(hint[*patient] == Some(*bucket)));
+        }
+    }
+
+    let validation = model.validate_cp_model();
+    if !validation.is_empty() {
+        return Err(SolveError::ModelInvalid(validation));
+    }
+
+    let mut sat_params = SatParameters::default();
+    if let Some(limit) = params.time_limit {
+        sat_params.max_time_in_seconds = Some(limit.as_secs_f64());
+    }
+    if let Some(workers) = params.workers {
+        sat_params.num_workers = Some(workers);
+    }
+
+    let response = model.solve_with_parameters(&sat_params);
+
+    let status = match response.status() {
+        CpSolverStatus::Optimal => SolveStatus::Optimal,
+        CpSolverStatus::Feasible => SolveStatus::Feasible,
+        CpSolverStatus::Infeasible => SolveStatus::Infeasible,
+        CpSolverStatus::Unknown if params.time_limit.is_some() => SolveStatus::Timeout,
+        CpSolverStatus::Unknown => {
+            return Err(SolveError::Inconclusive(
+                "CP-SAT reported UNKNOWN without a time limit".to_string(),
+            ));
+        }
+        CpSolverStatus::ModelInvalid => {
+            return Err(SolveError::ModelInvalid(
+                "CP-SAT reported MODEL_INVALID".to_string(),
+            ));
+        }
+    };
+
+    // Without a solution CP-SAT's bound means nothing either.
+    let mut choices = vec![None; problem.patients.len()];
+    let mut bound = None;
+    if matches!(status, SolveStatus::Optimal | SolveStatus::Feasible) {
+        for (patient, bucket, var) in &variables {
+            if var.solution_value(&response) {
+                choices[*patient] = Some(*bucket);
+            }
+        }
+        bound = Some(response.best_objective_bound.round() as i64);
+    }
+
+    Ok(ModelOutcome {
+        choices,
+        status,
+        bound,
+    })
+}
@@ -0,0 +1,133 @@
+//! Pure-Rust exact backend.
+//!
+//! The assignment problem is a bipartite transportation problem, so it is
+//! solved exactly as a minimum-cost flow: source → patient (one unit),
+//! patient → candidate bucket (cost = negated lives-saved score), bucket →
+//! sink (`beds_capacity` units). It optimizes the same objective as the
+//! CP-SAT backend and needs no native dependency.
+//!
+//! A warm start cannot speed the flow up, so it only breaks ties: among the
+//! optimal plans, the one keeping the most warm-start assignments wins.
+//! Assignments that do not fit the problem are ignored.
+
+use super::{
+    AssignmentOutcome, AssignmentPlan, Diagnostics, Problem, Solution, SolveError, SolveStatus,
+    Solver,
+    flow::FlowNetwork,
+    objective::{self, lives_saved_score},
+};
+use std::time::Instant;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExactSolver;
+
+impl Solver for ExactSolver {
+    fn name(&self) -> &'static str {
+        "exact"
+    }
+
+    fn solve(&self, problem: &Problem) -> Result<Solution, SolveError> {
+        self.solve_preferring(problem, None)
+    }
+
+    fn solve_from(
+        &self,
+        problem: &Problem,
+        warm_start: &AssignmentPlan,
+    ) -> Result<Solution, SolveError> {
+        self.solve_preferring(problem, Some(&warm_start_choices(problem, warm_start)))
+    }
+}
+
+impl ExactSolver {
+    fn solve_preferring(
+        &self,
+        problem: &Problem,
+        preferred: Option<&[Option<usize>]>,
+    ) -> Result<Solution, SolveError> {
+        let started = Instant::now();
+        let choices = choose(problem, preferred);
+        let value = objective::score_to_lives(objective::total_score(problem, &choices));
+
+        Ok(Solution {
+            plan: AssignmentPlan::from_choices(problem, &choices),
+            diagnostics: Diagnostics {
+                backend: self.name(),
+                status: SolveStatus::Optimal,
+                objective_value: value,
+                objective_bound: Some(value),
+                objectives: Vec::new(),
+                wall_time: started.elapsed(),
+            },
+        })
+    }
+}
+
+/// Bucket the warm start gives each patient. Entries for another patient or
+/// an unknown bucket count as no preference.
+fn warm_start_choices(problem: &Problem, warm_start: &AssignmentPlan) -> Vec<Option<usize>> {
+    let mut choices = vec![None; problem.patients.len()];
+    for ((choice, patient), assignment) in choices
+        .iter_mut()
+        .zip(&problem.patients)
+        .zip(&warm_start.assignments)
+    {
+        if let AssignmentOutcome::Assigned(bucket_ref) = &assignment.outcome
+            && assignment.patient_id == patient.id
+        {
+            *choice = problem
+                .buckets
+                .iter()
+                .position(|bucket| bucket.id == bucket_ref.speciality_id);
+        }
+    }
+    choices
+}
+
+fn choose(problem: &Problem, preferred: Option<&[Option<usize>]>) -> Vec<Option<usize>> {
+    // Scaling every score by more than the number of patients leaves room
+    // for a one-unit bonus per preferred arc that can never outweigh a
+    // difference in score.
+    let scale = match preferred {
+        Some(_) => problem.patients.len() as i64 + 1,
+        None => 1,
+    };
+
+    let mut network = FlowNetwork::default();
+    let source = network.add_node();
+    let sink = network.add_node();
+
+    let bucket_nodes: Vec<usize> = problem
+        .buckets
+        .iter()
+        .map(|bucket| {
+            let node = network.add_node();
+            network.add_arc(node, sink, bucket.capacity as i64, 0);
+            node
+        })
+        .collect();
+
+    let mut arcs: Vec<(usize, usize, usize)> = Vec::new();
+    for patient in 0..problem.patients.len() {
+        let node = network.add_node();
+        network.add_arc(source, node, 1, 0);
+
+        for bucket in problem.candidate_buckets(patient) {
+            let score = lives_saved_score(problem, patient, bucket) * scale;
+            let bonus = preferred.is_some_and(|preferred| preferred[patient] == Some(bucket));
+            let arc = network.add_arc(node, bucket_nodes[bucket], 1, -score - i64::from(bonus));
+            arcs.push((patient, bucket, arc));
+        }
+    }
+
+    network.min_cost_flow(source, sink);
+
+    let mut choices = vec![None; problem.patients.len()];
+    for (patient, bucket, arc) in arcs {
+        if network.flow(arc) > 0 {
+            choices[patient] = Some(bucket);
+        }
+    }
+
+    choices
+}
@@ -0,0 +1,57 @@
+//! Baseline backend: patients in id order take the matching bucket with the
+//! shortest waiting time that still has a free bed.
+
+use super::{
+    AssignmentPlan, Diagnostics, Problem, Solution, SolveError, SolveStatus, Solver, objective,
+};
+use std::time::Instant;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FirstFitSolver;
+
+impl Solver for FirstFitSolver {
+    fn name(&self) -> &'static str {
+        "first-fit"
+    }
+
+    fn solve(&self, problem: &Problem) -> Result<Solution, SolveError> {
+        let started = Instant::now();
+        let choices = choose(problem);
+
+        Ok(Solution {
+            plan: AssignmentPlan::from_choices(problem, &choices),
+            diagnostics: Diagnostics {
+                backend: self.name(),
+                status: SolveStatus::Feasible,
+                objective_value: objective::score_to_lives(objective::total_score(
+                    problem, &choices,
+                )),
+                objective_bound: None,
+                objectives: Vec::new(),
+                wall_time: started.elapsed(),
+            },
+        })
+    }
+}
+
+pub(crate) fn choose(problem: &Problem) -> Vec<Option<usize>> {
+    let mut remaining: Vec<u32> = problem
+        .buckets
+        .iter()
+        .map(|bucket| bucket.capacity)
+        .collect();
+
+    (0..problem.patients.len())
+        .map(|patient| {
+            let bucket = problem
+                .candidate_buckets(patient)
+                .filter(|bucket| remaining[*bucket] > 0)
+                .min_by_key(|bucket| {
+                    let row = &problem.buckets[*bucket];
+                    (row.waiting_time_seconds, row.id)
+                })?;
+            remaining[bucket] -= 1;
+            Some(bucket)
+        })
+        .collect()
+}
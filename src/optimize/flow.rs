@@ -0,0 +1,176 @@
+//! Minimum-cost flow by successive shortest paths.
+//!
+//! Augmenting paths are found with a queue-based Bellman-Ford so arcs may
+//! carry negative costs, which is how profits are encoded. The network must
+//! not contain a negative cycle before the first augmentation.
+//!
+//! Costs are plain `i64`s by default. Any totally ordered additive type works,
+//! which is how [`LexCost`] solves lexicographic objectives exactly.
+
+use std::{
+    collections::VecDeque,
+    ops::{Add, Neg},
+};
+
+/// Arc cost: a totally ordered group whose `Default` is zero.
+pub(crate) trait FlowCost:
+    Copy + Ord + Default + Add<Output = Self> + Neg<Output = Self>
+{
+    fn times(self, amount: i64) -> Self;
+}
+
+impl FlowCost for i64 {
+    fn times(self, amount: i64) -> Self {
+        self * amount
+    }
+}
+
+/// Most criteria a [`LexCost`] can rank.
+pub(crate) const LEX_LEVELS: usize = 8;
+
+/// Cost vector compared lexicographically: a lower level only matters when
+/// all higher ones tie.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct LexCost(pub [i64; LEX_LEVELS]);
+
+impl Add for LexCost {
+    type Output = LexCost;
+
+    fn add(self, other: LexCost) -> LexCost {
+        LexCost(std::array::from_fn(|level| self.0[level] + other.0[level]))
+    }
+}
+
+impl Neg for LexCost {
+    type Output = LexCost;
+
+    fn neg(self) -> LexCost {
+        LexCost(self.0.map(|cost| -cost))
+    }
+}
+
+impl FlowCost for LexCost {
+    fn times(self, amount: i64) -> Self {
+        LexCost(self.0.map(|cost| cost * amount))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Arc<C> {
+    to: usize,
+    capacity: i64,
+    cost: C,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct FlowNetwork<C = i64> {
+    adjacency: Vec<Vec<usize>>,
+    /// Arcs are stored in forward/residual pairs: `id ^ 1` is the reverse arc.
+    arcs: Vec<Arc<C>>,
+}
+
+impl<C> Default for FlowNetwork<C> {
+    fn default() -> Self {
+        FlowNetwork {
+            adjacency: Vec::new(),
+            arcs: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct FlowTotals<C = i64> {
+    pub flow: i64,
+    pub cost: C,
+}
+
+impl<C: FlowCost> FlowNetwork<C> {
+    pub fn add_node(&mut self) -> usize {
+        self.adjacency.push(Vec::new());
+        self.adjacency.len() - 1
+    }
+
+    pub fn add_arc(&mut self, from: usize, to: usize, capacity: i64, cost: C) -> usize {
+        let id = self.arcs.len();
+        self.arcs.push(Arc { to, capacity, cost });
+        self.arcs.push(Arc {
+            to: from,
+            capacity: 0,
+            cost: -cost,
+        });
+        self.adjacency[from].push(id);
+        self.adjacency[to].push(id + 1);
+        id
+    }
+
+    /// Flow currently routed through the arc returned by [`add_arc`](Self::add_arc).
+    pub fn flow(&self, arc: usize) -> i64 {
+        self.arcs[arc ^ 1].capacity
+    }
+
+    /// Augments along cheapest paths while they have a negative cost, which
+    /// yields the cheapest flow of any size from `source` to `sink`.
+    pub fn min_cost_flow(&mut self, source: usize, sink: usize) -> FlowTotals<C> {
+        let mut totals = FlowTotals::default();
+
+        while let Some((path_cost, parents)) = self.shortest_path(source, sink) {
+            if path_cost >= C::default() {
+                break;
+            }
+
+            let mut amount = i64::MAX;
+            let mut node = sink;
+            while node != source {
+                let arc = parents[node].expect("path reaches the source");
+                amount = amount.min(self.arcs[arc].capacity);
+                node = self.arcs[arc ^ 1].to;
+            }
+
+            let mut node = sink;
+            while node != source {
+                let arc = parents[node].expect("path reaches the source");
+                self.arcs[arc].capacity -= amount;
+                self.arcs[arc ^ 1].capacity += amount;
+                node = self.arcs[arc ^ 1].to;
+            }
+
+            totals.flow += amount;
+            totals.cost = totals.cost + path_cost.times(amount);
+        }
+
+        totals
+    }
+
+    fn shortest_path(&self, source: usize, sink: usize) -> Option<(C, Vec<Option<usize>>)> {
+        let nodes = self.adjacency.len();
+        let mut distance: Vec<Option<C>> = vec![None; nodes];
+        let mut parents = vec![None; nodes];
+        let mut queued = vec![false; nodes];
+        let mut queue = VecDeque::new();
+
+        distance[source] = Some(C::default());
+        queue.push_back(source);
+        queued[source] = true;
+
+        while let Some(node) = queue.pop_front() {
+            queued[node] = false;
+            let Some(reached) = distance[node] else {
+                continue;
+            };
+            for &arc in &self.adjacency[node] {
+                let Arc { to, capacity, cost } = self.arcs[arc];
+                let candidate = reached + cost;
+                if capacity > 0 && distance[to].is_none_or(|current| candidate < current) {
+                    distance[to] = Some(candidate);
+                    parents[to] = Some(arc);
+                    if !queued[to] {
+                        queued[to] = true;
+                        queue.push_back(to);
+                    }
+                }
+            }
+        }
+
+        distance[sink].map(|cost| (cost, parents))
+    }
+}
@@ -0,0 +1,160 @@
+//! Construction heuristics for sub-second answers.
+//!
+//! Neither backend proves optimality; both return in a few milliseconds even
+//! for large patient lists and make good warm starts for the exact backends
+//! (see [`Solver::solve_from`]).
+
+use super::{
+    AssignmentPlan, Diagnostics, Problem, Solution, SolveError, SolveStatus, Solver,
+    objective::{self, lives_saved_score},
+};
+use std::time::Instant;
+
+/// Most urgent patient first, each sent to the nearest hospital with a free
+/// bed in the matching triage bucket. "Nearest" means shortest travel time
+/// when the problem carries a travel-time matrix, great-circle distance
+/// otherwise.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GreedySolver;
+
+/// Regret insertion: repeatedly places the patient who would lose the most
+/// by not getting their best bucket, i.e. the one with the largest gap
+/// between their best and second-best remaining option.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RegretSolver;
+
+impl Solver for GreedySolver {
+    fn name(&self) -> &'static str {
+        "greedy"
+    }
+
+    fn solve(&self, problem: &Problem) -> Result<Solution, SolveError> {
+        let started = Instant::now();
+        let choices = greedy_choices(problem);
+        Ok(heuristic_solution(self.name(), problem, &choices, started))
+    }
+}
+
+impl Solver for RegretSolver {
+    fn name(&self) -> &'static str {
+        "regret"
+    }
+
+    fn solve(&self, problem: &Problem) -> Result<Solution, SolveError> {
+        let started = Instant::now();
+        let choices = regret_choices(problem);
+        Ok(heuristic_solution(self.name(), problem, &choices, started))
+    }
+}
+
+fn heuristic_solution(
+    backend: &'static str,
+    problem: &Problem,
+    choices: &[Option<usize>],
+    started: Instant,
+) -> Solution {
+    Solution {
+        plan: AssignmentPlan::from_choices(problem, choices),
+        diagnostics: Diagnostics {
+            backend,
+            status: SolveStatus::Feasible,
+            objective_value: objective::score_to_lives(objective::total_score(problem, choices)),
+            objective_bound: None,
+            objectives: Vec::new(),
+            wall_time: started.elapsed(),
+        },
+    }
+}
+
+fn greedy_choices(problem: &Problem) -> Vec<Option<usize>> {
+    let mut order: Vec<usize> = (0..problem.patients.len()).collect();
+    order.sort_by_key(|patient| {
+        let patient = &problem.patients[*patient];
+        (patient.condition, patient.id)
+    });
+
+    let mut remaining: Vec<u32> = problem.buckets.iter().map(|b| b.capacity).collect();
+    let mut choices = vec![None; problem.patients.len()];
+
+    for patient in order {
+        let nearest = problem
+            .candidate_buckets(patient)
+            .filter(|bucket| remaining[*bucket] > 0)
+            .min_by(|a, b| {
+                let proximity = |bucket: usize| {
+                    problem
+                        .travel_seconds(patient, bucket)
+                        .unwrap_or_else(|| problem.distance_km(patient, bucket))
+                };
+                proximity(*a).total_cmp(&proximity(*b)).then_with(|| {
+                    problem.buckets[*a]
+                        .waiting_time_seconds
+                        .cmp(&problem.buckets[*b].waiting_time_seconds)
+                })
+            });
+
+        if let Some(bucket) = nearest {
+            remaining[bucket] -= 1;
+            choices[patient] = Some(bucket);
+        }
+    }
+
+    choices
+}
+
+fn regret_choices(problem: &Problem) -> Vec<Option<usize>> {
+    let mut remaining: Vec<u32> = problem.buckets.iter().map(|b| b.capacity).collect();
+    let mut choices = vec![None; problem.patients.len()];
+    let mut pending: Vec<usize> = (0..problem.patients.len()).collect();
+
+    loop {
+        // (regret, best score, patient, best bucket) of the most urgent insertion.
+        let mut selected: Option<(i64, i64, usize, usize)> = None;
+
+        pending.retain(|&patient| {
+            let mut best: Option<(i64, usize)> = None;
+            let mut second_score: Option<i64> = None;
+
+            for bucket in problem.candidate_buckets(patient) {
+                if remaining[bucket] == 0 {
+                    continue;
+                }
+                let score = lives_saved_score(problem, patient, bucket);
+                match best {
+                    Some((best_score, _)) if score <= best_score => {
+                        second_score = Some(second_score.map_or(score, |s| s.max(score)));
+                    }
+                    _ => {
+                        second_score = best.map(|(s, _)| s);
+                        best = Some((score, bucket));
+                    }
+                }
+            }
+
+            let Some((best_score, bucket)) = best else {
+                return false;
+            };
+
+            // A patient with a single option left loses everything if it goes.
+            let regret = best_score - second_score.unwrap_or(0);
+            let candidate = (regret, best_score, patient, bucket);
+            let better = selected.is_none_or(|(r, s, p, _)| {
+                (regret, best_score) > (r, s) || ((regret, best_score) == (r, s) && patient < p)
+            });
+            if better {
+                selected = Some(candidate);
+            }
+            true
+        });
+
+        let Some((_, _, patient, bucket)) = selected else {
+            break;
+        };
+
+        remaining[bucket] -= 1;
+        choices[patient] = Some(bucket);
+        pending.retain(|&other| other != patient);
+    }
+
+    choices
+}
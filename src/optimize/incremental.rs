@@ -0,0 +1,190 @@
+//! Incremental re-optimization when patients arrive.
+//!
+//! Re-solving from scratch for every incident can move patients whose
+//! assignment dispatchers already acted on. [`reoptimize`] starts from the
+//! previous plan instead: new and previously unassigned patients are placed in
+//! the capacity that is left, and at most `max_disruption` earlier
+//! assignments may change to make room for better ones.
+//!
+//! The limit is enforced by Lagrangian relaxation. Keeping an earlier
+//! assignment earns a bonus on top of its lives-saved score. The exact
+//! backend's min-cost flow is solved for that bonus, and the bonus is binary
+//! searched for the smallest value whose plan stays within the limit. Such a
+//! plan is optimal among plans changing as many assignments as it does, and
+//! optimal outright when it uses the whole limit.
+
+use super::{
+    AssignmentOutcome, AssignmentPlan, Diagnostics, Problem, Solution, SolveStatus,
+    flow::{FlowNetwork, LEX_LEVELS, LexCost},
+    objective::{self, SCORE_SCALE, lives_saved_score},
+};
+use std::{collections::HashMap, time::Instant};
+
+pub const BACKEND: &str = "incremental";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IncrementalSolution {
+    pub solution: Solution,
+    /// Patients, by id, whose assignment in the previous plan was changed or
+    /// dropped.
+    pub disrupted: Vec<i32>,
+}
+
+/// Re-solves `problem` changing at most `max_disruption` of the assignments
+/// in `previous`.
+///
+/// Only patients of `problem` whose previous bucket still exists count
+/// towards the limit. When shrunken capacity leaves no plan within it, the
+/// plan with the fewest changes is returned with a
+/// [`Feasible`](SolveStatus::Feasible) status.
+pub fn reoptimize(
+    problem: &Problem,
+    previous: &AssignmentPlan,
+    max_disruption: usize,
+) -> IncrementalSolution {
+    let started = Instant::now();
+    let kept = previous_buckets(problem, previous);
+    let disruptions = |choices: &[Option<usize>]| {
+        kept.iter()
+            .zip(choices)
+            .filter(|(kept, choice)| kept.is_some() && kept != choice)
+            .count()
+    };
+
+    let unconstrained = choose(problem, &kept, 0);
+    let unconstrained_score = objective::total_score(problem, &unconstrained);
+
+    let (choices, status, bound) = if disruptions(&unconstrained) <= max_disruption {
+        (
+            unconstrained,
+            SolveStatus::Optimal,
+            Some(unconstrained_score),
+        )
+    } else {
+        // Above this bonus no gain in lives saved is worth a single change.
+        let mut high = (problem.patients.len() as i64 + 1) * SCORE_SCALE;
+        let mut best = choose(problem, &kept, high);
+
+        if disruptions(&best) > max_disruption {
+            (best, SolveStatus::Feasible, None)
+        } else {
+            let mut low = 0;
+            while high - low > 1 {
+                let bonus = low + (high - low) / 2;
+                let choices = choose(problem, &kept, bonus);
+                if disruptions(&choices) <= max_disruption {
+                    high = bonus;
+                    best = choices;
+                } else {
+                    low = bonus;
+                }
+            }
+
+            let used = disruptions(&best);
+            let score = objective::total_score(problem, &best);
+            let status = if used == max_disruption {
+                SolveStatus::Optimal
+            } else {
+                SolveStatus::Feasible
+            };
+            let dual = score + high * (max_disruption - used) as i64;
+            (best, status, Some(dual.min(unconstrained_score)))
+        }
+    };
+
+    let disrupted = kept
+        .iter()
+        .zip(&choices)
+        .zip(&problem.patients)
+        .filter(|((kept, choice), _)| kept.is_some() && kept != choice)
+        .map(|(_, patient)| patient.id)
+        .collect();
+
+    IncrementalSolution {
+        solution: Solution {
+            plan: AssignmentPlan::from_choices(problem, &choices),
+            diagnostics: Diagnostics {
+                backend: BACKEND,
+                status,
+                objective_value: objective::score_to_lives(objective::total_score(
+                    problem, &choices,
+                )),
+                objective_bound: bound.map(objective::score_to_lives),
+                objectives: Vec::new(),
+                wall_time: started.elapsed(),
+            },
+        },
+        disrupted,
+    }
+}
+
+/// The bucket index each patient of `problem` held in `previous`, when that
+/// bucket still exists and matches the patient's condition.
+fn previous_buckets(problem: &Problem, previous: &AssignmentPlan) -> Vec<Option<usize>> {
+    let assigned: HashMap<i32, i32> = previous
+        .assignments
+        .iter()
+        .filter_map(|assignment| match &assignment.outcome {
+            AssignmentOutcome::Assigned(bucket) => {
+                Some((assignment.patient_id, bucket.speciality_id))
+            }
+            AssignmentOutcome::Unassigned(_) => None,
+        })
+        .collect();
+
+    problem
+        .patients
+        .iter()
+        .map(|patient| {
+            let speciality_id = assigned.get(&patient.id)?;
+            problem.buckets.iter().position(|bucket| {
+                bucket.id == *speciality_id && bucket.triage_level == patient.condition
+            })
+        })
+        .collect()
+}
+
+/// Maximum lives saved plus `bonus` per kept assignment. Ties go to the plan
+/// keeping more assignments.
+fn choose(problem: &Problem, kept: &[Option<usize>], bonus: i64) -> Vec<Option<usize>> {
+    let mut network = FlowNetwork::<LexCost>::default();
+    let source = network.add_node();
+    let sink = network.add_node();
+
+    let bucket_nodes: Vec<usize> = problem
+        .buckets
+        .iter()
+        .map(|bucket| {
+            let node = network.add_node();
+            network.add_arc(node, sink, bucket.capacity as i64, LexCost::default());
+            node
+        })
+        .collect();
+
+    let mut arcs: Vec<(usize, usize, usize)> = Vec::new();
+    for (patient, kept) in kept.iter().enumerate() {
+        let node = network.add_node();
+        network.add_arc(source, node, 1, LexCost::default());
+
+        for bucket in problem.candidate_buckets(patient) {
+            let keeps = *kept == Some(bucket);
+            let mut cost = [0; LEX_LEVELS];
+            cost[0] =
+                -(lives_saved_score(problem, patient, bucket) + if keeps { bonus } else { 0 });
+            cost[1] = -(keeps as i64);
+            let arc = network.add_arc(node, bucket_nodes[bucket], 1, LexCost(cost));
+            arcs.push((patient, bucket, arc));
+        }
+    }
+
+    network.min_cost_flow(source, sink);
+
+    let mut choices = vec![None; problem.patients.len()];
+    for (patient, bucket, arc) in arcs {
+        if network.flow(arc) > 0 {
+            choices[patient] = Some(bucket);
+        }
+    }
+
+    choices
+}
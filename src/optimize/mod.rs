@@ -0,0 +1,39 @@
+//! Patient-to-hospital assignment.
+//!
+//! Build a [`Problem`] from the loaded `entity` rows, then run one of the
+//! [`Solver`] backends (looked up by name in a [`SolverRegistry`]) to obtain
+//! an [`AssignmentPlan`] that places every patient in a hospital triage
+//! bucket matching their condition without exceeding `beds_capacity`.
+
+pub mod conformance;
+#[cfg(feature = "cp-sat")]
+pub mod cp_sat;
+#[cfg(feature = "exact")]
+pub mod exact;
+mod first_fit;
+pub(crate) mod flow;
+mod heuristic;
+pub mod incremental;
+pub mod multi_objective;
+pub mod objective;
+mod plan;
+mod problem;
+mod registry;
+mod solver;
+mod survival;
+
+pub use first_fit::FirstFitSolver;
+pub use heuristic::{GreedySolver, RegretSolver};
+pub use incremental::{IncrementalSolution, reoptimize};
+pub use multi_objective::{MultiObjectiveSolver, ObjectiveConfig};
+pub use objective::{Criterion, ObjectiveValue};
+pub use plan::{AssignmentOutcome, AssignmentPlan, BucketRef, PatientAssignment, UnassignedReason};
+pub use problem::{HospitalSite, PatientDemand, Problem, ProblemError, TriageBucket};
+pub use registry::{DEFAULT_SOLVER, SolverRegistry};
+pub use solver::{Diagnostics, Solution, SolveError, SolveStatus, Solver};
+pub use survival::{ParseSurvivalModelError, SurvivalCurve, SurvivalModel};
+
+/// Assigns patients with the dependency-free [`FirstFitSolver`] strategy.
+pub fn optimize(problem: &Problem) -> AssignmentPlan {
+    AssignmentPlan::from_choices(problem, &first_fit::choose(problem))
+}
@@ -0,0 +1,620 @@
+//! Multi-objective assignment.
+//!
+//! [`MultiObjectiveSolver`] optimizes an ordered list of [`Criterion`]s,
+//! either lexicographically (each criterion only breaks ties among the ones
+//! before it) or as a weighted sum. Every criterion is separable over
+//! assignments and hospital loads, so both modes are solved exactly as a
+//! minimum-cost flow source → patient → bucket → hospital → sink. The
+//! hospital → sink leg has one unit arc per bed, whose costs price the
+//! load-balance and over-occupancy terms. Occupancy counts the beds already
+//! in use ([`HospitalSite::occupied_beds`](super::HospitalSite)) as well as
+//! the patients the plan sends.
+//!
+//! Minimizing the maximum utilization does not split that way. It is handled
+//! by capping every hospital at a candidate utilization level: a binary
+//! search for the lowest level that keeps the earlier criteria optimal in
+//! lexicographic mode. Weighted mode binary-searches the lowest level that
+//! costs nothing extra, then tries every level below it, since the capped
+//! cost plus the utilization penalty can have several local minima.
+//!
+//! Like every backend it reports expected lives saved in
+//! [`Diagnostics::objective_value`]. Since that is not what it optimizes, the
+//! status is [`SolveStatus::Feasible`]; the configured criteria are reported
+//! in [`Diagnostics::objectives`].
+
+use super::{
+    AssignmentPlan, Diagnostics, Problem, Solution, SolveError, SolveStatus, Solver,
+    flow::{FlowCost, FlowNetwork, LEX_LEVELS, LexCost},
+    objective::{self, Criterion, ObjectiveValue},
+};
+use crate::geo::{Coordinate, TravelTimeModel};
+use entity::TriageLevel;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, io, path::Path, time::Instant};
+use utoipa::ToSchema;
+
+pub const BACKEND: &str = "multi-objective";
+
+/// Scale of the integer costs in weighted mode.
+const WEIGHTED_SCALE: f64 = 1_000_000.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ObjectiveMode {
+    #[default]
+    Lexicographic,
+    Weighted,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ObjectiveSpec {
+    pub criterion: Criterion,
+    /// Only used in weighted mode.
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+fn default_occupancy_target() -> f64 {
+    0.85
+}
+
+/// Criteria in priority order, usually loaded from a TOML file:
+///
+/// ```toml
+/// mode = "lexicographic"
+///
+/// [[objectives]]
+/// criterion = "red-unassigned"
+///
+/// [[objectives]]
+/// criterion = "travel-time"
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ObjectiveConfig {
+    #[serde(default)]
+    pub mode: ObjectiveMode,
+    /// Share of a hospital's beds above which `over-occupancy` counts.
+    #[serde(default = "default_occupancy_target")]
+    pub occupancy_target: f64,
+    pub objectives: Vec<ObjectiveSpec>,
+}
+
+/// No Red patient left behind, then as many patients as possible, then the
+/// shortest travel, then the most even load.
+impl Default for ObjectiveConfig {
+    fn default() -> Self {
+        ObjectiveConfig {
+            mode: ObjectiveMode::Lexicographic,
+            occupancy_target: default_occupancy_target(),
+            objectives: [
+                Criterion::RedUnassigned,
+                Criterion::Unassigned,
+                Criterion::TravelTime,
+                Criterion::LoadBalance,
+            ]
+            .into_iter()
+            .map(|criterion| ObjectiveSpec {
+                criterion,
+                weight: default_weight(),
+            })
+            .collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ObjectiveConfigError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Empty,
+    Duplicate(Criterion),
+    InvalidWeight {
+        criterion: Criterion,
+        weight: f64,
+    },
+    InvalidOccupancyTarget(f64),
+    /// In lexicographic mode `max-utilization` is ranked before `unassigned`,
+    /// so leaving every patient without a bed would be the fairest plan.
+    MaxUtilizationBeforeUnassigned,
+}
+
+impl fmt::Display for ObjectiveConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectiveConfigError::Io(err) => write!(f, "failed to read the objectives: {err}"),
+            ObjectiveConfigError::Parse(err) => write!(f, "invalid objectives file: {err}"),
+            ObjectiveConfigError::Empty => f.write_str("no objectives configured"),
+            ObjectiveConfigError::Duplicate(criterion) => {
+                write!(f, "objective '{criterion}' is listed more than once")
+            }
+            ObjectiveConfigError::InvalidWeight { criterion, weight } => write!(
+                f,
+                "objective '{criterion}' has weight {weight}, expected a finite non-negative number"
+            ),
+            ObjectiveConfigError::InvalidOccupancyTarget(target) => write!(
+                f,
+                "occupancy target {target} must be a finite number above zero"
+            ),
+            ObjectiveConfigError::MaxUtilizationBeforeUnassigned => f.write_str(
+                "in lexicographic mode 'max-utilization' must be ranked after 'unassigned', \
+                 otherwise leaving every patient unassigned is the fairest plan",
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ObjectiveConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ObjectiveConfigError::Io(err) => Some(err),
+            ObjectiveConfigError::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl ObjectiveConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ObjectiveConfigError> {
+        let text = std::fs::read_to_string(path).map_err(ObjectiveConfigError::Io)?;
+        Self::from_toml(&text)
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, ObjectiveConfigError> {
+        let config: ObjectiveConfig = toml::from_str(text).map_err(ObjectiveConfigError::Parse)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks what the TOML schema cannot: a non-empty, duplicate-free list
+    /// with finite, non-negative weights, a positive occupancy target and, in
+    /// lexicographic mode, `unassigned` ranked before `max-utilization`.
+    pub fn validate(&self) -> Result<(), ObjectiveConfigError> {
+        if self.objectives.is_empty() {
+            return Err(ObjectiveConfigError::Empty);
+        }
+        if !self.occupancy_target.is_finite() || self.occupancy_target <= 0.0 {
+            return Err(ObjectiveConfigError::InvalidOccupancyTarget(
+                self.occupancy_target,
+            ));
+        }
+
+        for (idx, spec) in self.objectives.iter().enumerate() {
+            if self.objectives[..idx]
+                .iter()
+                .any(|earlier| earlier.criterion == spec.criterion)
+            {
+                return Err(ObjectiveConfigError::Duplicate(spec.criterion));
+            }
+            if !spec.weight.is_finite() || spec.weight < 0.0 {
+                return Err(ObjectiveConfigError::InvalidWeight {
+                    criterion: spec.criterion,
+                    weight: spec.weight,
+                });
+            }
+        }
+
+        let rank = |criterion| self.criteria().position(|listed| listed == criterion);
+        if self.mode == ObjectiveMode::Lexicographic
+            && let Some(fairness) = rank(Criterion::MaxUtilization)
+            && rank(Criterion::Unassigned).is_none_or(|coverage| coverage > fairness)
+        {
+            return Err(ObjectiveConfigError::MaxUtilizationBeforeUnassigned);
+        }
+        Ok(())
+    }
+
+    pub fn criteria(&self) -> impl Iterator<Item = Criterion> + '_ {
+        self.objectives.iter().map(|spec| spec.criterion)
+    }
+}
+
+/// Values of the configured criteria for one optional bucket index per
+/// patient.
+pub fn evaluate(
+    problem: &Problem,
+    choices: &[Option<usize>],
+    config: &ObjectiveConfig,
+) -> Vec<ObjectiveValue> {
+    let assigned = || {
+        choices
+            .iter()
+            .enumerate()
+            .filter_map(|(patient, bucket)| bucket.map(|bucket| (patient, bucket)))
+    };
+    let mut loads: Vec<u32> = problem
+        .hospitals
+        .iter()
+        .map(|hospital| hospital.occupied_beds)
+        .collect();
+    for (_, bucket) in assigned() {
+        loads[problem.buckets[bucket].hospital] += 1;
+    }
+    let utilization = |hospital: usize| match hospital_capacity(problem, hospital) {
+        0 => 0.0,
+        capacity => loads[hospital] as f64 / capacity as f64,
+    };
+
+    config
+        .criteria()
+        .map(|criterion| {
+            let value = match criterion {
+                Criterion::RedUnassigned => choices
+                    .iter()
+                    .zip(&problem.patients)
+                    .filter(|(bucket, patient)| {
+                        bucket.is_none() && patient.condition == TriageLevel::Red
+                    })
+                    .count() as f64,
+                Criterion::Unassigned => {
+                    choices.iter().filter(|bucket| bucket.is_none()).count() as f64
+                }
+                Criterion::TravelTime => assigned()
+                    .map(|(patient, bucket)| travel_seconds(problem, patient, bucket) / 60.0)
+                    .sum(),
+                Criterion::LoadBalance => (0..loads.len())
+                    .map(|hospital| utilization(hospital).powi(2))
+                    .sum(),
+                Criterion::LivesSaved => {
+                    objective::score_to_lives(objective::total_score(problem, choices))
+                }
+                Criterion::OverOccupancy => loads
+                    .iter()
+                    .enumerate()
+                    .map(|(hospital, load)| {
+                        load.saturating_sub(occupancy_target_beds(problem, config, hospital)) as f64
+                    })
+                    .sum(),
+                Criterion::MaxUtilization => (0..loads.len()).map(utilization).fold(0.0, f64::max),
+            };
+            ObjectiveValue { criterion, value }
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MultiObjectiveSolver {
+    config: ObjectiveConfig,
+}
+
+impl MultiObjectiveSolver {
+    pub fn new(config: ObjectiveConfig) -> Self {
+        MultiObjectiveSolver { config }
+    }
+
+    pub fn config(&self) -> &ObjectiveConfig {
+        &self.config
+    }
+}
+
+impl Solver for MultiObjectiveSolver {
+    fn name(&self) -> &'static str {
+        BACKEND
+    }
+
+    fn solve(&self, problem: &Problem) -> Result<Solution, SolveError> {
+        let started = Instant::now();
+        self.config
+            .validate()
+            .map_err(|err| SolveError::ModelInvalid(err.to_string()))?;
+        let specs = &self.config.objectives;
+        if specs.len() > LEX_LEVELS {
+            return Err(SolveError::ModelInvalid(format!(
+                "expected at most {LEX_LEVELS} objectives, got {}",
+                specs.len()
+            )));
+        }
+
+        let choices = match self.config.mode {
+            ObjectiveMode::Lexicographic => self.solve_lexicographic(problem),
+            ObjectiveMode::Weighted => self.solve_weighted(problem),
+        };
+
+        Ok(Solution {
+            plan: AssignmentPlan::from_choices(problem, &choices),
+            diagnostics: Diagnostics {
+                backend: self.name(),
+                status: SolveStatus::Feasible,
+                objective_value: objective::score_to_lives(objective::total_score(
+                    problem, &choices,
+                )),
+                objective_bound: None,
+                objectives: evaluate(problem, &choices, &self.config),
+                wall_time: started.elapsed(),
+            },
+        })
+    }
+}
+
+impl MultiObjectiveSolver {
+    fn solve_lexicographic(&self, problem: &Problem) -> Vec<Option<usize>> {
+        let specs = &self.config.objectives;
+        let limits = specs
+            .iter()
+            .position(|spec| spec.criterion == Criterion::MaxUtilization)
+            .map(|rank| {
+                let prefix = &specs[..rank];
+                let cost = lexicographic_cost(prefix);
+                let (_, best) = choose(problem, &self.config, prefix, None, &cost);
+
+                let levels = utilization_levels(problem);
+                let (mut low, mut high) = (0, levels.len() - 1);
+                while low < high {
+                    let middle = (low + high) / 2;
+                    let limits = bed_limits(problem, levels[middle]);
+                    let (_, reached) = choose(problem, &self.config, prefix, Some(&limits), &cost);
+                    if reached == best {
+                        high = middle;
+                    } else {
+                        low = middle + 1;
+                    }
+                }
+                bed_limits(problem, levels[low])
+            });
+
+        let cost = lexicographic_cost(specs);
+        choose(problem, &self.config, specs, limits.as_deref(), &cost).0
+    }
+
+    fn solve_weighted(&self, problem: &Problem) -> Vec<Option<usize>> {
+        let specs = &self.config.objectives;
+        let cost = weighted_cost(specs);
+        let Some(fairness) = specs
+            .iter()
+            .find(|spec| spec.criterion == Criterion::MaxUtilization)
+        else {
+            return choose(problem, &self.config, specs, None, &cost).0;
+        };
+
+        let levels = utilization_levels(problem);
+        let mut solved: HashMap<usize, (i64, Vec<Option<usize>>)> = HashMap::new();
+        let mut capped = |index: usize| {
+            solved
+                .entry(index)
+                .or_insert_with(|| {
+                    let limits = bed_limits(problem, levels[index]);
+                    let (choices, total) =
+                        choose(problem, &self.config, specs, Some(&limits), &cost);
+                    (total, choices)
+                })
+                .0
+        };
+
+        // Above the lowest level that matches the uncapped cost, raising the
+        // cap only adds penalty.
+        let (_, uncapped) = choose(problem, &self.config, specs, None, &cost);
+        let (mut low, mut high) = (0, levels.len() - 1);
+        while low < high {
+            let middle = (low + high) / 2;
+            if capped(middle) == uncapped {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+
+        // Below it the capped cost is a step function that can plateau, so
+        // its sum with the penalty is scanned level by level.
+        let penalty =
+            |index: usize| (fairness.weight * levels[index] * WEIGHTED_SCALE).round() as i64;
+        let best = (0..=high)
+            .min_by_key(|&index| capped(index) + penalty(index))
+            .expect("the search range is never empty");
+
+        solved
+            .remove(&best)
+            .map(|(_, choices)| choices)
+            .expect("every searched level was solved")
+    }
+}
+
+fn lexicographic_cost(specs: &[ObjectiveSpec]) -> impl Fn(&[f64]) -> LexCost + '_ {
+    move |marginals| {
+        let mut cost = LexCost::default();
+        for (level, (spec, marginal)) in specs.iter().zip(marginals).enumerate() {
+            cost.0[level] =
+                signed(spec.criterion, *marginal * integer_scale(spec.criterion)).round() as i64;
+        }
+        cost
+    }
+}
+
+fn weighted_cost(specs: &[ObjectiveSpec]) -> impl Fn(&[f64]) -> i64 + '_ {
+    move |marginals| {
+        let total: f64 = specs
+            .iter()
+            .zip(marginals)
+            .map(|(spec, marginal)| spec.weight * signed(spec.criterion, *marginal))
+            .sum();
+        (total * WEIGHTED_SCALE).round() as i64
+    }
+}
+
+/// Every utilization a hospital can reach, ascending, starting at zero.
+fn utilization_levels(problem: &Problem) -> Vec<f64> {
+    let mut levels = vec![0.0];
+    for hospital in 0..problem.hospitals.len() {
+        let capacity = hospital_capacity(problem, hospital);
+        let occupied = problem.hospitals[hospital].occupied_beds;
+        if capacity > 0 {
+            levels.extend(
+                (occupied..=occupied + bucket_beds(problem, hospital))
+                    .map(|beds| beds as f64 / capacity as f64),
+            );
+        }
+    }
+    levels.sort_by(f64::total_cmp);
+    levels.dedup_by(|a, b| (*a - *b).abs() < 1e-12);
+    levels
+}
+
+/// Free beds each hospital may fill without its occupancy exceeding
+/// `utilization`.
+fn bed_limits(problem: &Problem, utilization: f64) -> Vec<u32> {
+    (0..problem.hospitals.len())
+        .map(|hospital| {
+            let beds = (utilization * hospital_capacity(problem, hospital) as f64 + 1e-9).floor();
+            (beds as u32).saturating_sub(problem.hospitals[hospital].occupied_beds)
+        })
+        .collect()
+}
+
+/// Multiplier that makes lexicographic costs integral without losing
+/// meaningful precision.
+fn integer_scale(criterion: Criterion) -> f64 {
+    match criterion {
+        Criterion::RedUnassigned | Criterion::Unassigned | Criterion::OverOccupancy => 1.0,
+        Criterion::TravelTime => 60_000.0,
+        Criterion::LoadBalance | Criterion::MaxUtilization => 1_000_000.0,
+        Criterion::LivesSaved => objective::SCORE_SCALE as f64,
+    }
+}
+
+/// Flips maximized criteria so every cost is minimized.
+fn signed(criterion: Criterion, marginal: f64) -> f64 {
+    if criterion.maximize() {
+        -marginal
+    } else {
+        marginal
+    }
+}
+
+/// Solves the flow with arc costs built by `cost` from each criterion's
+/// marginal contribution, in natural units and `specs` order, filling at most
+/// `limits[h]` beds of hospital `h`. Returns the plan and its total cost.
+fn choose<C: FlowCost>(
+    problem: &Problem,
+    config: &ObjectiveConfig,
+    specs: &[ObjectiveSpec],
+    limits: Option<&[u32]>,
+    cost: &impl Fn(&[f64]) -> C,
+) -> (Vec<Option<usize>>, C) {
+    let mut network = FlowNetwork::<C>::default();
+    let source = network.add_node();
+    let sink = network.add_node();
+
+    let hospital_nodes: Vec<usize> = (0..problem.hospitals.len())
+        .map(|hospital| {
+            let node = network.add_node();
+            let capacity = hospital_capacity(problem, hospital);
+            let occupied = problem.hospitals[hospital].occupied_beds;
+            let allowance =
+                occupancy_target_beds(problem, config, hospital).saturating_sub(occupied);
+            let mut beds = bucket_beds(problem, hospital);
+            if let Some(limits) = limits {
+                beds = beds.min(limits[hospital]);
+            }
+
+            for bed in 1..=beds {
+                let marginals: Vec<f64> = specs
+                    .iter()
+                    .map(|spec| match spec.criterion {
+                        Criterion::LoadBalance if capacity > 0 => {
+                            (2.0 * (occupied + bed) as f64 - 1.0) / (capacity as f64).powi(2)
+                        }
+                        Criterion::OverOccupancy if bed > allowance => 1.0,
+                        _ => 0.0,
+                    })
+                    .collect();
+                network.add_arc(node, sink, 1, cost(&marginals));
+            }
+            node
+        })
+        .collect();
+
+    let bucket_nodes: Vec<usize> = problem
+        .buckets
+        .iter()
+        .map(|bucket| {
+            let node = network.add_node();
+            network.add_arc(
+                node,
+                hospital_nodes[bucket.hospital],
+                bucket.capacity as i64,
+                C::default(),
+            );
+            node
+        })
+        .collect();
+
+    let mut arcs: Vec<(usize, usize, usize)> = Vec::new();
+    for (patient, demand) in problem.patients.iter().enumerate() {
+        let node = network.add_node();
+        network.add_arc(source, node, 1, C::default());
+
+        for bucket in problem.candidate_buckets(patient) {
+            let marginals: Vec<f64> = specs
+                .iter()
+                .map(|spec| match spec.criterion {
+                    Criterion::RedUnassigned if demand.condition == TriageLevel::Red => -1.0,
+                    Criterion::RedUnassigned
+                    | Criterion::LoadBalance
+                    | Criterion::OverOccupancy
+                    | Criterion::MaxUtilization => 0.0,
+                    Criterion::Unassigned => -1.0,
+                    Criterion::TravelTime => travel_seconds(problem, patient, bucket) / 60.0,
+                    Criterion::LivesSaved => objective::score_to_lives(
+                        objective::lives_saved_score(problem, patient, bucket),
+                    ),
+                })
+                .collect();
+            let arc = network.add_arc(node, bucket_nodes[bucket], 1, cost(&marginals));
+            arcs.push((patient, bucket, arc));
+        }
+    }
+
+    let totals = network.min_cost_flow(source, sink);
+
+    let mut choices = vec![None; problem.patients.len()];
+    for (patient, bucket, arc) in arcs {
+        if network.flow(arc) > 0 {
+            choices[patient] = Some(bucket);
+        }
+    }
+
+    (choices, totals.cost)
+}
+
+/// Beds used as the denominator of a hospital's occupancy: its declared
+/// `bed_capacity`, or its free bucket beds plus the occupied ones when that
+/// is zero.
+fn hospital_capacity(problem: &Problem, hospital: usize) -> u32 {
+    match problem.hospitals[hospital].bed_capacity {
+        0 => bucket_beds(problem, hospital) + problem.hospitals[hospital].occupied_beds,
+        capacity => capacity,
+    }
+}
+
+/// Free beds offered by all triage buckets of a hospital.
+fn bucket_beds(problem: &Problem, hospital: usize) -> u32 {
+    problem
+        .buckets
+        .iter()
+        .filter(|bucket| bucket.hospital == hospital)
+        .map(|bucket| bucket.capacity)
+        .sum()
+}
+
+/// Beds, occupied ones included, a hospital can fill before `over-occupancy`
+/// counts.
+fn occupancy_target_beds(problem: &Problem, config: &ObjectiveConfig, hospital: usize) -> u32 {
+    (config.occupancy_target * hospital_capacity(problem, hospital) as f64 + 1e-9).floor() as u32
+}
+
+fn travel_seconds(problem: &Problem, patient: usize, bucket: usize) -> f64 {
+    problem.travel_seconds(patient, bucket).unwrap_or_else(|| {
+        let demand = &problem.patients[patient];
+        let hospital = problem.hospital_of(bucket);
+        TravelTimeModel::default().travel_seconds(
+            Coordinate::new(demand.latitude, demand.longitude),
+            Coordinate::new(hospital.latitude, hospital.longitude),
+            None,
+            0,
+        )
+    })
+}
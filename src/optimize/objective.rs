@@ -0,0 +1,153 @@
+//! Lives-saved objective shared by the solver backends.
+//!
+//! Each assignment is worth the patient's probability of surviving until
+//! treatment under the problem's [`SurvivalModel`](super::SurvivalModel),
+//! where time to treatment is travel plus the bucket's waiting time.
+//! Unassigned patients contribute nothing. Scores are integers in millionths
+//! of a life because CP-SAT only accepts integer coefficients.
+//!
+//! It also names the [`Criterion`]s a plan can be scored on, so any backend
+//! can report an [`ObjectiveValue`] per criterion in its diagnostics.
+
+use super::{AssignmentPlan, Problem};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+pub const SCORE_SCALE: i64 = 1_000_000;
+
+/// Probability that `patient` survives when sent to `bucket`.
+pub fn survival_probability(problem: &Problem, patient: usize, bucket: usize) -> f64 {
+    problem.survival.probability(
+        problem.patients[patient].condition,
+        problem.time_to_treatment_seconds(patient, bucket),
+    )
+}
+
+/// Score of sending `patient` to `bucket`, in millionths of a life.
+pub fn lives_saved_score(problem: &Problem, patient: usize, bucket: usize) -> i64 {
+    (survival_probability(problem, patient, bucket) * SCORE_SCALE as f64).round() as i64
+}
+
+/// Total score of one optional bucket index per patient.
+pub fn total_score(problem: &Problem, choices: &[Option<usize>]) -> i64 {
+    choices
+        .iter()
+        .enumerate()
+        .filter_map(|(patient, bucket)| bucket.map(|b| lives_saved_score(problem, patient, b)))
+        .sum()
+}
+
+pub fn score_to_lives(score: i64) -> f64 {
+    score as f64 / SCORE_SCALE as f64
+}
+
+/// Expected outcome of one patient under a plan.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatientSurvival {
+    pub patient_id: i32,
+    /// `None` when the patient is not assigned.
+    pub time_to_treatment_seconds: Option<f64>,
+    pub survival_probability: f64,
+}
+
+/// Per-patient survival estimates, in plan order. Their sum is the plan's
+/// expected lives saved, up to score rounding.
+pub fn survival_report(problem: &Problem, plan: &AssignmentPlan) -> Vec<PatientSurvival> {
+    plan.assignments
+        .iter()
+        .zip(plan.choices(problem))
+        .enumerate()
+        .map(|(patient, (assignment, bucket))| PatientSurvival {
+            patient_id: assignment.patient_id,
+            time_to_treatment_seconds: bucket
+                .map(|bucket| problem.time_to_treatment_seconds(patient, bucket)),
+            survival_probability: bucket
+                .map_or(0.0, |bucket| survival_probability(problem, patient, bucket)),
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Criterion {
+    /// Red patients left without a bed (minimized).
+    RedUnassigned,
+    /// Patients left without a bed (minimized).
+    Unassigned,
+    /// Minutes of travel from assigned patients to their hospitals
+    /// (minimized). Without a travel-time matrix the default
+    /// [`TravelTimeModel`](crate::geo::TravelTimeModel) estimate is used.
+    TravelTime,
+    /// Sum over hospitals of squared occupancy, which is lowest when load is
+    /// spread in proportion to capacity (minimized).
+    LoadBalance,
+    /// Expected lives saved (maximized).
+    LivesSaved,
+    /// Beds filled beyond the
+    /// [occupancy target](super::ObjectiveConfig::occupancy_target), summed
+    /// over hospitals (minimized). Ranked after `unassigned`, it keeps
+    /// hospitals under the target unless a patient would otherwise go without
+    /// a bed.
+    OverOccupancy,
+    /// Highest occupancy of any hospital (minimized).
+    MaxUtilization,
+}
+
+impl Criterion {
+    pub const ALL: [Criterion; 7] = [
+        Criterion::RedUnassigned,
+        Criterion::Unassigned,
+        Criterion::TravelTime,
+        Criterion::LoadBalance,
+        Criterion::LivesSaved,
+        Criterion::OverOccupancy,
+        Criterion::MaxUtilization,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Criterion::RedUnassigned => "red-unassigned",
+            Criterion::Unassigned => "unassigned",
+            Criterion::TravelTime => "travel-time",
+            Criterion::LoadBalance => "load-balance",
+            Criterion::LivesSaved => "lives-saved",
+            Criterion::OverOccupancy => "over-occupancy",
+            Criterion::MaxUtilization => "max-utilization",
+        }
+    }
+
+    pub fn maximize(self) -> bool {
+        self == Criterion::LivesSaved
+    }
+}
+
+impl fmt::Display for Criterion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Value of one criterion for a plan, in its natural unit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObjectiveValue {
+    pub criterion: Criterion,
+    pub value: f64,
+}
+
+impl fmt::Display for ObjectiveValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.criterion {
+            Criterion::RedUnassigned | Criterion::Unassigned => {
+                write!(f, "{} = {:.0} patients", self.criterion, self.value)
+            }
+            Criterion::TravelTime => write!(f, "{} = {:.1} min", self.criterion, self.value),
+            Criterion::LoadBalance => write!(f, "{} = {:.4}", self.criterion, self.value),
+            Criterion::LivesSaved => write!(f, "{} = {:.3} lives", self.criterion, self.value),
+            Criterion::OverOccupancy => write!(f, "{} = {:.0} beds", self.criterion, self.value),
+            Criterion::MaxUtilization => {
+                write!(f, "{} = {:.1}%", self.criterion, self.value * 100.0)
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use super::Problem;
+use entity::TriageLevel;
+use std::fmt;
+
+/// Outcome of an optimization: one entry per patient, in [`Problem::patients`] order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssignmentPlan {
+    pub assignments: Vec<PatientAssignment>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatientAssignment {
+    pub patient_id: i32,
+    pub outcome: AssignmentOutcome,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AssignmentOutcome {
+    Assigned(BucketRef),
+    Unassigned(UnassignedReason),
+}
+
+/// The hospital triage bucket a patient was sent to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BucketRef {
+    pub hospital_id: i32,
+    pub speciality_id: i32,
+    pub triage_level: TriageLevel,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnassignedReason {
+    /// No hospital offers a bucket for the patient's triage level.
+    NoMatchingTriage,
+    /// Every matching bucket is already full.
+    CapacityExhausted,
+    /// A matching bucket still had room but the solver left the patient out.
+    NotSelected,
+}
+
+impl UnassignedReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            UnassignedReason::NoMatchingTriage => "no matching triage bucket",
+            UnassignedReason::CapacityExhausted => "capacity exhausted",
+            UnassignedReason::NotSelected => "not selected",
+        }
+    }
+}
+
+impl fmt::Display for UnassignedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl AssignmentPlan {
+    /// Builds a plan from one optional bucket index per patient, deriving the
+    /// reason for every patient left without a bucket.
+    pub fn from_choices(problem: &Problem, choices: &[Option<usize>]) -> Self {
+        debug_assert_eq!(choices.len(), problem.patients.len());
+
+        let mut loads = vec![0u32; problem.buckets.len()];
+        for bucket in choices.iter().flatten() {
+            loads[*bucket] += 1;
+        }
+
+        let assignments = problem
+            .patients
+            .iter()
+            .zip(choices)
+            .enumerate()
+            .map(|(idx, (patient, choice))| {
+                let outcome = match choice {
+                    Some(bucket) => {
+                        let bucket_row = &problem.buckets[*bucket];
+                        AssignmentOutcome::Assigned(BucketRef {
+                            hospital_id: problem.hospitals[bucket_row.hospital].id,
+                            speciality_id: bucket_row.id,
+                            triage_level: bucket_row.triage_level,
+                        })
+                    }
+                    None => {
+                        let mut candidates = problem.candidate_buckets(idx).peekable();
+                        let reason = if candidates.peek().is_none() {
+                            UnassignedReason::NoMatchingTriage
+                        } else if candidates
+                            .any(|bucket| loads[bucket] < problem.buckets[bucket].capacity)
+                        {
+                            UnassignedReason::NotSelected
+                        } else {
+                            UnassignedReason::CapacityExhausted
+                        };
+                        AssignmentOutcome::Unassigned(reason)
+                    }
+                };
+
+                PatientAssignment {
+                    patient_id: patient.id,
+                    outcome,
+                }
+            })
+            .collect();
+
+        AssignmentPlan { assignments }
+    }
+
+    /// Inverse of [`from_choices`](Self::from_choices): the bucket index each
+    /// patient was assigned to, or `None` when unassigned or unknown.
+    pub fn choices(&self, problem: &Problem) -> Vec<Option<usize>> {
+        self.assignments
+            .iter()
+            .map(|assignment| match &assignment.outcome {
+                AssignmentOutcome::Assigned(bucket_ref) => problem
+                    .buckets
+                    .iter()
+                    .position(|bucket| bucket.id == bucket_ref.speciality_id),
+                AssignmentOutcome::Unassigned(_) => None,
+            })
+            .collect()
+    }
+
+    pub fn assigned_count(&self) -> usize {
+        self.assignments
+            .iter()
+            .filter(|assignment| matches!(assignment.outcome, AssignmentOutcome::Assigned(_)))
+            .count()
+    }
+
+    pub fn unassigned_count(&self) -> usize {
+        self.assignments.len() - self.assigned_count()
+    }
+}
@@ -0,0 +1,250 @@
+use super::SurvivalModel;
+use crate::{
+    geo::{self, Coordinate, TravelTimeMatrix},
+    occupancy::BedAvailability,
+};
+use entity::{TriageLevel, hospital, hospital_speciality, patient};
+use std::{collections::HashMap, fmt};
+
+/// Normalized optimization input built from the `entity` rows.
+///
+/// Hospitals, triage buckets and patients are sorted by primary key so that
+/// every solver sees the same indices for the same database snapshot.
+/// Only [pending](entity::patient::PatientStatus::is_pending) patients are
+/// included; the others already have a hospital or left the system.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Problem {
+    pub hospitals: Vec<HospitalSite>,
+    pub buckets: Vec<TriageBucket>,
+    pub patients: Vec<PatientDemand>,
+    /// Optional patient × hospital travel times, see [`Problem::with_travel_times`].
+    pub travel_times: Option<TravelTimeMatrix>,
+    /// Survival curves the objective is computed with.
+    pub survival: SurvivalModel,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HospitalSite {
+    pub id: i32,
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub bed_capacity: u32,
+    /// Beds already in use. Bucket capacities only count the free ones, so
+    /// occupancy criteria add this to the patients a plan sends here. See
+    /// [`Problem::with_occupied_beds`].
+    pub occupied_beds: u32,
+}
+
+/// One `hospital_speciality` row: the beds a hospital reserves for a triage level.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TriageBucket {
+    pub id: i32,
+    /// Index into [`Problem::hospitals`].
+    pub hospital: usize,
+    pub triage_level: TriageLevel,
+    pub waiting_time_seconds: i64,
+    pub capacity: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatientDemand {
+    pub id: i32,
+    pub condition: TriageLevel,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProblemError {
+    UnknownHospital {
+        speciality_id: i32,
+        hospital_id: i32,
+    },
+    NegativeCapacity {
+        table: &'static str,
+        id: i32,
+        value: i32,
+    },
+    TravelTimeShape {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+}
+
+impl fmt::Display for ProblemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProblemError::UnknownHospital {
+                speciality_id,
+                hospital_id,
+            } => write!(
+                f,
+                "hospital speciality {speciality_id} references unknown hospital {hospital_id}"
+            ),
+            ProblemError::NegativeCapacity { table, id, value } => {
+                write!(f, "{table} row {id} has a negative bed capacity ({value})")
+            }
+            ProblemError::TravelTimeShape { expected, found } => write!(
+                f,
+                "travel-time matrix is {}×{} but the problem needs {}×{} (patients × hospitals)",
+                found.0, found.1, expected.0, expected.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ProblemError {}
+
+impl Problem {
+    pub fn from_models(
+        hospitals: &[hospital::Model],
+        specialities: &[hospital_speciality::Model],
+        patients: &[patient::Model],
+    ) -> Result<Self, ProblemError> {
+        let mut hospitals: Vec<&hospital::Model> = hospitals.iter().collect();
+        hospitals.sort_by_key(|hospital| hospital.id);
+
+        let hospitals = hospitals
+            .into_iter()
+            .map(|hospital| {
+                Ok(HospitalSite {
+                    id: hospital.id,
+                    name: hospital.location_name.clone(),
+                    latitude: hospital.latitude,
+                    longitude: hospital.longitude,
+                    bed_capacity: non_negative("hospitals", hospital.id, hospital.bed_capacity)?,
+                    occupied_beds: 0,
+                })
+            })
+            .collect::<Result<Vec<_>, ProblemError>>()?;
+
+        let hospital_index: HashMap<i32, usize> = hospitals
+            .iter()
+            .enumerate()
+            .map(|(idx, hospital)| (hospital.id, idx))
+            .collect();
+
+        let mut specialities: Vec<&hospital_speciality::Model> = specialities.iter().collect();
+        specialities.sort_by_key(|speciality| speciality.id);
+
+        let buckets = specialities
+            .into_iter()
+            .map(|speciality| {
+                let hospital = *hospital_index.get(&speciality.hospital_id).ok_or(
+                    ProblemError::UnknownHospital {
+                        speciality_id: speciality.id,
+                        hospital_id: speciality.hospital_id,
+                    },
+                )?;
+
+                Ok(TriageBucket {
+                    id: speciality.id,
+                    hospital,
+                    triage_level: speciality.triage_level,
+                    waiting_time_seconds: speciality.waiting_time_seconds,
+                    capacity: non_negative(
+                        "hospital_specialities",
+                        speciality.id,
+                        speciality.beds_capacity,
+                    )?,
+                })
+            })
+            .collect::<Result<Vec<_>, ProblemError>>()?;
+
+        let mut patients: Vec<PatientDemand> = patients
+            .iter()
+            .filter(|patient| patient.status.is_pending())
+            .map(|patient| PatientDemand {
+                id: patient.id,
+                condition: patient.condition,
+                latitude: patient.latitude,
+                longitude: patient.longitude,
+            })
+            .collect();
+        patients.sort_by_key(|patient| patient.id);
+
+        Ok(Problem {
+            hospitals,
+            buckets,
+            patients,
+            travel_times: None,
+            survival: SurvivalModel::default(),
+        })
+    }
+
+    /// Attaches a patient × hospital travel-time matrix, e.g. from
+    /// [`TravelTimeMatrix::patients_to_hospitals`].
+    pub fn with_travel_times(mut self, matrix: TravelTimeMatrix) -> Result<Self, ProblemError> {
+        let expected = (self.patients.len(), self.hospitals.len());
+        let found = (matrix.rows(), matrix.columns());
+        if expected != found {
+            return Err(ProblemError::TravelTimeShape { expected, found });
+        }
+
+        self.travel_times = Some(matrix);
+        Ok(self)
+    }
+
+    /// Records the beds each hospital already has in use, summed over its
+    /// buckets in `availability`, e.g. from
+    /// [`available_beds_at`](crate::occupancy::available_beds_at).
+    pub fn with_occupied_beds(mut self, availability: &[BedAvailability]) -> Self {
+        for hospital in &mut self.hospitals {
+            hospital.occupied_beds = availability
+                .iter()
+                .filter(|entry| entry.hospital_id == hospital.id)
+                .map(|entry| entry.occupied.max(0) as u32)
+                .sum();
+        }
+        self
+    }
+
+    pub fn with_survival(mut self, survival: SurvivalModel) -> Self {
+        self.survival = survival;
+        self
+    }
+
+    /// Buckets whose triage level matches the patient's condition.
+    pub fn candidate_buckets(&self, patient: usize) -> impl Iterator<Item = usize> + '_ {
+        let condition = self.patients[patient].condition;
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(move |(_, bucket)| bucket.triage_level == condition)
+            .map(|(idx, _)| idx)
+    }
+
+    pub fn hospital_of(&self, bucket: usize) -> &HospitalSite {
+        &self.hospitals[self.buckets[bucket].hospital]
+    }
+
+    /// Great-circle distance between a patient and the hospital of a bucket.
+    pub fn distance_km(&self, patient: usize, bucket: usize) -> f64 {
+        let patient = &self.patients[patient];
+        let hospital = self.hospital_of(bucket);
+        geo::haversine_km(
+            Coordinate::new(patient.latitude, patient.longitude),
+            Coordinate::new(hospital.latitude, hospital.longitude),
+        )
+    }
+
+    /// Travel time from a patient to the hospital of a bucket, when a
+    /// travel-time matrix is attached.
+    pub fn travel_seconds(&self, patient: usize, bucket: usize) -> Option<f64> {
+        self.travel_times
+            .as_ref()
+            .map(|matrix| matrix.get(patient, self.buckets[bucket].hospital))
+    }
+
+    /// Travel time (zero without a travel-time matrix) plus the bucket's
+    /// waiting time.
+    pub fn time_to_treatment_seconds(&self, patient: usize, bucket: usize) -> f64 {
+        self.travel_seconds(patient, bucket).unwrap_or(0.0)
+            + self.buckets[bucket].waiting_time_seconds.max(0) as f64
+    }
+}
+
+fn non_negative(table: &'static str, id: i32, value: i32) -> Result<u32, ProblemError> {
+    u32::try_from(value).map_err(|_| ProblemError::NegativeCapacity { table, id, value })
+}
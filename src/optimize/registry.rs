@@ -0,0 +1,58 @@
+use super::{FirstFitSolver, GreedySolver, MultiObjectiveSolver, RegretSolver, Solver};
+
+/// Backend picked when no name is configured.
+#[cfg(feature = "exact")]
+pub const DEFAULT_SOLVER: &str = "exact";
+#[cfg(not(feature = "exact"))]
+pub const DEFAULT_SOLVER: &str = "first-fit";
+
+/// Solver backends addressable by name, e.g. from the `SOLVER` variable.
+pub struct SolverRegistry {
+    solvers: Vec<Box<dyn Solver>>,
+}
+
+impl SolverRegistry {
+    /// An empty registry; see [`Default`] for one with every compiled-in backend.
+    pub fn new() -> Self {
+        SolverRegistry {
+            solvers: Vec::new(),
+        }
+    }
+
+    /// Adds a backend, replacing any previous one with the same name.
+    pub fn register(&mut self, solver: impl Solver + 'static) {
+        self.solvers
+            .retain(|existing| existing.name() != solver.name());
+        self.solvers.push(Box::new(solver));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Solver> {
+        self.solvers
+            .iter()
+            .find(|solver| solver.name().eq_ignore_ascii_case(name.trim()))
+            .map(|solver| solver.as_ref())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.solvers.iter().map(|solver| solver.name())
+    }
+
+    pub fn solvers(&self) -> impl Iterator<Item = &dyn Solver> {
+        self.solvers.iter().map(|solver| solver.as_ref())
+    }
+}
+
+impl Default for SolverRegistry {
+    fn default() -> Self {
+        let mut registry = SolverRegistry::new();
+        registry.register(FirstFitSolver);
+        registry.register(GreedySolver);
+        registry.register(RegretSolver);
+        registry.register(MultiObjectiveSolver::default());
+        #[cfg(feature = "exact")]
+        registry.register(super::exact::ExactSolver);
+        #[cfg(feature = "cp-sat")]
+        registry.register(super::cp_sat::CpSatSolver::default());
+        registry
+    }
+}
@@ -0,0 +1,100 @@
+//! Pluggable solver backends.
+//!
+//! Every backend consumes the same normalized [`Problem`] and returns a
+//! [`Solution`], so callers can swap algorithms without touching the code
+//! that loads the `entity` rows.
+
+use super::{AssignmentPlan, Problem, objective::ObjectiveValue};
+use std::{fmt, time::Duration};
+
+pub trait Solver: Send + Sync {
+    /// Stable identifier used by [`SolverRegistry`](super::SolverRegistry).
+    fn name(&self) -> &'static str;
+
+    fn solve(&self, problem: &Problem) -> Result<Solution, SolveError>;
+
+    /// Solves `problem` starting from `warm_start`, typically the plan of a
+    /// fast heuristic. Backends that cannot exploit a starting point fall back
+    /// to [`solve`](Self::solve).
+    fn solve_from(
+        &self,
+        problem: &Problem,
+        warm_start: &AssignmentPlan,
+    ) -> Result<Solution, SolveError> {
+        let _ = warm_start;
+        self.solve(problem)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolveStatus {
+    /// The plan is proven to maximize the objective.
+    Optimal,
+    /// The plan is valid but the search stopped before proving optimality.
+    Feasible,
+    /// No valid plan exists.
+    Infeasible,
+    /// The time limit was reached before any plan was found.
+    Timeout,
+}
+
+impl SolveStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            SolveStatus::Optimal => "optimal",
+            SolveStatus::Feasible => "feasible",
+            SolveStatus::Infeasible => "infeasible",
+            SolveStatus::Timeout => "timeout",
+        }
+    }
+}
+
+impl fmt::Display for SolveStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solution {
+    pub plan: AssignmentPlan,
+    pub diagnostics: Diagnostics,
+}
+
+/// What a backend knows about the quality of the plan it returned.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostics {
+    pub backend: &'static str,
+    pub status: SolveStatus,
+    /// Expected lives saved by the plan.
+    pub objective_value: f64,
+    /// Best proven upper bound on the expected lives saved, when the backend
+    /// computes one.
+    pub objective_bound: Option<f64>,
+    /// Criteria a multi-objective backend optimized, in priority order. Empty
+    /// for single-objective backends.
+    pub objectives: Vec<ObjectiveValue>,
+    pub wall_time: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// The backend rejected the model it was given.
+    ModelInvalid(String),
+    /// The backend stopped without a plan or a proof, with no time limit to
+    /// blame.
+    Inconclusive(String),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::ModelInvalid(details) => write!(f, "solver rejected the model: {details}"),
+            SolveError::Inconclusive(details) => {
+                write!(f, "solver stopped without a result: {details}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
@@ -0,0 +1,153 @@
+//! Survival curves: chance of surviving as a function of time to treatment.
+//!
+//! Each triage level has a logistic curve normalized so that a patient
+//! treated immediately survives with the level's `baseline` probability. The
+//! curve keeps roughly that value until `midpoint_minutes` approaches, then
+//! decays over a window of a few `scale_minutes`.
+
+use entity::TriageLevel;
+use serde::Serialize;
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct SurvivalCurve {
+    /// Survival probability when treated at once, in `(0, 1]`.
+    pub baseline: f64,
+    /// Minutes until treatment at which the curve is steepest.
+    pub midpoint_minutes: f64,
+    /// Larger values spread the decay over a longer period.
+    pub scale_minutes: f64,
+}
+
+impl SurvivalCurve {
+    pub fn probability(&self, seconds_to_treatment: f64) -> f64 {
+        let minutes = seconds_to_treatment.max(0.0) / 60.0;
+        let at_zero = 1.0 + (-self.midpoint_minutes / self.scale_minutes).exp();
+        let now = 1.0 + ((minutes - self.midpoint_minutes) / self.scale_minutes).exp();
+        self.baseline * at_zero / now
+    }
+}
+
+/// One [`SurvivalCurve`] per triage level.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SurvivalModel {
+    curves: [SurvivalCurve; 5],
+}
+
+impl Default for SurvivalModel {
+    fn default() -> Self {
+        let curve = |baseline, midpoint_minutes, scale_minutes| SurvivalCurve {
+            baseline,
+            midpoint_minutes,
+            scale_minutes,
+        };
+
+        SurvivalModel {
+            curves: [
+                curve(0.85, 30.0, 10.0),
+                curve(0.95, 60.0, 20.0),
+                curve(0.98, 180.0, 60.0),
+                curve(0.99, 360.0, 120.0),
+                curve(0.995, 720.0, 240.0),
+            ],
+        }
+    }
+}
+
+impl SurvivalModel {
+    pub fn curve(&self, triage_level: TriageLevel) -> &SurvivalCurve {
+        &self.curves[slot(triage_level)]
+    }
+
+    pub fn set_curve(&mut self, triage_level: TriageLevel, curve: SurvivalCurve) {
+        self.curves[slot(triage_level)] = curve;
+    }
+
+    pub fn probability(&self, triage_level: TriageLevel, seconds_to_treatment: f64) -> f64 {
+        self.curve(triage_level).probability(seconds_to_treatment)
+    }
+}
+
+fn slot(triage_level: TriageLevel) -> usize {
+    TriageLevel::ALL
+        .iter()
+        .position(|level| *level == triage_level)
+        .expect("TriageLevel::ALL lists every level")
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseSurvivalModelError {
+    /// An entry that is not `level=baseline/midpoint_minutes/scale_minutes`
+    /// with values in range.
+    Invalid(String),
+    /// A triage level given more than once.
+    Duplicate(TriageLevel),
+}
+
+impl fmt::Display for ParseSurvivalModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseSurvivalModelError::Invalid(entry) => write!(
+                f,
+                "invalid survival curve '{entry}', expected 'level=baseline/midpoint_minutes/scale_minutes'"
+            ),
+            ParseSurvivalModelError::Duplicate(level) => {
+                write!(f, "survival curve for '{level}' given more than once")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseSurvivalModelError {}
+
+/// Parses comma-separated overrides of the default curves, e.g.
+/// `red=0.9/25/8,orange=0.95/90/30`.
+impl FromStr for SurvivalModel {
+    type Err = ParseSurvivalModelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut model = SurvivalModel::default();
+        let mut seen = Vec::new();
+
+        for entry in s
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let invalid = || ParseSurvivalModelError::Invalid(entry.to_string());
+            let (level, params) = entry.split_once('=').ok_or_else(invalid)?;
+            let level: TriageLevel = level.parse().map_err(|_| invalid())?;
+            if seen.contains(&level) {
+                return Err(ParseSurvivalModelError::Duplicate(level));
+            }
+            seen.push(level);
+
+            let params = params
+                .split('/')
+                .map(|value| value.trim().parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid())?;
+            let [baseline, midpoint_minutes, scale_minutes] = params[..] else {
+                return Err(invalid());
+            };
+            if !(baseline > 0.0
+                && baseline <= 1.0
+                && midpoint_minutes >= 0.0
+                && scale_minutes > 0.0)
+            {
+                return Err(invalid());
+            }
+
+            model.set_curve(
+                level,
+                SurvivalCurve {
+                    baseline,
+                    midpoint_minutes,
+                    scale_minutes,
+                },
+            );
+        }
+
+        Ok(model)
+    }
+}
@@ -0,0 +1,370 @@
+//! Road-network travel times from a local OpenStreetMap extract.
+//!
+//! [`RoadGraph::from_pbf`] reads an `.osm.pbf` file offline, keeps the
+//! drivable `highway=*` ways and turns them into a directed graph weighted by
+//! free-flow driving time. [`RoadGraph::patients_to_hospitals`] snaps every
+//! patient and hospital to the nearest road node and runs one Dijkstra search
+//! per patient to fill a [`TravelTimeMatrix`].
+
+mod nearest;
+mod pbf;
+
+use crate::{
+    geo::{self, Coordinate, TravelTimeMatrix, TravelTimeModel},
+    optimize::Problem,
+};
+use entity::ambulance::AmbulanceType;
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+    fmt, io,
+    path::Path,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoutingProfile {
+    /// Free-flow speed for each drivable `highway=*` value. Ways with other
+    /// values are ignored.
+    pub highway_speeds_kmh: HashMap<String, f64>,
+    /// Speed for the straight-line leg between a point and its snapped road
+    /// node.
+    pub access_speed_kmh: f64,
+}
+
+impl Default for RoutingProfile {
+    fn default() -> Self {
+        let speeds = [
+            ("motorway", 100.0),
+            ("motorway_link", 60.0),
+            ("trunk", 80.0),
+            ("trunk_link", 50.0),
+            ("primary", 50.0),
+            ("primary_link", 40.0),
+            ("secondary", 45.0),
+            ("secondary_link", 35.0),
+            ("tertiary", 40.0),
+            ("tertiary_link", 30.0),
+            ("unclassified", 30.0),
+            ("residential", 30.0),
+            ("road", 30.0),
+            ("living_street", 10.0),
+            ("service", 15.0),
+        ];
+
+        RoutingProfile {
+            highway_speeds_kmh: speeds
+                .into_iter()
+                .map(|(highway, speed)| (highway.to_string(), speed))
+                .collect(),
+            access_speed_kmh: 20.0,
+        }
+    }
+}
+
+impl RoutingProfile {
+    /// Speed for a way, or `None` when it is not drivable. A numeric
+    /// `maxspeed` tag (km/h) overrides the per-class default.
+    fn way_speed_kmh(&self, way: &pbf::WayRecord<'_>) -> Option<f64> {
+        let class_speed = *self.highway_speeds_kmh.get(way.tag("highway")?)?;
+        if matches!(way.tag("access"), Some("no" | "private")) || way.tag("area") == Some("yes") {
+            return None;
+        }
+
+        let speed = way
+            .tag("maxspeed")
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .filter(|speed| *speed > 0.0)
+            .unwrap_or(class_speed);
+        Some(speed)
+    }
+}
+
+#[derive(Debug)]
+pub enum RoutingError {
+    Io(io::Error),
+    /// The file is not a well-formed OSM PBF extract.
+    Decode(String),
+    /// The file uses a PBF feature this reader does not implement.
+    Unsupported(String),
+    /// The extract contains no drivable roads.
+    NoRoads,
+}
+
+impl fmt::Display for RoutingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoutingError::Io(err) => write!(f, "failed to read the OSM extract: {err}"),
+            RoutingError::Decode(details) => write!(f, "malformed OSM extract: {details}"),
+            RoutingError::Unsupported(details) => {
+                write!(f, "unsupported OSM extract: {details}")
+            }
+            RoutingError::NoRoads => f.write_str("the OSM extract contains no drivable roads"),
+        }
+    }
+}
+
+impl std::error::Error for RoutingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RoutingError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RoutingError {
+    fn from(err: io::Error) -> Self {
+        RoutingError::Io(err)
+    }
+}
+
+impl From<prost::DecodeError> for RoutingError {
+    fn from(err: prost::DecodeError) -> Self {
+        RoutingError::Decode(err.to_string())
+    }
+}
+
+/// Directed road graph in compressed sparse row form.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoadGraph {
+    coordinates: Vec<Coordinate>,
+    /// Outgoing edges of node `n` are `offsets[n]..offsets[n + 1]`.
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    seconds: Vec<f64>,
+    nodes: nearest::NodeIndex,
+}
+
+impl RoadGraph {
+    /// Builds the graph in two passes over the file: the first collects the
+    /// drivable ways, the second the coordinates of the nodes they use.
+    pub fn from_pbf(
+        path: impl AsRef<Path>,
+        profile: &RoutingProfile,
+    ) -> Result<Self, RoutingError> {
+        let path = path.as_ref();
+        let mut ways: Vec<(Vec<i64>, f64, Direction)> = Vec::new();
+        pbf::for_each_block(path, |block| {
+            block.for_each_way(|way| {
+                if let Some(speed) = profile.way_speed_kmh(&way) {
+                    let direction = Direction::of(&way);
+                    ways.push((way.refs, speed, direction));
+                }
+            })
+        })?;
+
+        let used: HashSet<i64> = ways.iter().flat_map(|(refs, _, _)| refs).copied().collect();
+        let mut index: HashMap<i64, usize> = HashMap::with_capacity(used.len());
+        let mut coordinates = Vec::with_capacity(used.len());
+        pbf::for_each_block(path, |block| {
+            block.for_each_node(|id, latitude, longitude| {
+                if used.contains(&id) && !index.contains_key(&id) {
+                    index.insert(id, coordinates.len());
+                    coordinates.push(Coordinate::new(latitude, longitude));
+                }
+            })
+        })?;
+
+        let mut edges: Vec<(usize, usize, f64)> = Vec::new();
+        for (refs, speed, direction) in &ways {
+            for pair in refs.windows(2) {
+                let (Some(&from), Some(&to)) = (index.get(&pair[0]), index.get(&pair[1])) else {
+                    continue;
+                };
+                let seconds =
+                    geo::haversine_km(coordinates[from], coordinates[to]) / speed * 3_600.0;
+                if *direction != Direction::Backward {
+                    edges.push((from, to, seconds));
+                }
+                if *direction != Direction::Forward {
+                    edges.push((to, from, seconds));
+                }
+            }
+        }
+
+        if edges.is_empty() {
+            return Err(RoutingError::NoRoads);
+        }
+        Ok(RoadGraph::from_edges(coordinates, edges))
+    }
+
+    /// Builds a graph from node coordinates and `(from, to, seconds)` edges
+    /// between their indices. Panics if an edge refers to a missing node.
+    pub fn from_edges(coordinates: Vec<Coordinate>, mut edges: Vec<(usize, usize, f64)>) -> Self {
+        edges.sort_by_key(|(from, _, _)| *from);
+
+        let mut offsets = vec![0; coordinates.len() + 1];
+        for (from, _, _) in &edges {
+            offsets[from + 1] += 1;
+        }
+        for node in 0..coordinates.len() {
+            offsets[node + 1] += offsets[node];
+        }
+
+        RoadGraph {
+            nodes: nearest::NodeIndex::new(&coordinates),
+            coordinates,
+            offsets,
+            targets: edges.iter().map(|(_, to, _)| *to).collect(),
+            seconds: edges.iter().map(|(_, _, seconds)| *seconds).collect(),
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.coordinates.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Index of the road node closest to `point` and its distance in km.
+    pub fn snap(&self, point: Coordinate) -> Option<(usize, f64)> {
+        let node = self.nodes.nearest(point)?;
+        Some((node, geo::haversine_km(point, self.coordinates[node])))
+    }
+
+    /// Free-flow seconds from `source` to every node, stopping once all
+    /// `targets` are settled. Unreachable nodes stay at infinity.
+    pub fn shortest_seconds(&self, source: usize, targets: &[usize]) -> Vec<f64> {
+        let mut best = vec![f64::INFINITY; self.node_count()];
+        let mut pending: HashSet<usize> = targets.iter().copied().collect();
+        let mut queue = BinaryHeap::new();
+        best[source] = 0.0;
+        queue.push(Visit {
+            seconds: 0.0,
+            node: source,
+        });
+
+        while let Some(Visit { seconds, node }) = queue.pop() {
+            if seconds > best[node] {
+                continue;
+            }
+            pending.remove(&node);
+            if pending.is_empty() {
+                break;
+            }
+
+            for edge in self.offsets[node]..self.offsets[node + 1] {
+                let next = self.targets[edge];
+                let candidate = seconds + self.seconds[edge];
+                if candidate < best[next] {
+                    best[next] = candidate;
+                    queue.push(Visit {
+                        seconds: candidate,
+                        node: next,
+                    });
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Patient × hospital matrix of road travel times, including the
+    /// straight-line legs to and from the snapped nodes and the model's
+    /// congestion factor at `hour`. Pairs with no road connection fall back
+    /// to the model's great-circle estimate.
+    pub fn patients_to_hospitals(
+        &self,
+        problem: &Problem,
+        profile: &RoutingProfile,
+        model: &TravelTimeModel,
+        vehicle: Option<AmbulanceType>,
+        hour: u32,
+    ) -> TravelTimeMatrix {
+        let access_seconds = |km: f64| km / profile.access_speed_kmh * 3_600.0;
+        let snap = |latitude: f64, longitude: f64| {
+            self.snap(Coordinate::new(latitude, longitude))
+                .map(|(node, km)| (node, access_seconds(km)))
+        };
+
+        let hospitals: Vec<Option<(usize, f64)>> = problem
+            .hospitals
+            .iter()
+            .map(|hospital| snap(hospital.latitude, hospital.longitude))
+            .collect();
+        let hospital_nodes: Vec<usize> =
+            hospitals.iter().flatten().map(|(node, _)| *node).collect();
+
+        let road_seconds: Vec<Vec<Option<f64>>> = problem
+            .patients
+            .iter()
+            .map(|patient| {
+                let Some((source, access)) = snap(patient.latitude, patient.longitude) else {
+                    return vec![None; hospitals.len()];
+                };
+                let best = self.shortest_seconds(source, &hospital_nodes);
+                hospitals
+                    .iter()
+                    .map(|hospital| {
+                        let (node, egress) = (*hospital)?;
+                        best[node].is_finite().then(|| {
+                            (access + best[node] + egress) * model.congestion.factor_at(hour)
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        TravelTimeMatrix::from_fn(problem.patients.len(), problem.hospitals.len(), |p, h| {
+            road_seconds[p][h].unwrap_or_else(|| {
+                let patient = &problem.patients[p];
+                let hospital = &problem.hospitals[h];
+                model.travel_seconds(
+                    Coordinate::new(patient.latitude, patient.longitude),
+                    Coordinate::new(hospital.latitude, hospital.longitude),
+                    vehicle,
+                    hour,
+                )
+            })
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Direction {
+    Both,
+    Forward,
+    Backward,
+}
+
+impl Direction {
+    fn of(way: &pbf::WayRecord<'_>) -> Self {
+        match way.tag("oneway") {
+            Some("yes" | "1" | "true") => Direction::Forward,
+            Some("-1" | "reverse") => Direction::Backward,
+            Some("no" | "0" | "false") => Direction::Both,
+            _ if way.tag("highway") == Some("motorway")
+                || way.tag("junction") == Some("roundabout") =>
+            {
+                Direction::Forward
+            }
+            _ => Direction::Both,
+        }
+    }
+}
+
+/// Dijkstra queue entry, ordered so the heap pops the smallest time first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Visit {
+    seconds: f64,
+    node: usize,
+}
+
+impl Eq for Visit {}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .seconds
+            .total_cmp(&self.seconds)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
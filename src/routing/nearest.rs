@@ -0,0 +1,90 @@
+//! Nearest-node lookup for snapping points to the road graph.
+//!
+//! Nodes are stored as unit vectors on the sphere, where the straight-line
+//! (chord) distance grows with the great-circle distance, so the nearest
+//! node in a 3-d k-d tree is also the nearest along the Earth's surface.
+
+use crate::geo::Coordinate;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct NodeIndex {
+    points: Vec<[f64; 3]>,
+    /// Node indices laid out as an implicit balanced k-d tree: the median of
+    /// each range splits it on axis `depth % 3`.
+    tree: Vec<usize>,
+}
+
+impl NodeIndex {
+    pub fn new(coordinates: &[Coordinate]) -> Self {
+        let points: Vec<[f64; 3]> = coordinates.iter().copied().map(unit_vector).collect();
+        let mut tree: Vec<usize> = (0..points.len()).collect();
+        build(&mut tree, &points, 0);
+        NodeIndex { points, tree }
+    }
+
+    /// Index of the node closest to `point`, or `None` when there are none.
+    pub fn nearest(&self, point: Coordinate) -> Option<usize> {
+        let query = unit_vector(point);
+        let mut best = None;
+        self.search(0, self.tree.len(), 0, &query, &mut best);
+        best.map(|(node, _)| node)
+    }
+
+    fn search(
+        &self,
+        start: usize,
+        end: usize,
+        depth: usize,
+        query: &[f64; 3],
+        best: &mut Option<(usize, f64)>,
+    ) {
+        if start >= end {
+            return;
+        }
+
+        let middle = start + (end - start) / 2;
+        let node = self.tree[middle];
+        let distance = squared_distance(&self.points[node], query);
+        if best.is_none_or(|(_, closest)| distance < closest) {
+            *best = Some((node, distance));
+        }
+
+        let axis = depth % 3;
+        let offset = query[axis] - self.points[node][axis];
+        let (near, far) = if offset < 0.0 {
+            ((start, middle), (middle + 1, end))
+        } else {
+            ((middle + 1, end), (start, middle))
+        };
+        self.search(near.0, near.1, depth + 1, query, best);
+        if best.is_none_or(|(_, closest)| offset * offset < closest) {
+            self.search(far.0, far.1, depth + 1, query, best);
+        }
+    }
+}
+
+fn build(tree: &mut [usize], points: &[[f64; 3]], depth: usize) {
+    if tree.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % 3;
+    let middle = tree.len() / 2;
+    tree.select_nth_unstable_by(middle, |a, b| points[*a][axis].total_cmp(&points[*b][axis]));
+    let (left, right) = tree.split_at_mut(middle);
+    build(left, points, depth + 1);
+    build(&mut right[1..], points, depth + 1);
+}
+
+fn unit_vector(point: Coordinate) -> [f64; 3] {
+    let (latitude, longitude) = (point.latitude.to_radians(), point.longitude.to_radians());
+    [
+        latitude.cos() * longitude.cos(),
+        latitude.cos() * longitude.sin(),
+        latitude.sin(),
+    ]
+}
+
+fn squared_distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| (a - b).powi(2)).sum()
+}
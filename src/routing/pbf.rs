@@ -0,0 +1,253 @@
+//! Minimal reader for the OpenStreetMap PBF format.
+//!
+//! Only the messages needed to build a road graph are declared: blob framing,
+//! the header's required features, nodes (plain and dense) and ways. Unknown
+//! fields, relations and metadata are skipped by the decoder.
+
+use super::RoutingError;
+use flate2::read::ZlibDecoder;
+use prost::Message;
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+/// Limits from the format specification.
+const MAX_HEADER_BYTES: usize = 64 * 1024;
+const MAX_BLOB_BYTES: usize = 32 * 1024 * 1024;
+
+const SUPPORTED_FEATURES: &[&str] = &["OsmSchema-V0.6", "DenseNodes"];
+
+#[derive(Clone, PartialEq, Message)]
+struct BlobHeader {
+    #[prost(string, tag = "1")]
+    kind: String,
+    #[prost(int32, tag = "3")]
+    datasize: i32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Blob {
+    #[prost(bytes = "vec", optional, tag = "1")]
+    raw: Option<Vec<u8>>,
+    #[prost(int32, optional, tag = "2")]
+    raw_size: Option<i32>,
+    #[prost(bytes = "vec", optional, tag = "3")]
+    zlib_data: Option<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct HeaderBlock {
+    #[prost(string, repeated, tag = "4")]
+    required_features: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(super) struct PrimitiveBlock {
+    #[prost(message, optional, tag = "1")]
+    stringtable: Option<StringTable>,
+    #[prost(message, repeated, tag = "2")]
+    primitivegroup: Vec<PrimitiveGroup>,
+    #[prost(int32, optional, tag = "17")]
+    granularity: Option<i32>,
+    #[prost(int64, optional, tag = "19")]
+    lat_offset: Option<i64>,
+    #[prost(int64, optional, tag = "20")]
+    lon_offset: Option<i64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct StringTable {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    s: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct PrimitiveGroup {
+    #[prost(message, repeated, tag = "1")]
+    nodes: Vec<Node>,
+    #[prost(message, optional, tag = "2")]
+    dense: Option<DenseNodes>,
+    #[prost(message, repeated, tag = "3")]
+    ways: Vec<Way>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Node {
+    #[prost(sint64, tag = "1")]
+    id: i64,
+    #[prost(sint64, tag = "8")]
+    lat: i64,
+    #[prost(sint64, tag = "9")]
+    lon: i64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct DenseNodes {
+    #[prost(sint64, repeated, tag = "1")]
+    id: Vec<i64>,
+    #[prost(sint64, repeated, tag = "8")]
+    lat: Vec<i64>,
+    #[prost(sint64, repeated, tag = "9")]
+    lon: Vec<i64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Way {
+    #[prost(int64, tag = "1")]
+    id: i64,
+    #[prost(uint32, repeated, tag = "2")]
+    keys: Vec<u32>,
+    #[prost(uint32, repeated, tag = "3")]
+    vals: Vec<u32>,
+    #[prost(sint64, repeated, tag = "8")]
+    refs: Vec<i64>,
+}
+
+/// A way with its tags resolved and node references un-delta-coded.
+pub(super) struct WayRecord<'a> {
+    pub tags: Vec<(&'a str, &'a str)>,
+    pub refs: Vec<i64>,
+}
+
+impl WayRecord<'_> {
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| *value)
+    }
+}
+
+impl PrimitiveBlock {
+    /// Calls `visit` with `(id, latitude, longitude)` for every node.
+    pub fn for_each_node(&self, mut visit: impl FnMut(i64, f64, f64)) {
+        let granularity = self.granularity.unwrap_or(100) as f64;
+        let lat_offset = self.lat_offset.unwrap_or(0) as f64;
+        let lon_offset = self.lon_offset.unwrap_or(0) as f64;
+        let degrees = |offset: f64, value: i64| (offset + granularity * value as f64) * 1e-9;
+
+        for group in &self.primitivegroup {
+            for node in &group.nodes {
+                visit(
+                    node.id,
+                    degrees(lat_offset, node.lat),
+                    degrees(lon_offset, node.lon),
+                );
+            }
+
+            if let Some(dense) = &group.dense {
+                let (mut id, mut lat, mut lon) = (0i64, 0i64, 0i64);
+                for ((d_id, d_lat), d_lon) in dense.id.iter().zip(&dense.lat).zip(&dense.lon) {
+                    id += d_id;
+                    lat += d_lat;
+                    lon += d_lon;
+                    visit(id, degrees(lat_offset, lat), degrees(lon_offset, lon));
+                }
+            }
+        }
+    }
+
+    pub fn for_each_way(&self, mut visit: impl FnMut(WayRecord<'_>)) {
+        let strings: &[Vec<u8>] = self.stringtable.as_ref().map_or(&[], |table| &table.s);
+        let string = |index: u32| {
+            strings
+                .get(index as usize)
+                .and_then(|bytes| std::str::from_utf8(bytes).ok())
+                .unwrap_or("")
+        };
+
+        for group in &self.primitivegroup {
+            for way in &group.ways {
+                let tags = way
+                    .keys
+                    .iter()
+                    .zip(&way.vals)
+                    .map(|(key, value)| (string(*key), string(*value)))
+                    .collect();
+                let refs = way
+                    .refs
+                    .iter()
+                    .scan(0i64, |id, delta| {
+                        *id += delta;
+                        Some(*id)
+                    })
+                    .collect();
+                visit(WayRecord { tags, refs });
+            }
+        }
+    }
+}
+
+/// Streams the data blocks of an `.osm.pbf` file.
+pub(super) fn for_each_block(
+    path: &Path,
+    mut visit: impl FnMut(&PrimitiveBlock),
+) -> Result<(), RoutingError> {
+    let mut reader = BufReader::new(File::open(path)?);
+
+    while let Some(header) = read_blob_header(&mut reader)? {
+        let size = usize::try_from(header.datasize)
+            .ok()
+            .filter(|size| *size <= MAX_BLOB_BYTES)
+            .ok_or_else(|| RoutingError::Decode(format!("blob of {} bytes", header.datasize)))?;
+        let mut bytes = vec![0; size];
+        reader.read_exact(&mut bytes)?;
+        let data = blob_data(Blob::decode(bytes.as_slice())?)?;
+
+        match header.kind.as_str() {
+            "OSMHeader" => {
+                let block = HeaderBlock::decode(data.as_slice())?;
+                if let Some(feature) = block
+                    .required_features
+                    .iter()
+                    .find(|feature| !SUPPORTED_FEATURES.contains(&feature.as_str()))
+                {
+                    return Err(RoutingError::Unsupported(format!(
+                        "required feature '{feature}'"
+                    )));
+                }
+            }
+            "OSMData" => visit(&PrimitiveBlock::decode(data.as_slice())?),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn read_blob_header(reader: &mut impl Read) -> Result<Option<BlobHeader>, RoutingError> {
+    let mut length = [0u8; 4];
+    match reader.read_exact(&mut length) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err.into()),
+    }
+
+    let length = u32::from_be_bytes(length) as usize;
+    if length > MAX_HEADER_BYTES {
+        return Err(RoutingError::Decode(format!(
+            "blob header of {length} bytes"
+        )));
+    }
+    let mut bytes = vec![0; length];
+    reader.read_exact(&mut bytes)?;
+    Ok(Some(BlobHeader::decode(bytes.as_slice())?))
+}
+
+fn blob_data(blob: Blob) -> Result<Vec<u8>, RoutingError> {
+    if let Some(raw) = blob.raw {
+        return Ok(raw);
+    }
+
+    let Some(compressed) = blob.zlib_data else {
+        return Err(RoutingError::Unsupported(
+            "blob compression other than zlib".to_string(),
+        ));
+    };
+    let capacity = blob.raw_size.unwrap_or(0).clamp(0, MAX_BLOB_BYTES as i32) as usize;
+    let mut data = Vec::with_capacity(capacity);
+    ZlibDecoder::new(compressed.as_slice()).read_to_end(&mut data)?;
+    Ok(data)
+}
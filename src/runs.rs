@@ -0,0 +1,293 @@
+//! Persistence of optimization runs for auditing.
+//!
+//! Every run is stored as one `optimization_runs` row plus one `assignments`
+//! row per patient. The input hash identifies the exact snapshot the run was
+//! given. Runs over the same snapshot share a hash, so different backends or
+//! parameters can be compared like for like.
+
+use crate::{
+    dispatch::{DispatchOutcome, DispatchPlan, DispatchProblem},
+    optimize::{
+        AssignmentOutcome, AssignmentPlan, BucketRef, Diagnostics, PatientAssignment, Problem,
+        SurvivalModel, UnassignedReason, objective,
+    },
+};
+use entity::{TriageLevel, assignment, optimization_run};
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr,
+    EntityTrait, QueryFilter, QueryOrder, TransactionTrait, prelude::Json,
+};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+/// What a run decided for one patient.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decision {
+    pub patient_id: i32,
+    pub triage_level: TriageLevel,
+    /// `None` when the patient was left unassigned.
+    pub bucket: Option<BucketRef>,
+    pub ambulance_id: Option<i32>,
+    pub time_to_treatment_seconds: Option<f64>,
+    pub survival_probability: f64,
+    pub unassigned_reason: Option<String>,
+}
+
+/// Decisions of an assignment plan, in [`Problem::patients`] order.
+pub fn plan_decisions(problem: &Problem, plan: &AssignmentPlan) -> Vec<Decision> {
+    let survival = objective::survival_report(problem, plan);
+
+    plan.assignments
+        .iter()
+        .zip(&problem.patients)
+        .zip(survival)
+        .map(|((assignment, patient), survival)| {
+            let (bucket, unassigned_reason) = match &assignment.outcome {
+                AssignmentOutcome::Assigned(bucket) => (Some(bucket.clone()), None),
+                AssignmentOutcome::Unassigned(reason) => (None, Some(reason.to_string())),
+            };
+
+            Decision {
+                patient_id: assignment.patient_id,
+                triage_level: patient.condition,
+                bucket,
+                ambulance_id: None,
+                time_to_treatment_seconds: survival.time_to_treatment_seconds,
+                survival_probability: survival.survival_probability,
+                unassigned_reason,
+            }
+        })
+        .collect()
+}
+
+/// Decisions of a dispatch plan, in [`Problem::patients`] order.
+pub fn dispatch_decisions(dispatch: &DispatchProblem, plan: &DispatchPlan) -> Vec<Decision> {
+    let problem = &dispatch.problem;
+
+    plan.assignments
+        .iter()
+        .zip(&problem.patients)
+        .map(|(assignment, patient)| match &assignment.outcome {
+            DispatchOutcome::Dispatched(dispatched) => {
+                let seconds = dispatched.time_to_treatment_seconds() as f64;
+                Decision {
+                    patient_id: assignment.patient_id,
+                    triage_level: patient.condition,
+                    bucket: Some(dispatched.bucket.clone()),
+                    ambulance_id: Some(dispatched.ambulance_id),
+                    time_to_treatment_seconds: Some(seconds),
+                    survival_probability: problem.survival.probability(patient.condition, seconds),
+                    unassigned_reason: None,
+                }
+            }
+            DispatchOutcome::Unserved(reason) => Decision {
+                patient_id: assignment.patient_id,
+                triage_level: patient.condition,
+                bucket: None,
+                ambulance_id: None,
+                time_to_treatment_seconds: None,
+                survival_probability: 0.0,
+                unassigned_reason: Some(reason.to_string()),
+            },
+        })
+        .collect()
+}
+
+/// Survival curves as run parameters, keyed by triage level.
+pub fn survival_parameters(survival: &SurvivalModel) -> Json {
+    TriageLevel::ALL
+        .iter()
+        .map(|level| (level.to_value(), json!(survival.curve(*level))))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+/// Hex SHA-256 of everything the solvers read from `problem`: hospitals,
+/// buckets, patients, travel times and survival curves.
+pub fn input_hash(problem: &Problem) -> String {
+    let mut hasher = Sha256::new();
+    hash_problem(&mut hasher, problem);
+    hex(&hasher.finalize())
+}
+
+/// [`input_hash`] extended with the available ambulances.
+pub fn dispatch_input_hash(dispatch: &DispatchProblem) -> String {
+    let mut hasher = Sha256::new();
+    hash_problem(&mut hasher, &dispatch.problem);
+    hasher.update((dispatch.ambulances.len() as u64).to_le_bytes());
+    for ambulance in &dispatch.ambulances {
+        hasher.update(ambulance.id.to_le_bytes());
+        hasher.update(ambulance.ambulance_type.label().as_bytes());
+        hasher.update(ambulance.latitude.to_le_bytes());
+        hasher.update(ambulance.longitude.to_le_bytes());
+    }
+    hex(&hasher.finalize())
+}
+
+fn hash_problem(hasher: &mut Sha256, problem: &Problem) {
+    hasher.update((problem.hospitals.len() as u64).to_le_bytes());
+    for hospital in &problem.hospitals {
+        hasher.update(hospital.id.to_le_bytes());
+        hasher.update(hospital.latitude.to_le_bytes());
+        hasher.update(hospital.longitude.to_le_bytes());
+        hasher.update(hospital.bed_capacity.to_le_bytes());
+        hasher.update(hospital.occupied_beds.to_le_bytes());
+    }
+
+    hasher.update((problem.buckets.len() as u64).to_le_bytes());
+    for bucket in &problem.buckets {
+        hasher.update(bucket.id.to_le_bytes());
+        hasher.update((bucket.hospital as u64).to_le_bytes());
+        hasher.update([level_code(bucket.triage_level)]);
+        hasher.update(bucket.waiting_time_seconds.to_le_bytes());
+        hasher.update(bucket.capacity.to_le_bytes());
+    }
+
+    hasher.update((problem.patients.len() as u64).to_le_bytes());
+    for patient in &problem.patients {
+        hasher.update(patient.id.to_le_bytes());
+        hasher.update([level_code(patient.condition)]);
+        hasher.update(patient.latitude.to_le_bytes());
+        hasher.update(patient.longitude.to_le_bytes());
+    }
+
+    match &problem.travel_times {
+        Some(matrix) => {
+            hasher.update([1]);
+            for row in 0..matrix.rows() {
+                for column in 0..matrix.columns() {
+                    hasher.update(matrix.get(row, column).to_le_bytes());
+                }
+            }
+        }
+        None => hasher.update([0]),
+    }
+
+    for level in TriageLevel::ALL {
+        let curve = problem.survival.curve(level);
+        hasher.update(curve.baseline.to_le_bytes());
+        hasher.update(curve.midpoint_minutes.to_le_bytes());
+        hasher.update(curve.scale_minutes.to_le_bytes());
+    }
+}
+
+fn level_code(level: TriageLevel) -> u8 {
+    TriageLevel::ALL
+        .iter()
+        .position(|candidate| *candidate == level)
+        .expect("TriageLevel::ALL lists every level") as u8
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Stores a run and its decisions in one transaction.
+pub async fn record<C: ConnectionTrait + TransactionTrait>(
+    db: &C,
+    input_hash: String,
+    diagnostics: &Diagnostics,
+    parameters: Json,
+    decisions: &[Decision],
+) -> Result<optimization_run::Model, DbErr> {
+    let objectives: Vec<Json> = diagnostics
+        .objectives
+        .iter()
+        .map(|objective| json!({ "criterion": objective.criterion.name(), "value": objective.value }))
+        .collect();
+
+    let txn = db.begin().await?;
+
+    let run = optimization_run::ActiveModel {
+        input_hash: Set(input_hash),
+        backend: Set(diagnostics.backend.to_string()),
+        parameters: Set(parameters),
+        status: Set(diagnostics.status.label().to_string()),
+        objective_value: Set(diagnostics.objective_value),
+        objective_bound: Set(diagnostics.objective_bound),
+        objectives: Set(Json::Array(objectives)),
+        wall_time_ms: Set(diagnostics.wall_time.as_millis() as i64),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    if !decisions.is_empty() {
+        let rows = decisions.iter().map(|decision| assignment::ActiveModel {
+            run_id: Set(run.id),
+            patient_id: Set(decision.patient_id),
+            triage_level: Set(decision.triage_level),
+            hospital_id: Set(decision.bucket.as_ref().map(|bucket| bucket.hospital_id)),
+            hospital_speciality_id: Set(decision
+                .bucket
+                .as_ref()
+                .map(|bucket| bucket.speciality_id)),
+            ambulance_id: Set(decision.ambulance_id),
+            time_to_treatment_seconds: Set(decision.time_to_treatment_seconds),
+            survival_probability: Set(decision.survival_probability),
+            unassigned_reason: Set(decision.unassigned_reason.clone()),
+            ..Default::default()
+        });
+        assignment::Entity::insert_many(rows).exec(&txn).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(run)
+}
+
+/// The plan stored for run `run_id`, e.g. to re-optimize incrementally from
+/// it. `None` when there is no such run.
+pub async fn load_plan<C: ConnectionTrait>(
+    db: &C,
+    run_id: i32,
+) -> Result<Option<AssignmentPlan>, DbErr> {
+    if optimization_run::Entity::find_by_id(run_id)
+        .one(db)
+        .await?
+        .is_none()
+    {
+        return Ok(None);
+    }
+
+    let rows = assignment::Entity::find()
+        .filter(assignment::Column::RunId.eq(run_id))
+        .order_by_asc(assignment::Column::Id)
+        .all(db)
+        .await?;
+
+    let assignments = rows
+        .into_iter()
+        .map(|row| {
+            let outcome = match (row.hospital_id, row.hospital_speciality_id) {
+                (Some(hospital_id), Some(speciality_id)) => {
+                    AssignmentOutcome::Assigned(BucketRef {
+                        hospital_id,
+                        speciality_id,
+                        triage_level: row.triage_level,
+                    })
+                }
+                _ => AssignmentOutcome::Unassigned(unassigned_reason(
+                    row.unassigned_reason.as_deref(),
+                )),
+            };
+            PatientAssignment {
+                patient_id: row.patient_id,
+                outcome,
+            }
+        })
+        .collect();
+
+    Ok(Some(AssignmentPlan { assignments }))
+}
+
+/// The reason stored by [`record`]; dispatch reasons map to `NotSelected`.
+fn unassigned_reason(label: Option<&str>) -> UnassignedReason {
+    [
+        UnassignedReason::NoMatchingTriage,
+        UnassignedReason::CapacityExhausted,
+    ]
+    .into_iter()
+    .find(|reason| Some(reason.label()) == label)
+    .unwrap_or(UnassignedReason::NotSelected)
+}
@@ -0,0 +1,541 @@
+//! Scenario files: a database snapshot that can be replayed without Postgres.
+//!
+//! A [`Scenario`] holds the hospitals with their triage buckets and bed
+//! occupancy, the patients, the ambulances and, optionally, the parameters a
+//! run was made with. It is written as TOML, or as JSON when the file name
+//! ends in `.json`:
+//!
+//! ```toml
+//! version = 1
+//! taken_at = "2026-10-13T23:40:00+01:00"
+//!
+//! [parameters]
+//! solver = "exact"
+//!
+//! [[hospitals]]
+//! id = 1
+//! name = "Hospital de Santa Maria"
+//! latitude = 38.7487
+//! longitude = -9.1603
+//! bed_capacity = 40
+//!
+//! [[hospitals.triage_buckets]]
+//! id = 1
+//! triage_level = "red"
+//! waiting_time_seconds = 300
+//! beds_capacity = 4
+//! occupied_beds = 1
+//!
+//! [[patients]]
+//! id = 7
+//! condition = "red"
+//! latitude = 38.7369
+//! longitude = -9.1427
+//! ```
+//!
+//! [`Scenario::problem`] builds the same [`Problem`] as loading the rows it
+//! was [exported](export) from, so a replay has the input hash of the
+//! original run.
+
+use crate::{
+    dispatch::DispatchProblem,
+    occupancy::{self, BedAvailability},
+    optimize::{ObjectiveConfig, Problem, ProblemError, multi_objective::ObjectiveConfigError},
+};
+use chrono::Timelike;
+use entity::{
+    TriageLevel,
+    ambulance::{self, AmbulanceType, CrewStatus},
+    hospital, hospital_speciality, patient,
+    patient::PatientStatus,
+    prelude::*,
+};
+use sea_orm::{ConnectionTrait, DbErr, EntityTrait, prelude::DateTimeWithTimeZone};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt, io, path::Path};
+
+/// The format version written by this build and the only one it reads.
+pub const SCENARIO_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// When the snapshot was taken; the default for `reported_at` and
+    /// `departure_hour`.
+    pub taken_at: DateTimeWithTimeZone,
+    #[serde(default)]
+    pub parameters: ScenarioParameters,
+    #[serde(default)]
+    pub hospitals: Vec<ScenarioHospital>,
+    #[serde(default)]
+    pub patients: Vec<ScenarioPatient>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ambulances: Vec<ScenarioAmbulance>,
+}
+
+/// How to solve the scenario. Unset values fall back to the environment
+/// variables of the same name, as for a database run.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioParameters {
+    /// `OPTIMIZATION_MODE`: `assignment` or `dispatch`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solver: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warm_start: Option<String>,
+    /// In the `SURVIVAL_CURVES` format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub survival_curves: Option<String>,
+    /// Inline `OBJECTIVES_FILE` for the `multi-objective` backend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub objectives: Option<ObjectiveConfig>,
+    /// Local hour (0-23) the ambulances leave; defaults to the hour of
+    /// `taken_at`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub departure_hour: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioHospital {
+    pub id: i32,
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub bed_capacity: i32,
+    #[serde(default)]
+    pub triage_buckets: Vec<ScenarioBucket>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioBucket {
+    pub id: i32,
+    pub triage_level: TriageLevel,
+    pub waiting_time_seconds: i64,
+    /// Nominal capacity, as in `hospital_specialities`.
+    pub beds_capacity: i32,
+    /// Beds in use when the snapshot was taken.
+    #[serde(default)]
+    pub occupied_beds: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioPatient {
+    pub id: i32,
+    pub condition: TriageLevel,
+    pub latitude: f64,
+    pub longitude: f64,
+    #[serde(default = "reported")]
+    pub status: PatientStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reported_at: Option<DateTimeWithTimeZone>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admitted_at: Option<DateTimeWithTimeZone>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assigned_hospital_id: Option<i32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioAmbulance {
+    pub id: i32,
+    pub call_sign: String,
+    pub ambulance_type: AmbulanceType,
+    #[serde(default = "available")]
+    pub crew_status: CrewStatus,
+    #[serde(default = "one")]
+    pub capacity: i32,
+    pub latitude: f64,
+    pub longitude: f64,
+    #[serde(default)]
+    pub home_base: String,
+    /// Defaults to the current position.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub home_latitude: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub home_longitude: Option<f64>,
+}
+
+fn reported() -> PatientStatus {
+    PatientStatus::Reported
+}
+
+fn available() -> CrewStatus {
+    CrewStatus::Available
+}
+
+fn one() -> i32 {
+    1
+}
+
+#[derive(Debug)]
+pub enum ScenarioError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    TomlWrite(toml::ser::Error),
+    Json(serde_json::Error),
+    UnsupportedVersion(u32),
+    DuplicateId { table: &'static str, id: i32 },
+    Problem(ProblemError),
+    Objectives(ObjectiveConfigError),
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioError::Io(err) => write!(f, "failed to access the scenario file: {err}"),
+            ScenarioError::Toml(err) => write!(f, "invalid scenario file: {err}"),
+            ScenarioError::TomlWrite(err) => write!(f, "failed to write the scenario: {err}"),
+            ScenarioError::Json(err) => write!(f, "invalid scenario JSON: {err}"),
+            ScenarioError::UnsupportedVersion(version) => write!(
+                f,
+                "scenario version {version} is not supported; this build reads version {SCENARIO_VERSION}"
+            ),
+            ScenarioError::DuplicateId { table, id } => {
+                write!(f, "scenario lists {table} id {id} more than once")
+            }
+            ScenarioError::Problem(err) => write!(f, "invalid scenario: {err}"),
+            ScenarioError::Objectives(err) => write!(f, "invalid scenario objectives: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScenarioError::Io(err) => Some(err),
+            ScenarioError::Toml(err) => Some(err),
+            ScenarioError::TomlWrite(err) => Some(err),
+            ScenarioError::Json(err) => Some(err),
+            ScenarioError::Problem(err) => Some(err),
+            ScenarioError::Objectives(err) => Some(err),
+            ScenarioError::UnsupportedVersion(_) | ScenarioError::DuplicateId { .. } => None,
+        }
+    }
+}
+
+impl From<ProblemError> for ScenarioError {
+    fn from(err: ProblemError) -> Self {
+        ScenarioError::Problem(err)
+    }
+}
+
+impl Scenario {
+    /// Reads a scenario file, as JSON when `path` ends in `.json` and as TOML
+    /// otherwise.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ScenarioError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(ScenarioError::Io)?;
+        if is_json(path) {
+            Self::from_json(&text)
+        } else {
+            Self::from_toml(&text)
+        }
+    }
+
+    /// Writes the scenario in the format [`Scenario::load`] picks for `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ScenarioError> {
+        let path = path.as_ref();
+        let text = if is_json(path) {
+            serde_json::to_string_pretty(self).map_err(ScenarioError::Json)? + "\n"
+        } else {
+            self.to_toml()?
+        };
+        std::fs::write(path, text).map_err(ScenarioError::Io)
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, ScenarioError> {
+        let scenario: Scenario = toml::from_str(text).map_err(ScenarioError::Toml)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, ScenarioError> {
+        let scenario: Scenario = serde_json::from_str(text).map_err(ScenarioError::Json)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn to_toml(&self) -> Result<String, ScenarioError> {
+        toml::to_string(self).map_err(ScenarioError::TomlWrite)
+    }
+
+    /// Checks the version, the objectives and that no id is used twice
+    /// within a table.
+    pub fn validate(&self) -> Result<(), ScenarioError> {
+        if self.version != SCENARIO_VERSION {
+            return Err(ScenarioError::UnsupportedVersion(self.version));
+        }
+        if let Some(objectives) = &self.parameters.objectives {
+            objectives.validate().map_err(ScenarioError::Objectives)?;
+        }
+
+        let buckets = self
+            .hospitals
+            .iter()
+            .flat_map(|hospital| &hospital.triage_buckets);
+        unique(
+            "hospitals",
+            self.hospitals.iter().map(|hospital| hospital.id),
+        )?;
+        unique("hospital_specialities", buckets.map(|bucket| bucket.id))?;
+        unique("patients", self.patients.iter().map(|patient| patient.id))?;
+        unique(
+            "ambulances",
+            self.ambulances.iter().map(|ambulance| ambulance.id),
+        )
+    }
+
+    /// Snapshots the given rows, with each bucket's occupancy taken from
+    /// `availability`. Buckets are listed under their hospital, so any
+    /// whose hospital is missing are left out.
+    pub fn from_models(
+        taken_at: DateTimeWithTimeZone,
+        hospitals: &[hospital::Model],
+        specialities: &[hospital_speciality::Model],
+        availability: &[BedAvailability],
+        patients: &[patient::Model],
+        ambulances: &[ambulance::Model],
+    ) -> Self {
+        let mut hospitals: Vec<ScenarioHospital> = hospitals
+            .iter()
+            .map(|hospital| ScenarioHospital {
+                id: hospital.id,
+                name: hospital.location_name.clone(),
+                latitude: hospital.latitude,
+                longitude: hospital.longitude,
+                bed_capacity: hospital.bed_capacity,
+                triage_buckets: Vec::new(),
+            })
+            .collect();
+        hospitals.sort_by_key(|hospital| hospital.id);
+
+        let mut specialities: Vec<&hospital_speciality::Model> = specialities.iter().collect();
+        specialities.sort_by_key(|speciality| speciality.id);
+        for speciality in specialities {
+            let Some(hospital) = hospitals
+                .iter_mut()
+                .find(|hospital| hospital.id == speciality.hospital_id)
+            else {
+                continue;
+            };
+            let occupied_beds = availability
+                .iter()
+                .find(|entry| entry.speciality_id == speciality.id)
+                .map_or(0, |entry| entry.occupied);
+            hospital.triage_buckets.push(ScenarioBucket {
+                id: speciality.id,
+                triage_level: speciality.triage_level,
+                waiting_time_seconds: speciality.waiting_time_seconds,
+                beds_capacity: speciality.beds_capacity,
+                occupied_beds,
+            });
+        }
+
+        let mut patients: Vec<ScenarioPatient> = patients
+            .iter()
+            .map(|patient| ScenarioPatient {
+                id: patient.id,
+                condition: patient.condition,
+                latitude: patient.latitude,
+                longitude: patient.longitude,
+                status: patient.status,
+                reported_at: Some(patient.reported_at),
+                admitted_at: patient.admitted_at,
+                assigned_hospital_id: patient.assigned_hospital_id,
+            })
+            .collect();
+        patients.sort_by_key(|patient| patient.id);
+
+        let mut ambulances: Vec<ScenarioAmbulance> = ambulances
+            .iter()
+            .map(|ambulance| ScenarioAmbulance {
+                id: ambulance.id,
+                call_sign: ambulance.call_sign.clone(),
+                ambulance_type: ambulance.ambulance_type,
+                crew_status: ambulance.crew_status,
+                capacity: ambulance.capacity,
+                latitude: ambulance.latitude,
+                longitude: ambulance.longitude,
+                home_base: ambulance.home_base.clone(),
+                home_latitude: Some(ambulance.home_latitude),
+                home_longitude: Some(ambulance.home_longitude),
+            })
+            .collect();
+        ambulances.sort_by_key(|ambulance| ambulance.id);
+
+        Scenario {
+            version: SCENARIO_VERSION,
+            description: None,
+            taken_at,
+            parameters: ScenarioParameters {
+                departure_hour: Some(taken_at.hour()),
+                ..Default::default()
+            },
+            hospitals,
+            patients,
+            ambulances,
+        }
+    }
+
+    pub fn hospitals(&self) -> Vec<hospital::Model> {
+        self.hospitals
+            .iter()
+            .map(|hospital| hospital::Model {
+                id: hospital.id,
+                location_name: hospital.name.clone(),
+                latitude: hospital.latitude,
+                longitude: hospital.longitude,
+                bed_capacity: hospital.bed_capacity,
+            })
+            .collect()
+    }
+
+    /// The triage buckets with their free beds as `beds_capacity`, like
+    /// [`occupancy::live_specialities`] returns them.
+    pub fn specialities(&self) -> Vec<hospital_speciality::Model> {
+        self.hospitals
+            .iter()
+            .flat_map(|hospital| {
+                hospital
+                    .triage_buckets
+                    .iter()
+                    .map(|bucket| hospital_speciality::Model {
+                        id: bucket.id,
+                        hospital_id: hospital.id,
+                        triage_level: bucket.triage_level,
+                        waiting_time_seconds: bucket.waiting_time_seconds,
+                        beds_capacity: bucket.beds_capacity.max(0)
+                            - bucket.occupied_beds.clamp(0, bucket.beds_capacity.max(0)),
+                    })
+            })
+            .collect()
+    }
+
+    pub fn patients(&self) -> Vec<patient::Model> {
+        self.patients
+            .iter()
+            .map(|patient| patient::Model {
+                id: patient.id,
+                condition: patient.condition,
+                latitude: patient.latitude,
+                longitude: patient.longitude,
+                status: patient.status,
+                reported_at: patient.reported_at.unwrap_or(self.taken_at),
+                admitted_at: patient.admitted_at,
+                assigned_hospital_id: patient.assigned_hospital_id,
+            })
+            .collect()
+    }
+
+    pub fn ambulances(&self) -> Vec<ambulance::Model> {
+        self.ambulances
+            .iter()
+            .map(|ambulance| ambulance::Model {
+                id: ambulance.id,
+                call_sign: ambulance.call_sign.clone(),
+                ambulance_type: ambulance.ambulance_type,
+                crew_status: ambulance.crew_status,
+                capacity: ambulance.capacity,
+                latitude: ambulance.latitude,
+                longitude: ambulance.longitude,
+                home_base: ambulance.home_base.clone(),
+                home_latitude: ambulance.home_latitude.unwrap_or(ambulance.latitude),
+                home_longitude: ambulance.home_longitude.unwrap_or(ambulance.longitude),
+            })
+            .collect()
+    }
+
+    /// The local hour ambulances leave at.
+    pub fn departure_hour(&self) -> u32 {
+        self.parameters
+            .departure_hour
+            .unwrap_or_else(|| self.taken_at.hour())
+    }
+
+    /// Occupancy of every triage bucket when the snapshot was taken, like
+    /// [`occupancy::available_beds_at`] returns it.
+    pub fn availability(&self) -> Vec<BedAvailability> {
+        self.hospitals
+            .iter()
+            .flat_map(|hospital| {
+                hospital.triage_buckets.iter().map(|bucket| {
+                    let capacity = bucket.beds_capacity.max(0);
+                    let occupied = bucket.occupied_beds.clamp(0, capacity);
+                    BedAvailability {
+                        speciality_id: bucket.id,
+                        hospital_id: hospital.id,
+                        triage_level: bucket.triage_level,
+                        capacity: bucket.beds_capacity,
+                        occupied,
+                        available: capacity - occupied,
+                        recorded_at: Some(self.taken_at),
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// The assignment input, without travel times or survival curves.
+    pub fn problem(&self) -> Result<Problem, ScenarioError> {
+        Ok(
+            Problem::from_models(&self.hospitals(), &self.specialities(), &self.patients())?
+                .with_occupied_beds(&self.availability()),
+        )
+    }
+
+    /// The dispatch input, without travel times or survival curves.
+    pub fn dispatch_problem(&self) -> Result<DispatchProblem, ScenarioError> {
+        let mut dispatch = DispatchProblem::from_models(
+            &self.hospitals(),
+            &self.specialities(),
+            &self.patients(),
+            &self.ambulances(),
+        )?;
+        dispatch.problem = dispatch.problem.with_occupied_beds(&self.availability());
+        Ok(dispatch)
+    }
+}
+
+/// Snapshots the database, with bed occupancy as of `at`.
+pub async fn export<C: ConnectionTrait>(
+    db: &C,
+    at: DateTimeWithTimeZone,
+) -> Result<Scenario, DbErr> {
+    let hospitals = Hospital::find().all(db).await?;
+    let specialities = HospitalSpeciality::find().all(db).await?;
+    let availability = occupancy::available_beds_at(db, at).await?;
+    let patients = Patient::find().all(db).await?;
+    let ambulances = Ambulance::find().all(db).await?;
+
+    Ok(Scenario::from_models(
+        at,
+        &hospitals,
+        &specialities,
+        &availability,
+        &patients,
+        &ambulances,
+    ))
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+}
+
+fn unique(table: &'static str, ids: impl Iterator<Item = i32>) -> Result<(), ScenarioError> {
+    let mut seen = HashSet::new();
+    for id in ids {
+        if !seen.insert(id) {
+            return Err(ScenarioError::DuplicateId { table, id });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,620 @@
+//! Discrete-event simulation of an emergency shift.
+//!
+//! [`simulate`] starts from a [`Scenario`]'s hospitals, bed occupancy and
+//! pending patients and plays out [`SimulationConfig::duration`]. Patients
+//! arrive as a Poisson process with a configurable triage mix, uniformly over
+//! the hospitals' surroundings. After every arrival and every bed release the
+//! solver assigns the waiting patients to the beds left. An assigned patient
+//! holds the bed from that moment, is treated after the travel time plus the
+//! bucket's waiting time, and frees the bed after a log-normal length of stay
+//! for their triage level. Patients the solver leaves waiting longer than
+//! [`SimulationConfig::patience`], or whose triage level no hospital takes,
+//! are turned away. Beds occupied at the start free up after a fresh length
+//! of stay.
+
+use crate::{
+    optimize::{
+        AssignmentOutcome, PatientDemand, Problem, ProblemError, SolveError, Solver, SurvivalModel,
+        UnassignedReason,
+    },
+    scenario::Scenario,
+    travel::TravelTimes,
+};
+use entity::{TriageLevel, hospital_speciality};
+use rand::{Rng, SeedableRng, distr::weighted::WeightedIndex, rngs::StdRng};
+use rand_distr::{Distribution, Exp, LogNormal};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Degrees around the hospitals within which patients appear.
+const AREA_MARGIN_DEGREES: f64 = 0.02;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationConfig {
+    pub duration: Duration,
+    pub arrivals_per_hour: f64,
+    /// Relative frequency of each triage level, in [`TriageLevel::ALL`] order.
+    pub triage_mix: [f64; 5],
+    /// Length of stay of each triage level, in [`TriageLevel::ALL`] order.
+    pub length_of_stay: [LengthOfStay; 5],
+    /// How long a patient waits for a bed before being turned away.
+    pub patience: Duration,
+    pub seed: u64,
+}
+
+/// Log-normal length of stay.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LengthOfStay {
+    pub median: Duration,
+    /// Standard deviation of the stay's logarithm.
+    pub sigma: f64,
+}
+
+/// A 12-hour shift with 8 arrivals an hour, a typical Manchester triage mix
+/// and stays from 6 hours (Red) down to 90 minutes (Blue).
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        let stay = |hours: f64| LengthOfStay {
+            median: Duration::from_secs_f64(hours * 3_600.0),
+            sigma: 0.6,
+        };
+
+        SimulationConfig {
+            duration: Duration::from_secs(12 * 3_600),
+            arrivals_per_hour: 8.0,
+            triage_mix: [0.02, 0.10, 0.35, 0.45, 0.08],
+            length_of_stay: [stay(6.0), stay(5.0), stay(4.0), stay(2.5), stay(1.5)],
+            patience: Duration::from_secs(3_600),
+            seed: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationReport {
+    pub solver: &'static str,
+    /// Solver calls, one per event that left patients waiting.
+    pub decisions: usize,
+    pub solver_time: Duration,
+    /// One entry per triage level, most urgent first.
+    pub levels: Vec<LevelMetrics>,
+    /// One entry per hospital, by id.
+    pub hospitals: Vec<HospitalMetrics>,
+    /// Patients still waiting or on their way when the shift ended.
+    pub unfinished: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelMetrics {
+    pub triage_level: TriageLevel,
+    pub arrivals: usize,
+    /// Patients whose treatment started during the shift.
+    pub treated: usize,
+    pub turned_away: usize,
+    /// From arrival to the start of treatment: waiting for a bed, transport
+    /// and the bucket's waiting time.
+    pub mean_time_to_treatment_seconds: Option<f64>,
+    pub mean_survival_probability: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HospitalMetrics {
+    pub hospital_id: i32,
+    pub name: String,
+    /// Beds over all of the hospital's triage buckets.
+    pub beds: u32,
+    /// Share of bed-time in use or reserved over the shift.
+    pub utilization: f64,
+    pub peak_occupied: u32,
+}
+
+#[derive(Debug)]
+pub enum SimulationError {
+    InvalidConfig(String),
+    Problem(ProblemError),
+    Solve(SolveError),
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulationError::InvalidConfig(details) => {
+                write!(f, "invalid simulation settings: {details}")
+            }
+            SimulationError::Problem(err) => write!(f, "invalid hospital network: {err}"),
+            SimulationError::Solve(err) => write!(f, "the solver failed mid-shift: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SimulationError::InvalidConfig(_) => None,
+            SimulationError::Problem(err) => Some(err),
+            SimulationError::Solve(err) => Some(err),
+        }
+    }
+}
+
+/// Plays out one shift starting at the scenario's departure hour.
+pub fn simulate(
+    scenario: &Scenario,
+    solver: &dyn Solver,
+    travel_times: &TravelTimes,
+    survival: &SurvivalModel,
+    config: &SimulationConfig,
+) -> Result<SimulationReport, SimulationError> {
+    let mut shift = Shift::new(scenario, solver, travel_times, survival, config)?;
+    shift.run()?;
+    Ok(shift.report())
+}
+
+#[derive(Clone, Copy, Debug)]
+enum EventKind {
+    Arrival,
+    /// Treatment starts for the patient at this index of `Shift::patients`.
+    Admission {
+        patient: usize,
+    },
+    Discharge {
+        bucket: usize,
+    },
+    GiveUp {
+        patient: usize,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Event {
+    at: f64,
+    /// Breaks ties in scheduling order.
+    sequence: u64,
+    kind: EventKind,
+}
+
+/// Earliest first in a max-heap.
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .at
+            .total_cmp(&self.at)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Event {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PatientState {
+    Waiting,
+    InTransport,
+    Treated {
+        time_to_treatment_seconds: f64,
+        survival_probability: f64,
+    },
+    TurnedAway,
+}
+
+struct SimulatedPatient {
+    demand: PatientDemand,
+    arrived_at: f64,
+    state: PatientState,
+}
+
+struct Shift<'a> {
+    solver: &'a dyn Solver,
+    travel_times: &'a TravelTimes,
+    config: &'a SimulationConfig,
+    /// Every bucket at its nominal capacity.
+    network: Problem,
+    occupied: Vec<u32>,
+    hospital_usage: Vec<Usage>,
+    patients: Vec<SimulatedPatient>,
+    next_patient_id: i32,
+    events: BinaryHeap<Event>,
+    sequence: u64,
+    now: f64,
+    end: f64,
+    start_hour: u32,
+    rng: StdRng,
+    arrivals: Exp<f64>,
+    triage: WeightedIndex<f64>,
+    stays: Vec<LogNormal<f64>>,
+    area: [(f64, f64); 2],
+    decisions: usize,
+    solver_time: Duration,
+}
+
+/// Time-weighted bed use of one hospital.
+#[derive(Clone, Copy, Debug, Default)]
+struct Usage {
+    occupied: u32,
+    peak: u32,
+    since: f64,
+    bed_seconds: f64,
+}
+
+impl<'a> Shift<'a> {
+    fn new(
+        scenario: &Scenario,
+        solver: &'a dyn Solver,
+        travel_times: &'a TravelTimes,
+        survival: &SurvivalModel,
+        config: &'a SimulationConfig,
+    ) -> Result<Self, SimulationError> {
+        let invalid = |details: &str| Err(SimulationError::InvalidConfig(details.to_string()));
+        if config.duration.is_zero() {
+            return invalid("the shift must last longer than zero");
+        }
+        if !(config.arrivals_per_hour > 0.0 && config.arrivals_per_hour.is_finite()) {
+            return invalid("the arrival rate must be positive");
+        }
+        let triage = WeightedIndex::new(config.triage_mix)
+            .map_err(|err| SimulationError::InvalidConfig(format!("triage mix: {err}")))?;
+        let arrivals = Exp::new(config.arrivals_per_hour / 3_600.0)
+            .map_err(|err| SimulationError::InvalidConfig(format!("arrival rate: {err}")))?;
+        let stays = config
+            .length_of_stay
+            .iter()
+            .map(|stay| LogNormal::new(stay.median.as_secs_f64().ln(), stay.sigma))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| SimulationError::InvalidConfig(format!("length of stay: {err}")))?;
+
+        let nominal: Vec<hospital_speciality::Model> = scenario
+            .hospitals
+            .iter()
+            .flat_map(|hospital| {
+                hospital
+                    .triage_buckets
+                    .iter()
+                    .map(|bucket| hospital_speciality::Model {
+                        id: bucket.id,
+                        hospital_id: hospital.id,
+                        triage_level: bucket.triage_level,
+                        waiting_time_seconds: bucket.waiting_time_seconds,
+                        beds_capacity: bucket.beds_capacity,
+                    })
+            })
+            .collect();
+        let mut network =
+            Problem::from_models(&scenario.hospitals(), &nominal, &scenario.patients())
+                .map_err(SimulationError::Problem)?
+                .with_survival(survival.clone());
+        let pending = std::mem::take(&mut network.patients);
+
+        let latitudes = network.hospitals.iter().map(|hospital| hospital.latitude);
+        let longitudes = network.hospitals.iter().map(|hospital| hospital.longitude);
+        let area = [bounds(latitudes), bounds(longitudes)];
+
+        let mut shift = Shift {
+            solver,
+            travel_times,
+            config,
+            occupied: vec![0; network.buckets.len()],
+            hospital_usage: vec![Usage::default(); network.hospitals.len()],
+            network,
+            patients: Vec::new(),
+            next_patient_id: 1,
+            events: BinaryHeap::new(),
+            sequence: 0,
+            now: 0.0,
+            end: config.duration.as_secs_f64(),
+            start_hour: scenario.departure_hour(),
+            rng: StdRng::seed_from_u64(config.seed),
+            arrivals,
+            triage,
+            stays,
+            area,
+            decisions: 0,
+            solver_time: Duration::ZERO,
+        };
+
+        let occupied_beds: HashMap<i32, i32> = scenario
+            .hospitals
+            .iter()
+            .flat_map(|hospital| &hospital.triage_buckets)
+            .map(|bucket| (bucket.id, bucket.occupied_beds))
+            .collect();
+        for bucket in 0..shift.network.buckets.len() {
+            let id = shift.network.buckets[bucket].id;
+            let capacity = shift.network.buckets[bucket].capacity as i32;
+            let occupied = occupied_beds.get(&id).copied().unwrap_or_default();
+            for _ in 0..occupied.clamp(0, capacity) {
+                shift.take_bed(bucket);
+                let level = shift.network.buckets[bucket].triage_level;
+                let stay = shift.length_of_stay(level);
+                shift.schedule(stay, EventKind::Discharge { bucket });
+            }
+        }
+
+        for patient in pending {
+            shift.next_patient_id = shift.next_patient_id.max(patient.id + 1);
+            shift.admit_arrival(patient);
+        }
+        let first = shift.arrivals.sample(&mut shift.rng);
+        shift.schedule(first, EventKind::Arrival);
+
+        Ok(shift)
+    }
+
+    fn run(&mut self) -> Result<(), SimulationError> {
+        self.assign()?;
+
+        while let Some(event) = self.events.pop() {
+            if event.at > self.end {
+                break;
+            }
+            self.now = event.at;
+
+            match event.kind {
+                EventKind::Arrival => {
+                    let patient = self.random_patient();
+                    self.admit_arrival(patient);
+                    let next = self.arrivals.sample(&mut self.rng);
+                    self.schedule(next, EventKind::Arrival);
+                    self.assign()?;
+                }
+                EventKind::Admission { patient } => {
+                    let arrived_at = self.patients[patient].arrived_at;
+                    let condition = self.patients[patient].demand.condition;
+                    let time_to_treatment_seconds = self.now - arrived_at;
+                    self.patients[patient].state = PatientState::Treated {
+                        time_to_treatment_seconds,
+                        survival_probability: self
+                            .network
+                            .survival
+                            .probability(condition, time_to_treatment_seconds),
+                    };
+                }
+                EventKind::Discharge { bucket } => {
+                    self.release_bed(bucket);
+                    self.assign()?;
+                }
+                EventKind::GiveUp { patient } => {
+                    if self.patients[patient].state == PatientState::Waiting {
+                        self.patients[patient].state = PatientState::TurnedAway;
+                    }
+                }
+            }
+        }
+
+        self.now = self.end;
+        for hospital in 0..self.hospital_usage.len() {
+            self.settle(hospital);
+        }
+        Ok(())
+    }
+
+    /// Solves for the waiting patients against the beds left.
+    fn assign(&mut self) -> Result<(), SimulationError> {
+        let waiting: Vec<usize> = (0..self.patients.len())
+            .filter(|&patient| self.patients[patient].state == PatientState::Waiting)
+            .collect();
+        if waiting.is_empty() {
+            return Ok(());
+        }
+
+        let mut problem = self.network.clone();
+        for (bucket, occupied) in problem.buckets.iter_mut().zip(&self.occupied) {
+            bucket.capacity -= occupied;
+            problem.hospitals[bucket.hospital].occupied_beds += occupied;
+        }
+        problem.patients = waiting
+            .iter()
+            .map(|&patient| self.patients[patient].demand.clone())
+            .collect();
+        let hour = (self.start_hour + (self.now / 3_600.0) as u32) % 24;
+        let problem = self
+            .travel_times
+            .attach(problem, hour)
+            .map_err(SimulationError::Problem)?;
+
+        let started = Instant::now();
+        let solution = self
+            .solver
+            .solve(&problem)
+            .map_err(SimulationError::Solve)?;
+        self.solver_time += started.elapsed();
+        self.decisions += 1;
+
+        let choices = solution.plan.choices(&problem);
+        for (index, &patient) in waiting.iter().enumerate() {
+            match choices[index] {
+                Some(bucket) => {
+                    self.take_bed(bucket);
+                    self.patients[patient].state = PatientState::InTransport;
+                    let delay = problem.time_to_treatment_seconds(index, bucket);
+                    self.schedule(delay, EventKind::Admission { patient });
+                    let level = problem.patients[index].condition;
+                    let stay = delay + self.length_of_stay(level);
+                    self.schedule(stay, EventKind::Discharge { bucket });
+                }
+                None => {
+                    if matches!(
+                        solution.plan.assignments[index].outcome,
+                        AssignmentOutcome::Unassigned(UnassignedReason::NoMatchingTriage)
+                    ) {
+                        self.patients[patient].state = PatientState::TurnedAway;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn admit_arrival(&mut self, demand: PatientDemand) {
+        let patient = self.patients.len();
+        self.patients.push(SimulatedPatient {
+            demand,
+            arrived_at: self.now,
+            state: PatientState::Waiting,
+        });
+        self.schedule(
+            self.config.patience.as_secs_f64(),
+            EventKind::GiveUp { patient },
+        );
+    }
+
+    fn random_patient(&mut self) -> PatientDemand {
+        let [latitude, longitude] = self.area;
+        self.next_patient_id += 1;
+        PatientDemand {
+            id: self.next_patient_id - 1,
+            condition: TriageLevel::ALL[self.triage.sample(&mut self.rng)],
+            latitude: self.rng.random_range(latitude.0..=latitude.1),
+            longitude: self.rng.random_range(longitude.0..=longitude.1),
+        }
+    }
+
+    fn length_of_stay(&mut self, level: TriageLevel) -> f64 {
+        let index = TriageLevel::ALL
+            .iter()
+            .position(|candidate| *candidate == level)
+            .unwrap_or_default();
+        self.stays[index].sample(&mut self.rng)
+    }
+
+    fn schedule(&mut self, after: f64, kind: EventKind) {
+        self.sequence += 1;
+        self.events.push(Event {
+            at: self.now + after,
+            sequence: self.sequence,
+            kind,
+        });
+    }
+
+    fn take_bed(&mut self, bucket: usize) {
+        self.occupied[bucket] += 1;
+        let hospital = self.network.buckets[bucket].hospital;
+        self.settle(hospital);
+        let usage = &mut self.hospital_usage[hospital];
+        usage.occupied += 1;
+        usage.peak = usage.peak.max(usage.occupied);
+    }
+
+    fn release_bed(&mut self, bucket: usize) {
+        self.occupied[bucket] -= 1;
+        let hospital = self.network.buckets[bucket].hospital;
+        self.settle(hospital);
+        self.hospital_usage[hospital].occupied -= 1;
+    }
+
+    /// Accounts the hospital's bed use up to now.
+    fn settle(&mut self, hospital: usize) {
+        let usage = &mut self.hospital_usage[hospital];
+        usage.bed_seconds += usage.occupied as f64 * (self.now - usage.since);
+        usage.since = self.now;
+    }
+
+    fn report(&self) -> SimulationReport {
+        let levels = TriageLevel::ALL
+            .into_iter()
+            .map(|triage_level| {
+                let patients: Vec<&SimulatedPatient> = self
+                    .patients
+                    .iter()
+                    .filter(|patient| patient.demand.condition == triage_level)
+                    .collect();
+                let treated: Vec<(f64, f64)> = patients
+                    .iter()
+                    .filter_map(|patient| match patient.state {
+                        PatientState::Treated {
+                            time_to_treatment_seconds,
+                            survival_probability,
+                        } => Some((time_to_treatment_seconds, survival_probability)),
+                        _ => None,
+                    })
+                    .collect();
+                let mean = |value: fn(&(f64, f64)) -> f64| {
+                    (!treated.is_empty())
+                        .then(|| treated.iter().map(value).sum::<f64>() / treated.len() as f64)
+                };
+
+                LevelMetrics {
+                    triage_level,
+                    arrivals: patients.len(),
+                    treated: treated.len(),
+                    turned_away: patients
+                        .iter()
+                        .filter(|patient| patient.state == PatientState::TurnedAway)
+                        .count(),
+                    mean_time_to_treatment_seconds: mean(|(seconds, _)| *seconds),
+                    mean_survival_probability: mean(|(_, probability)| *probability),
+                }
+            })
+            .collect();
+
+        let hospitals = self
+            .network
+            .hospitals
+            .iter()
+            .zip(&self.hospital_usage)
+            .enumerate()
+            .map(|(index, (hospital, usage))| {
+                let beds: u32 = self
+                    .network
+                    .buckets
+                    .iter()
+                    .filter(|bucket| bucket.hospital == index)
+                    .map(|bucket| bucket.capacity)
+                    .sum();
+                HospitalMetrics {
+                    hospital_id: hospital.id,
+                    name: hospital.name.clone(),
+                    beds,
+                    utilization: match beds {
+                        0 => 0.0,
+                        beds => usage.bed_seconds / (beds as f64 * self.end),
+                    },
+                    peak_occupied: usage.peak,
+                }
+            })
+            .collect();
+
+        SimulationReport {
+            solver: self.solver.name(),
+            decisions: self.decisions,
+            solver_time: self.solver_time,
+            levels,
+            hospitals,
+            unfinished: self
+                .patients
+                .iter()
+                .filter(|patient| {
+                    matches!(
+                        patient.state,
+                        PatientState::Waiting | PatientState::InTransport
+                    )
+                })
+                .count(),
+        }
+    }
+}
+
+/// The padded range of `values`.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (low, high) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), value| {
+        (low.min(value), high.max(value))
+    });
+    if low > high {
+        return (0.0, 0.0);
+    }
+    (low - AREA_MARGIN_DEGREES, high + AREA_MARGIN_DEGREES)
+}
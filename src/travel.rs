@@ -0,0 +1,77 @@
+//! Travel-time source shared by the CLI and the HTTP server.
+//!
+//! [`TravelTimes`] builds the patient × hospital matrix a [`Problem`] is
+//! solved with: the great-circle model by default, or road routing over an
+//! OpenStreetMap extract when the `osm` feature is enabled and a graph is
+//! attached.
+
+#[cfg(feature = "osm")]
+use crate::routing::{RoadGraph, RoutingProfile};
+use crate::{
+    geo::{CongestionProfile, TravelTimeMatrix, TravelTimeModel},
+    optimize::{Problem, ProblemError},
+};
+
+pub struct TravelTimes {
+    pub model: TravelTimeModel,
+    #[cfg(feature = "osm")]
+    roads: Option<(RoadGraph, RoutingProfile)>,
+}
+
+/// The great-circle model with Lisbon weekday congestion.
+impl Default for TravelTimes {
+    fn default() -> Self {
+        TravelTimes::new(TravelTimeModel {
+            congestion: CongestionProfile::lisbon_weekday(),
+            ..Default::default()
+        })
+    }
+}
+
+impl TravelTimes {
+    pub fn new(model: TravelTimeModel) -> Self {
+        TravelTimes {
+            model,
+            #[cfg(feature = "osm")]
+            roads: None,
+        }
+    }
+
+    /// Routes over `graph` instead of great-circle estimates.
+    #[cfg(feature = "osm")]
+    pub fn with_roads(mut self, graph: RoadGraph, profile: RoutingProfile) -> Self {
+        self.roads = Some((graph, profile));
+        self
+    }
+
+    /// `"road"` or `"great-circle"`, for reports and run parameters.
+    pub fn source(&self) -> &'static str {
+        #[cfg(feature = "osm")]
+        if self.roads.is_some() {
+            return "road";
+        }
+
+        "great-circle"
+    }
+
+    /// Travel times for ambulances leaving at local hour `departure_hour`.
+    pub fn matrix(&self, problem: &Problem, departure_hour: u32) -> TravelTimeMatrix {
+        #[cfg(feature = "osm")]
+        if let Some((graph, profile)) = &self.roads {
+            return graph.patients_to_hospitals(
+                problem,
+                profile,
+                &self.model,
+                None,
+                departure_hour,
+            );
+        }
+
+        TravelTimeMatrix::patients_to_hospitals(problem, &self.model, None, departure_hour)
+    }
+
+    pub fn attach(&self, problem: Problem, departure_hour: u32) -> Result<Problem, ProblemError> {
+        let matrix = self.matrix(&problem, departure_hour);
+        problem.with_travel_times(matrix)
+    }
+}
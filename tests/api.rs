@@ -0,0 +1,158 @@
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+    response::IntoResponse,
+};
+use entity::{hospital, optimization_run};
+use hospitopt_rs::{
+    api::{ApiError, AppState, PlanFeed, router},
+    optimize::{DEFAULT_SOLVER, ProblemError, SolveError, SolverRegistry, SurvivalModel},
+    travel::TravelTimes,
+};
+use sea_orm::{DatabaseBackend, DbErr, MockDatabase};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// The API over a mock database that answers queries with `db`'s results.
+fn app(db: MockDatabase) -> Router {
+    router(Arc::new(AppState {
+        db: db.into_connection(),
+        registry: SolverRegistry::default(),
+        default_solver: DEFAULT_SOLVER.to_string(),
+        objectives: None,
+        survival: SurvivalModel::default(),
+        travel_times: TravelTimes::default(),
+        plans: PlanFeed::default(),
+        max_disruption: None,
+    }))
+}
+
+fn empty() -> MockDatabase {
+    MockDatabase::new(DatabaseBackend::Postgres)
+}
+
+async fn send(db: MockDatabase, request: Request<Body>) -> (StatusCode, String) {
+    let response = app(db).oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    (status, body["error"].as_str().unwrap().to_string())
+}
+
+async fn optimize(db: MockDatabase, body: Value) -> (StatusCode, String) {
+    send(
+        db,
+        Request::post("/optimize")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn errors_map_to_status_codes() {
+    let cases = [
+        (
+            ApiError::NotFound {
+                resource: "hospital",
+                id: 3,
+            },
+            StatusCode::NOT_FOUND,
+            "hospital 3 not found",
+        ),
+        (
+            ApiError::BadRequest("bad input".to_string()),
+            StatusCode::BAD_REQUEST,
+            "bad input",
+        ),
+        (
+            ApiError::Database(DbErr::Custom("offline".to_string())),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "database error: Custom Error: offline",
+        ),
+        (
+            ApiError::Problem(ProblemError::NegativeCapacity {
+                table: "hospitals",
+                id: 1,
+                value: -1,
+            }),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "invalid optimization input: ",
+        ),
+        (
+            ApiError::Solve {
+                backend: "exact",
+                source: SolveError::ModelInvalid("no beds".to_string()),
+            },
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "the 'exact' backend failed: ",
+        ),
+        (
+            ApiError::Internal("the solver task failed".to_string()),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "the solver task failed",
+        ),
+    ];
+
+    for (error, status, message) in cases {
+        assert_eq!(error.status(), status);
+        let response = error.into_response();
+        assert_eq!(response.status(), status);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert!(body["error"].as_str().unwrap().starts_with(message));
+    }
+
+    // Extractor rejections, missing rows and failed queries go through the
+    // same mapping.
+    let get = |path: &str| Request::get(path).body(Body::empty()).unwrap();
+    let (status, _) = send(empty(), get("/hospitals/one")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = optimize(empty(), json!({ "persist": "yes" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let missing = empty().append_query_results([Vec::<hospital::Model>::new()]);
+    let (status, message) = send(missing, get("/hospitals/7")).await;
+    assert_eq!(
+        (status, message.as_str()),
+        (StatusCode::NOT_FOUND, "hospital 7 not found")
+    );
+    let failing = empty().append_query_errors([DbErr::Custom("offline".to_string())]);
+    let (status, message) = send(failing, get("/hospitals")).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(message.starts_with("database error"));
+}
+
+#[tokio::test]
+async fn optimize_validates_incremental_requests() {
+    for field in [
+        json!({ "solver": "greedy" }),
+        json!({ "warm_start": "greedy" }),
+        json!({ "objectives": { "objectives": [{ "criterion": "unassigned" }] } }),
+    ] {
+        let mut request = json!({ "previous_run_id": 1 });
+        request
+            .as_object_mut()
+            .unwrap()
+            .extend(field.as_object().unwrap().clone());
+        let (status, message) = optimize(empty(), request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(message.starts_with("previous_run_id selects the incremental backend"));
+    }
+
+    let (status, message) = optimize(empty(), json!({ "max_disruption": 2 })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(message, "max_disruption only applies with previous_run_id");
+
+    // Valid requests get as far as loading the previous run.
+    let no_runs = empty().append_query_results([Vec::<optimization_run::Model>::new()]);
+    let (status, message) = optimize(
+        no_runs,
+        json!({ "previous_run_id": 1, "max_disruption": 2 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(message, "optimization run 1 not found");
+}
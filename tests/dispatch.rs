@@ -0,0 +1,192 @@
+use entity::{
+    TriageLevel::{self, Blue, Green, Red, Yellow},
+    ambulance::AmbulanceType,
+};
+use hospitopt_rs::{
+    dispatch::{
+        AmbulanceUnit, DispatchOutcome, DispatchParams, DispatchPlan, DispatchProblem,
+        UnservedReason, solve,
+    },
+    optimize::{
+        AssignmentOutcome, HospitalSite, PatientDemand, Problem, SurvivalModel, TriageBucket,
+        UnassignedReason,
+    },
+};
+
+/// One hospital with a red, a yellow and a green bucket of the given sizes.
+fn dispatch(
+    red_beds: u32,
+    patients: &[(i32, TriageLevel, f64)],
+    ambulances: &[(i32, f64)],
+) -> DispatchProblem {
+    let bucket = |id: i32, triage_level, capacity| TriageBucket {
+        id,
+        hospital: 0,
+        triage_level,
+        waiting_time_seconds: 300,
+        capacity,
+    };
+
+    DispatchProblem {
+        problem: Problem {
+            hospitals: vec![HospitalSite {
+                id: 1,
+                name: "Santa Maria".to_string(),
+                latitude: 38.7487,
+                longitude: -9.1603,
+                bed_capacity: 20,
+                occupied_beds: 0,
+            }],
+            buckets: vec![
+                bucket(10, Red, red_beds),
+                bucket(11, Yellow, 5),
+                bucket(12, Green, 5),
+            ],
+            patients: patients
+                .iter()
+                .map(|&(id, condition, latitude)| PatientDemand {
+                    id,
+                    condition,
+                    latitude,
+                    longitude: -9.15,
+                })
+                .collect(),
+            travel_times: None,
+            survival: SurvivalModel::default(),
+        },
+        ambulances: ambulances
+            .iter()
+            .map(|&(id, latitude)| AmbulanceUnit {
+                id,
+                call_sign: format!("INEM {id:02}"),
+                ambulance_type: AmbulanceType::Als,
+                latitude,
+                longitude: -9.15,
+            })
+            .collect(),
+    }
+}
+
+fn outcome(plan: &DispatchPlan, patient_id: i32) -> &DispatchOutcome {
+    &plan
+        .assignments
+        .iter()
+        .find(|assignment| assignment.patient_id == patient_id)
+        .unwrap()
+        .outcome
+}
+
+fn ambulance_of(plan: &DispatchPlan, patient_id: i32) -> Option<i32> {
+    match outcome(plan, patient_id) {
+        DispatchOutcome::Dispatched(dispatch) => Some(dispatch.ambulance_id),
+        DispatchOutcome::Unserved(_) => None,
+    }
+}
+
+#[test]
+fn each_ambulance_serves_one_patient() {
+    let patients = [(1, Yellow, 38.73), (2, Yellow, 38.60), (3, Yellow, 38.75)];
+    let plan = solve(
+        &dispatch(2, &patients, &[(7, 38.73), (8, 38.75)]),
+        &DispatchParams::default(),
+    );
+
+    assert_eq!(plan.dispatched_count(), 2);
+    let mut used: Vec<i32> = (1..=3).filter_map(|id| ambulance_of(&plan, id)).collect();
+    used.sort();
+    assert_eq!(used, [7, 8]);
+    // Each ambulance takes the patient it is parked next to; the one far
+    // south of both waits.
+    assert_eq!(ambulance_of(&plan, 1), Some(7));
+    assert_eq!(ambulance_of(&plan, 3), Some(8));
+    assert_eq!(
+        outcome(&plan, 2),
+        &DispatchOutcome::Unserved(UnservedReason::NoAmbulance)
+    );
+}
+
+#[test]
+fn the_only_ambulance_goes_to_the_red_patient() {
+    // The green patient is next to the ambulance, the red one across town.
+    let patients = [(1, Green, 38.70), (2, Red, 38.80)];
+    let plan = solve(
+        &dispatch(2, &patients, &[(7, 38.70)]),
+        &DispatchParams::default(),
+    );
+
+    assert_eq!(ambulance_of(&plan, 2), Some(7));
+    assert_eq!(
+        outcome(&plan, 1),
+        &DispatchOutcome::Unserved(UnservedReason::NoAmbulance)
+    );
+    assert!(plan.weighted_time_to_treatment_seconds > 0);
+}
+
+#[test]
+fn bucket_capacity_limits_dispatches() {
+    let patients = [(1, Red, 38.73), (2, Red, 38.74)];
+    let plan = solve(
+        &dispatch(1, &patients, &[(7, 38.73), (8, 38.74)]),
+        &DispatchParams::default(),
+    );
+
+    assert_eq!(plan.dispatched_count(), 1);
+    let unserved: Vec<&DispatchOutcome> = [1, 2]
+        .iter()
+        .map(|&id| outcome(&plan, id))
+        .filter(|outcome| matches!(outcome, DispatchOutcome::Unserved(_)))
+        .collect();
+    assert_eq!(
+        unserved,
+        [&DispatchOutcome::Unserved(UnservedReason::NoBed)]
+    );
+}
+
+#[test]
+fn unserved_reasons_map_to_the_bed_plan() {
+    // No blue bucket, a full red bucket, and one ambulance for two yellows.
+    let patients = [
+        (1, Blue, 38.73),
+        (2, Red, 38.73),
+        (3, Yellow, 38.73),
+        (4, Yellow, 38.60),
+    ];
+    let plan = solve(
+        &dispatch(0, &patients, &[(7, 38.73)]),
+        &DispatchParams::default(),
+    );
+
+    let reasons: Vec<&DispatchOutcome> = (1..=4).map(|id| outcome(&plan, id)).collect();
+    assert_eq!(
+        reasons[..2],
+        [
+            &DispatchOutcome::Unserved(UnservedReason::NoMatchingTriage),
+            &DispatchOutcome::Unserved(UnservedReason::NoBed),
+        ]
+    );
+    assert_eq!(ambulance_of(&plan, 3), Some(7));
+    assert_eq!(
+        reasons[3],
+        &DispatchOutcome::Unserved(UnservedReason::NoAmbulance)
+    );
+
+    let beds: Vec<AssignmentOutcome> = plan
+        .to_assignment_plan()
+        .assignments
+        .into_iter()
+        .map(|assignment| assignment.outcome)
+        .collect();
+    assert_eq!(
+        beds[0],
+        AssignmentOutcome::Unassigned(UnassignedReason::NoMatchingTriage)
+    );
+    assert_eq!(
+        beds[1],
+        AssignmentOutcome::Unassigned(UnassignedReason::CapacityExhausted)
+    );
+    assert!(matches!(beds[2], AssignmentOutcome::Assigned(_)));
+    assert_eq!(
+        beds[3],
+        AssignmentOutcome::Unassigned(UnassignedReason::NotSelected)
+    );
+}
@@ -0,0 +1,121 @@
+use entity::{TriageLevel, ambulance::AmbulanceType};
+use hospitopt_rs::{
+    geo::{CongestionProfile, Coordinate, TravelTimeMatrix, TravelTimeModel, haversine_km},
+    optimize::{HospitalSite, PatientDemand, Problem, SurvivalModel},
+};
+
+const LISBON: Coordinate = Coordinate {
+    latitude: 38.7223,
+    longitude: -9.1393,
+};
+const PORTO: Coordinate = Coordinate {
+    latitude: 41.1579,
+    longitude: -8.6291,
+};
+
+fn problem(patients: usize, hospitals: usize) -> Problem {
+    Problem {
+        hospitals: (0..hospitals)
+            .map(|i| HospitalSite {
+                id: i as i32 + 1,
+                name: format!("Hospital {i}"),
+                latitude: 38.70 + 0.05 * i as f64,
+                longitude: -9.14,
+                bed_capacity: 10,
+                occupied_beds: 0,
+            })
+            .collect(),
+        buckets: Vec::new(),
+        patients: (0..patients)
+            .map(|i| PatientDemand {
+                id: i as i32 + 1,
+                condition: TriageLevel::Yellow,
+                latitude: 38.72,
+                longitude: -9.10 - 0.02 * i as f64,
+            })
+            .collect(),
+        travel_times: None,
+        survival: SurvivalModel::default(),
+    }
+}
+
+#[test]
+fn haversine_matches_known_distances() {
+    let km = haversine_km(LISBON, PORTO);
+
+    assert!((km - 274.0).abs() < 1.0, "{km}");
+    assert_eq!(haversine_km(PORTO, LISBON), km);
+    assert_eq!(haversine_km(LISBON, LISBON), 0.0);
+}
+
+#[test]
+fn congestion_scales_travel_time_by_departure_hour() {
+    let model = TravelTimeModel {
+        congestion: CongestionProfile::lisbon_weekday(),
+        ..Default::default()
+    };
+    let free_flow = TravelTimeModel::default();
+
+    for hour in 0..24 {
+        let factor = CongestionProfile::lisbon_weekday().factor_at(hour);
+        let expected = free_flow.travel_seconds(LISBON, PORTO, None, hour) * factor;
+        let seconds = model.travel_seconds(LISBON, PORTO, None, hour);
+        assert!((seconds - expected).abs() < 1e-6, "hour {hour}");
+    }
+    assert!(
+        model.travel_seconds(LISBON, PORTO, None, 8) > model.travel_seconds(LISBON, PORTO, None, 3)
+    );
+    // Hours wrap around the day.
+    assert_eq!(
+        model.travel_seconds(LISBON, PORTO, None, 32),
+        model.travel_seconds(LISBON, PORTO, None, 8)
+    );
+}
+
+#[test]
+fn travel_time_uses_the_detour_and_vehicle_speed() {
+    let model = TravelTimeModel::default();
+    let road_km = haversine_km(LISBON, PORTO) * model.detour_factor;
+    let hours = |vehicle| model.travel_seconds(LISBON, PORTO, vehicle, 12) / 3_600.0;
+
+    assert!((hours(None) - road_km / 40.0).abs() < 1e-9);
+    assert!((hours(Some(AmbulanceType::Bls)) - road_km / 45.0).abs() < 1e-9);
+    assert!((hours(Some(AmbulanceType::Als)) - road_km / 50.0).abs() < 1e-9);
+
+    let without_als = TravelTimeModel {
+        vehicle_speeds_kmh: [(AmbulanceType::Bls, 45.0)].into(),
+        ..Default::default()
+    };
+    assert_eq!(without_als.speed_kmh(Some(AmbulanceType::Als)), 40.0);
+}
+
+#[test]
+fn matrix_is_patients_by_hospitals() {
+    let problem = problem(3, 2);
+    let model = TravelTimeModel::default();
+    let matrix =
+        TravelTimeMatrix::patients_to_hospitals(&problem, &model, Some(AmbulanceType::Als), 9);
+
+    assert_eq!((matrix.rows(), matrix.columns()), (3, 2));
+    for (row, patient) in problem.patients.iter().enumerate() {
+        for (column, hospital) in problem.hospitals.iter().enumerate() {
+            let expected = model.travel_seconds(
+                Coordinate::new(patient.latitude, patient.longitude),
+                Coordinate::new(hospital.latitude, hospital.longitude),
+                Some(AmbulanceType::Als),
+                9,
+            );
+            assert_eq!(matrix.get(row, column), expected);
+        }
+    }
+
+    let bls =
+        TravelTimeMatrix::patients_to_hospitals(&problem, &model, Some(AmbulanceType::Bls), 9);
+    assert!((bls.get(2, 1) / matrix.get(2, 1) - 50.0 / 45.0).abs() < 1e-9);
+}
+
+#[test]
+#[should_panic]
+fn matrix_rejects_cells_out_of_range() {
+    TravelTimeMatrix::from_fn(2, 3, |_, _| 0.0).get(2, 0);
+}
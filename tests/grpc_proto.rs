@@ -0,0 +1,257 @@
+//! Checks the hand-declared messages in `src/grpc/proto.rs` and the service
+//! in `build.rs` against `proto/hospitopt/v1/dispatch.proto`.
+
+use hospitopt_rs::grpc::proto;
+use prost::Message;
+use std::collections::BTreeMap;
+
+const PROTO: &str = include_str!("../proto/hospitopt/v1/dispatch.proto");
+const BUILD: &str = include_str!("../build.rs");
+
+struct Field {
+    label: Option<String>,
+    kind: String,
+    tag: u32,
+}
+
+/// What the proto file declares: the fields of each message, the constants
+/// of each enum and each RPC as `Request -> [stream] Response`.
+struct Schema {
+    messages: BTreeMap<String, Vec<Field>>,
+    enums: BTreeMap<String, Vec<(String, i32)>>,
+    rpcs: Vec<String>,
+}
+
+fn schema() -> Schema {
+    let mut schema = Schema {
+        messages: BTreeMap::new(),
+        enums: BTreeMap::new(),
+        rpcs: Vec::new(),
+    };
+    let mut block: Option<(&str, String)> = None;
+
+    for line in PROTO.lines().map(str::trim) {
+        if line.starts_with("//") || line.is_empty() {
+            continue;
+        }
+        let words: Vec<&str> = line
+            .split(|c: char| c.is_whitespace() || "=;{}()".contains(c))
+            .filter(|word| !word.is_empty())
+            .collect();
+        match (words.as_slice(), &block) {
+            (["message", name, ..], None) => {
+                schema.messages.insert(name.to_string(), Vec::new());
+                if !line.ends_with("{}") {
+                    block = Some(("message", name.to_string()));
+                }
+            }
+            (["enum", name], None) => {
+                schema.enums.insert(name.to_string(), Vec::new());
+                block = Some(("enum", name.to_string()));
+            }
+            (["service", _], None) => block = Some(("service", String::new())),
+            (["rpc", _, request, "returns", rest @ ..], Some(("service", _))) => {
+                schema.rpcs.push(format!("{request} -> {}", rest.join(" ")));
+            }
+            ([], Some(_)) if line == "}" => block = None,
+            ([constant, value], Some(("enum", name))) => {
+                let constants = schema.enums.get_mut(name).unwrap();
+                constants.push((constant.to_string(), value.parse().unwrap()));
+            }
+            (field, Some(("message", name))) => {
+                let (label, field) = match field {
+                    [label @ ("optional" | "repeated"), rest @ ..] => {
+                        (Some(label.to_string()), rest)
+                    }
+                    _ => (None, field),
+                };
+                let [kind, _, tag] = field else {
+                    panic!("unexpected line in message {name}: {line}");
+                };
+                schema.messages.get_mut(name).unwrap().push(Field {
+                    label,
+                    kind: kind.to_string(),
+                    tag: tag.parse().unwrap(),
+                });
+            }
+            _ => {}
+        }
+    }
+    schema
+}
+
+fn varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn key(out: &mut Vec<u8>, tag: u32, wire_type: u64) {
+    varint(out, (u64::from(tag) << 3) | wire_type);
+}
+
+/// A message with every field set to a value that is not its default,
+/// encoded by tag the way the proto file declares them.
+fn encode(schema: &Schema, message: &str) -> Vec<u8> {
+    if message == "google.protobuf.Timestamp" {
+        let mut out = Vec::new();
+        key(&mut out, 1, 0);
+        varint(&mut out, 1_760_000_000);
+        key(&mut out, 2, 0);
+        varint(&mut out, 500);
+        return out;
+    }
+
+    let mut fields: Vec<&Field> = schema.messages[message].iter().collect();
+    fields.sort_by_key(|field| field.tag);
+    let mut out = Vec::new();
+    for field in fields {
+        let value = u64::from(field.tag) + 1;
+        let repeated = field.label.as_deref() == Some("repeated");
+        match field.kind.as_str() {
+            "int32" | "int64" | "uint32" | "uint64" | "bool" => {
+                let value = if field.kind == "bool" { 1 } else { value };
+                if repeated {
+                    // Scalars are packed in proto3.
+                    let mut packed = Vec::new();
+                    varint(&mut packed, value);
+                    varint(&mut packed, value + 1);
+                    key(&mut out, field.tag, 2);
+                    varint(&mut out, packed.len() as u64);
+                    out.extend(packed);
+                } else {
+                    key(&mut out, field.tag, 0);
+                    varint(&mut out, value);
+                }
+            }
+            "double" => {
+                key(&mut out, field.tag, 1);
+                out.extend((value as f64 + 0.5).to_le_bytes());
+            }
+            "string" => {
+                let text = format!("field {}", field.tag);
+                key(&mut out, field.tag, 2);
+                varint(&mut out, text.len() as u64);
+                out.extend(text.as_bytes());
+            }
+            kind if schema.enums.contains_key(kind) => {
+                key(&mut out, field.tag, 0);
+                varint(&mut out, schema.enums[kind].last().unwrap().1 as u64);
+            }
+            kind => {
+                let nested = encode(schema, kind);
+                key(&mut out, field.tag, 2);
+                varint(&mut out, nested.len() as u64);
+                out.extend(nested);
+            }
+        }
+    }
+    out
+}
+
+/// Decodes `bytes` as the hand-declared `message` and encodes it again.
+fn reencode(message: &str, bytes: &[u8]) -> Vec<u8> {
+    macro_rules! messages {
+        ($($name:ident),* $(,)?) => {
+            match message {
+                $(stringify!($name) => proto::$name::decode(bytes)
+                    .unwrap_or_else(|err| panic!("{message}: {err}"))
+                    .encode_to_vec(),)*
+                other => panic!("{other} is not declared in src/grpc/proto.rs"),
+            }
+        };
+    }
+    messages!(
+        Hospital,
+        TriageBucket,
+        Patient,
+        ListHospitalsRequest,
+        ListHospitalsResponse,
+        ListTriageBucketsRequest,
+        ListTriageBucketsResponse,
+        GetPatientRequest,
+        ReportIncidentRequest,
+        OptimizeRequest,
+        OptimizeResponse,
+        OptimizeStreamRequest,
+        PlanUpdate,
+        Diagnostics,
+        ObjectiveValue,
+        Assignment,
+        AssignmentChange,
+    )
+}
+
+#[test]
+fn messages_match_the_proto_tags() {
+    let schema = schema();
+    assert_eq!(schema.messages.len(), 17);
+
+    for message in schema.messages.keys() {
+        let bytes = encode(&schema, message);
+        // Fields under another tag would be dropped as unknown, and a
+        // different wire type fails to decode.
+        assert_eq!(reencode(message, &bytes), bytes, "{message} drifted");
+    }
+}
+
+#[test]
+fn enums_match_the_proto_values() {
+    let schema = schema();
+    let variant = |name: &str, value: i32| match name {
+        "TriageLevel" => proto::TriageLevel::try_from(value).map(|level| format!("{level:?}")),
+        "PatientStatus" => {
+            proto::PatientStatus::try_from(value).map(|status| format!("{status:?}"))
+        }
+        other => panic!("{other} is not declared in src/grpc/proto.rs"),
+    };
+    let screaming = |camel: &str| {
+        camel
+            .char_indices()
+            .flat_map(|(i, c)| {
+                let separator = (i > 0 && c.is_ascii_uppercase()).then_some('_');
+                separator.into_iter().chain([c.to_ascii_uppercase()])
+            })
+            .collect::<String>()
+    };
+
+    assert_eq!(schema.enums.len(), 2);
+    for (name, constants) in &schema.enums {
+        for (constant, value) in constants {
+            let variant = variant(name, *value).unwrap();
+            assert_eq!(
+                format!("{}_{}", screaming(name), screaming(&variant)),
+                *constant
+            );
+        }
+        let next = constants.iter().map(|(_, value)| value).max().unwrap() + 1;
+        assert!(variant(name, next).is_err(), "{name} has more variants");
+    }
+}
+
+#[test]
+fn service_methods_match_the_proto() {
+    let declared: Vec<String> = BUILD
+        .split("method(\"")
+        .skip(1)
+        .map(|call| {
+            let (route, rest) = call.split_once("\", \"").unwrap();
+            let (output, rest) = rest.split_once('"').unwrap();
+            let streaming = rest
+                .split(".build()")
+                .next()
+                .unwrap()
+                .contains(".server_streaming()");
+            let output = if streaming {
+                format!("stream {output}")
+            } else {
+                output.to_string()
+            };
+            format!("{route}Request -> {output}")
+        })
+        .collect();
+
+    assert_eq!(declared, schema().rpcs);
+}
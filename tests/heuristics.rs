@@ -0,0 +1,229 @@
+use entity::TriageLevel::{self, Red, Yellow};
+use hospitopt_rs::{
+    geo::TravelTimeMatrix,
+    optimize::{
+        AssignmentOutcome, AssignmentPlan, BucketRef, GreedySolver, HospitalSite,
+        PatientAssignment, PatientDemand, Problem, RegretSolver, Solver, SurvivalModel,
+        TriageBucket, UnassignedReason, exact::ExactSolver,
+    },
+};
+
+fn hospital(id: i32) -> HospitalSite {
+    HospitalSite {
+        id,
+        name: format!("Hospital {id}"),
+        latitude: 38.70,
+        longitude: -9.14,
+        bed_capacity: 10,
+        occupied_beds: 0,
+    }
+}
+
+fn bucket(id: i32, hospital: usize, triage_level: TriageLevel, capacity: u32) -> TriageBucket {
+    TriageBucket {
+        id,
+        hospital,
+        triage_level,
+        waiting_time_seconds: 0,
+        capacity,
+    }
+}
+
+fn patient(id: i32, condition: TriageLevel) -> PatientDemand {
+    PatientDemand {
+        id,
+        condition,
+        latitude: 38.70,
+        longitude: -9.14,
+    }
+}
+
+/// Two hospitals with one bed per bucket and the given minutes from each
+/// patient to each hospital.
+fn problem(patients: Vec<PatientDemand>, minutes: &[[f64; 2]]) -> Problem {
+    let matrix = TravelTimeMatrix::from_fn(patients.len(), 2, |patient, hospital| {
+        minutes[patient][hospital] * 60.0
+    });
+    Problem {
+        hospitals: vec![hospital(1), hospital(2)],
+        buckets: vec![
+            bucket(10, 0, Red, 1),
+            bucket(20, 1, Red, 1),
+            bucket(11, 0, Yellow, 1),
+            bucket(21, 1, Yellow, 1),
+        ],
+        patients,
+        travel_times: None,
+        survival: SurvivalModel::default(),
+    }
+    .with_travel_times(matrix)
+    .unwrap()
+}
+
+/// Bucket id of each patient, by patient id.
+fn buckets(plan: &AssignmentPlan) -> Vec<(i32, Option<i32>)> {
+    let mut buckets: Vec<_> = plan
+        .assignments
+        .iter()
+        .map(|assignment| {
+            let bucket = match &assignment.outcome {
+                AssignmentOutcome::Assigned(bucket) => Some(bucket.speciality_id),
+                AssignmentOutcome::Unassigned(_) => None,
+            };
+            (assignment.patient_id, bucket)
+        })
+        .collect();
+    buckets.sort();
+    buckets
+}
+
+/// Warm start sending each patient, in order, to the bucket with that id.
+/// Unknown ids keep the patient's triage level and no hospital.
+fn plan(problem: &Problem, bucket_ids: &[Option<i32>]) -> AssignmentPlan {
+    AssignmentPlan {
+        assignments: problem
+            .patients
+            .iter()
+            .zip(bucket_ids)
+            .map(|(patient, bucket_id)| PatientAssignment {
+                patient_id: patient.id,
+                outcome: match bucket_id {
+                    Some(id) => {
+                        let bucket = problem.buckets.iter().find(|bucket| bucket.id == *id);
+                        AssignmentOutcome::Assigned(BucketRef {
+                            hospital_id: bucket.map_or(0, |b| problem.hospitals[b.hospital].id),
+                            speciality_id: *id,
+                            triage_level: bucket.map_or(patient.condition, |b| b.triage_level),
+                        })
+                    }
+                    None => AssignmentOutcome::Unassigned(UnassignedReason::NotSelected),
+                },
+            })
+            .collect(),
+    }
+}
+
+/// The same-level bucket in the other hospital.
+fn other_hospital(bucket: i32) -> i32 {
+    match bucket {
+        10 => 20,
+        20 => 10,
+        11 => 21,
+        21 => 11,
+        other => panic!("no bucket {other}"),
+    }
+}
+
+#[test]
+fn greedy_serves_urgent_patients_first_whatever_the_input_order() {
+    // Every patient is closest to hospital 1, which has one bed per level.
+    let listed = [
+        patient(4, Yellow),
+        patient(3, Red),
+        patient(2, Yellow),
+        patient(1, Red),
+    ];
+    let minutes = [[5.0, 30.0]; 4];
+    let expected = [(1, Some(10)), (2, Some(11)), (3, Some(20)), (4, Some(21))];
+
+    let forward = problem(listed.to_vec(), &minutes);
+    let reversed = problem(listed.into_iter().rev().collect(), &minutes);
+    for problem in [forward, reversed] {
+        let solution = GreedySolver.solve(&problem).unwrap();
+        assert_eq!(buckets(&solution.plan), expected);
+    }
+}
+
+#[test]
+fn regret_beats_greedy_when_the_first_patient_has_a_close_second_choice() {
+    // Patient 1 loses a minute by going to hospital 2; patient 2 loses most
+    // of their survival chance. Greedy serves patient 1 first and takes the
+    // shared bed, regret gives it to patient 2.
+    let problem = problem(
+        vec![patient(1, Red), patient(2, Red)],
+        &[[5.0, 6.0], [5.0, 60.0]],
+    );
+
+    let greedy = GreedySolver.solve(&problem).unwrap();
+    let regret = RegretSolver.solve(&problem).unwrap();
+
+    assert_eq!(buckets(&greedy.plan), [(1, Some(10)), (2, Some(20))]);
+    assert_eq!(buckets(&regret.plan), [(1, Some(20)), (2, Some(10))]);
+    assert!(regret.diagnostics.objective_value > greedy.diagnostics.objective_value + 0.5);
+}
+
+#[test]
+fn exact_keeps_the_warm_start_among_equally_good_plans() {
+    // Both hospitals are equally far, so any one-bed-each plan is optimal.
+    let problem = problem(
+        vec![patient(1, Yellow), patient(2, Yellow)],
+        &[[10.0, 10.0], [10.0, 10.0]],
+    );
+    let cold = ExactSolver.solve(&problem).unwrap();
+
+    for warm in [[Some(11), Some(21)], [Some(21), Some(11)]] {
+        let warm_start = plan(&problem, &warm);
+        let solution = ExactSolver.solve_from(&problem, &warm_start).unwrap();
+        assert_eq!(solution.plan, warm_start);
+        assert_eq!(
+            solution.diagnostics.objective_value,
+            cold.diagnostics.objective_value
+        );
+    }
+}
+
+#[test]
+fn exact_does_not_keep_a_worse_warm_start() {
+    let problem = problem(
+        vec![patient(1, Red), patient(2, Red)],
+        &[[5.0, 6.0], [5.0, 60.0]],
+    );
+    let greedy = GreedySolver.solve(&problem).unwrap();
+
+    let solution = ExactSolver.solve_from(&problem, &greedy.plan).unwrap();
+
+    assert_eq!(buckets(&solution.plan), [(1, Some(20)), (2, Some(10))]);
+}
+
+#[test]
+fn exact_ignores_what_does_not_fit_in_a_warm_start() {
+    // Ties everywhere, so any preference that was honoured would show.
+    let problem = problem(
+        vec![patient(1, Yellow), patient(2, Yellow), patient(3, Red)],
+        &[[10.0, 10.0]; 3],
+    );
+    let cold = ExactSolver.solve(&problem).unwrap();
+    let elsewhere: Vec<Option<i32>> = buckets(&cold.plan)
+        .into_iter()
+        .map(|(_, bucket)| bucket.map(other_hospital))
+        .collect();
+
+    let wrong_level = plan(&problem, &[Some(10), Some(20), Some(11)]);
+    let unknown_bucket = plan(&problem, &[Some(98), Some(99), Some(97)]);
+    let mut other_patients = plan(&problem, &elsewhere);
+    for assignment in &mut other_patients.assignments {
+        assignment.patient_id += 100;
+    }
+
+    for warm_start in [wrong_level, unknown_bucket, other_patients] {
+        let solution = ExactSolver.solve_from(&problem, &warm_start).unwrap();
+        assert_eq!(solution.plan, cold.plan, "{warm_start:?}");
+    }
+
+    // Both Yellow patients want the same bed: one gets it, the other still
+    // gets the remaining one.
+    let over_capacity = plan(&problem, &[Some(21), Some(21), None]);
+    let solution = ExactSolver.solve_from(&problem, &over_capacity).unwrap();
+    let assigned = buckets(&solution.plan);
+    assert!(assigned.contains(&(1, Some(21))) || assigned.contains(&(2, Some(21))));
+    assert_eq!(solution.plan.assigned_count(), 3);
+    assert_eq!(
+        solution.diagnostics.objective_value,
+        cold.diagnostics.objective_value
+    );
+
+    // A plan for fewer patients only speaks for the ones it covers.
+    let truncated = plan(&problem, &elsewhere[..1]);
+    let solution = ExactSolver.solve_from(&problem, &truncated).unwrap();
+    assert_eq!(buckets(&solution.plan)[0], (1, elsewhere[0]));
+}
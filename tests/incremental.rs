@@ -0,0 +1,98 @@
+use entity::TriageLevel::Red;
+use hospitopt_rs::optimize::{
+    AssignmentOutcome, AssignmentPlan, BucketRef, HospitalSite, PatientAssignment, PatientDemand,
+    Problem, SolveStatus, SurvivalModel, TriageBucket, reoptimize,
+};
+
+/// Two red patients were sent to a slow bucket while the fast one was full.
+/// The fast one has since emptied and a third red patient arrives.
+fn arrival() -> (Problem, AssignmentPlan) {
+    let hospital = |id: i32| HospitalSite {
+        id,
+        name: format!("Hospital {id}"),
+        latitude: 38.72,
+        longitude: -9.14,
+        bed_capacity: 2,
+        occupied_beds: 0,
+    };
+    let bucket = |id: i32, hospital: usize, waiting_time_seconds: i64| TriageBucket {
+        id,
+        hospital,
+        triage_level: Red,
+        waiting_time_seconds,
+        capacity: 2,
+    };
+    let patient = |id: i32| PatientDemand {
+        id,
+        condition: Red,
+        latitude: 38.73,
+        longitude: -9.15,
+    };
+
+    let problem = Problem {
+        hospitals: vec![hospital(1), hospital(2)],
+        buckets: vec![bucket(10, 0, 60), bucket(20, 1, 3_600)],
+        patients: vec![patient(1), patient(2), patient(3)],
+        travel_times: None,
+        survival: SurvivalModel::default(),
+    };
+    let slow = |patient_id| PatientAssignment {
+        patient_id,
+        outcome: AssignmentOutcome::Assigned(BucketRef {
+            hospital_id: 2,
+            speciality_id: 20,
+            triage_level: Red,
+        }),
+    };
+    let previous = AssignmentPlan {
+        assignments: vec![slow(1), slow(2)],
+    };
+
+    (problem, previous)
+}
+
+fn bucket_of(plan: &AssignmentPlan, patient_id: i32) -> Option<i32> {
+    plan.assignments
+        .iter()
+        .find(|assignment| assignment.patient_id == patient_id)
+        .and_then(|assignment| match &assignment.outcome {
+            AssignmentOutcome::Assigned(bucket) => Some(bucket.speciality_id),
+            AssignmentOutcome::Unassigned(_) => None,
+        })
+}
+
+#[test]
+fn zero_disruption_only_inserts_the_new_patient() {
+    let (problem, previous) = arrival();
+    let result = reoptimize(&problem, &previous, 0);
+
+    assert!(result.disrupted.is_empty());
+    assert_eq!(bucket_of(&result.solution.plan, 1), Some(20));
+    assert_eq!(bucket_of(&result.solution.plan, 2), Some(20));
+    assert_eq!(bucket_of(&result.solution.plan, 3), Some(10));
+}
+
+#[test]
+fn one_disruption_moves_a_patient_to_the_freed_bucket() {
+    let (problem, previous) = arrival();
+    let kept = reoptimize(&problem, &previous, 0);
+    let result = reoptimize(&problem, &previous, 1);
+
+    assert_eq!(result.disrupted.len(), 1);
+    assert_eq!(result.solution.diagnostics.status, SolveStatus::Optimal);
+    assert!(
+        result.solution.diagnostics.objective_value > kept.solution.diagnostics.objective_value
+    );
+    let fast = [1, 2, 3]
+        .into_iter()
+        .filter(|id| bucket_of(&result.solution.plan, *id) == Some(10))
+        .count();
+    assert_eq!(fast, 2);
+
+    let unlimited = reoptimize(&problem, &previous, usize::MAX);
+    assert_eq!(
+        unlimited.disrupted.len(),
+        1,
+        "no change beyond the useful one"
+    );
+}
@@ -0,0 +1,178 @@
+use chrono::Utc;
+use entity::{
+    TriageLevel::{self, Red, Yellow},
+    bed_occupancy, hospital, hospital_speciality, patient,
+    patient::PatientStatus,
+};
+use hospitopt_rs::{
+    api::{
+        AppState, PlanFeed,
+        dto::Assignment,
+        live::{changes, publish_next_plan},
+    },
+    optimize::{DEFAULT_SOLVER, SolverRegistry, SurvivalModel},
+    travel::TravelTimes,
+};
+use sea_orm::{DatabaseBackend, MockDatabase};
+use std::sync::Arc;
+
+fn assignment(patient_id: i32, triage_bucket_id: Option<i32>) -> Assignment {
+    Assignment {
+        patient_id,
+        triage_level: Red,
+        assigned: triage_bucket_id.is_some(),
+        hospital_id: triage_bucket_id.map(|_| 1),
+        triage_bucket_id,
+        time_to_treatment_seconds: triage_bucket_id.map(|_| 600.0),
+        survival_probability: if triage_bucket_id.is_some() { 0.8 } else { 0.0 },
+        unassigned_reason: None,
+    }
+}
+
+/// The bucket of a patient in one plan, `None` when the patient is not in it.
+type Bucket = Option<Option<i32>>;
+
+/// `(patient_id, previous, current)` per change.
+fn diff(previous: &[Assignment], current: &[Assignment]) -> Vec<(i32, Bucket, Bucket)> {
+    changes(previous, current)
+        .into_iter()
+        .map(|change| {
+            (
+                change.patient_id,
+                change
+                    .previous
+                    .map(|assignment| assignment.triage_bucket_id),
+                change.current.map(|assignment| assignment.triage_bucket_id),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn changes_list_added_removed_and_moved_patients() {
+    let previous = [
+        assignment(1, Some(10)),
+        assignment(2, Some(10)),
+        assignment(3, None),
+        assignment(5, Some(11)),
+    ];
+    let current = [
+        assignment(6, Some(11)),
+        assignment(5, Some(11)),
+        assignment(3, Some(10)),
+        assignment(2, Some(11)),
+        assignment(4, None),
+    ];
+
+    assert_eq!(
+        diff(&previous, &current),
+        [
+            (1, Some(Some(10)), None),
+            (2, Some(Some(10)), Some(Some(11))),
+            (3, Some(None), Some(Some(10))),
+            (4, None, Some(None)),
+            (6, None, Some(Some(11))),
+        ]
+    );
+}
+
+#[test]
+fn unchanged_plans_have_no_changes() {
+    let plan = [assignment(1, Some(10)), assignment(2, None)];
+
+    assert!(changes(&plan, &plan).is_empty());
+    assert!(changes(&[], &[]).is_empty());
+    assert_eq!(diff(&[], &plan).len(), 2);
+}
+
+fn state(db: MockDatabase) -> Arc<AppState> {
+    Arc::new(AppState {
+        db: db.into_connection(),
+        registry: SolverRegistry::default(),
+        default_solver: DEFAULT_SOLVER.to_string(),
+        objectives: None,
+        survival: SurvivalModel::default(),
+        travel_times: TravelTimes::default(),
+        plans: PlanFeed::default(),
+        max_disruption: None,
+    })
+}
+
+fn pending(id: i32, condition: TriageLevel) -> patient::Model {
+    patient::Model {
+        id,
+        condition,
+        latitude: 38.73,
+        longitude: -9.15,
+        status: PatientStatus::Reported,
+        reported_at: Utc::now().fixed_offset(),
+        admitted_at: None,
+        assigned_hospital_id: None,
+    }
+}
+
+/// Queues the query results of one snapshot: a hospital with a Red and a
+/// Yellow bucket, no occupancy records and `patients` pending.
+fn snapshot(db: MockDatabase, patients: Vec<patient::Model>) -> MockDatabase {
+    let hospitals = vec![hospital::Model {
+        id: 1,
+        location_name: "Hospital de Santa Maria".to_string(),
+        latitude: 38.75,
+        longitude: -9.16,
+        bed_capacity: 10,
+    }];
+    let specialities = vec![
+        hospital_speciality::Model {
+            id: 10,
+            hospital_id: 1,
+            triage_level: Red,
+            waiting_time_seconds: 300,
+            beds_capacity: 2,
+        },
+        hospital_speciality::Model {
+            id: 11,
+            hospital_id: 1,
+            triage_level: Yellow,
+            waiting_time_seconds: 900,
+            beds_capacity: 2,
+        },
+    ];
+
+    db.append_query_results([hospitals])
+        .append_query_results([specialities.clone()])
+        .append_query_results([patients])
+        .append_query_results([specialities])
+        .append_query_results([Vec::<bed_occupancy::Model>::new()])
+        .append_query_results([Vec::<patient::Model>::new()])
+}
+
+#[tokio::test]
+async fn a_plan_is_published_only_when_the_input_hash_changes() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres);
+    let db = snapshot(db, vec![pending(1, Red)]);
+    let db = snapshot(db, vec![pending(1, Red)]);
+    let db = snapshot(db, vec![pending(1, Red), pending(2, Yellow)]);
+    let state = state(db);
+
+    assert!(publish_next_plan(&state).await.unwrap());
+    let first = state.plans.latest().unwrap();
+    assert_eq!(first.sequence, 1);
+    assert!(first.changes.is_empty());
+
+    // Same snapshot: nothing is solved or published.
+    assert!(!publish_next_plan(&state).await.unwrap());
+    assert!(Arc::ptr_eq(&state.plans.latest().unwrap(), &first));
+
+    assert!(publish_next_plan(&state).await.unwrap());
+    let second = state.plans.latest().unwrap();
+    assert_eq!(second.sequence, 2);
+    assert_ne!(second.input_hash, first.input_hash);
+    assert_eq!(
+        second
+            .changes
+            .iter()
+            .map(|change| change.patient_id)
+            .collect::<Vec<_>>(),
+        [2]
+    );
+}
@@ -0,0 +1,353 @@
+use entity::TriageLevel::{self, Green, Red, Yellow};
+use hospitopt_rs::optimize::{
+    AssignmentOutcome, Criterion, HospitalSite, MultiObjectiveSolver, ObjectiveConfig,
+    PatientDemand, Problem, Solver, SurvivalModel, TriageBucket,
+    multi_objective::{ObjectiveConfigError, ObjectiveMode, ObjectiveSpec},
+};
+
+fn config(mode: ObjectiveMode, criteria: &[Criterion]) -> ObjectiveConfig {
+    ObjectiveConfig {
+        mode,
+        objectives: criteria
+            .iter()
+            .map(|&criterion| ObjectiveSpec {
+                criterion,
+                weight: 1.0,
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// Bucket id each patient was sent to, in patient order.
+fn buckets(config: ObjectiveConfig, problem: &Problem) -> Vec<Option<i32>> {
+    MultiObjectiveSolver::new(config)
+        .solve(problem)
+        .unwrap()
+        .plan
+        .assignments
+        .into_iter()
+        .map(|assignment| match assignment.outcome {
+            AssignmentOutcome::Assigned(bucket) => Some(bucket.speciality_id),
+            AssignmentOutcome::Unassigned(_) => None,
+        })
+        .collect()
+}
+
+fn hospital(id: i32, latitude: f64, bed_capacity: u32) -> HospitalSite {
+    HospitalSite {
+        id,
+        name: format!("Hospital {id}"),
+        latitude,
+        longitude: -9.14,
+        bed_capacity,
+        occupied_beds: 0,
+    }
+}
+
+fn bucket(id: i32, hospital: usize, triage_level: TriageLevel, capacity: u32) -> TriageBucket {
+    TriageBucket {
+        id,
+        hospital,
+        triage_level,
+        waiting_time_seconds: 600,
+        capacity,
+    }
+}
+
+fn patient(id: i32, condition: TriageLevel) -> PatientDemand {
+    PatientDemand {
+        id,
+        condition,
+        latitude: 38.70,
+        longitude: -9.14,
+    }
+}
+
+#[test]
+fn parses_and_validates_toml() {
+    let shipped = ObjectiveConfig::load("config/objectives.toml").unwrap();
+    assert_eq!(shipped.mode, ObjectiveMode::Lexicographic);
+    assert_eq!(
+        shipped.criteria().collect::<Vec<_>>(),
+        [
+            Criterion::RedUnassigned,
+            Criterion::Unassigned,
+            Criterion::OverOccupancy,
+            Criterion::TravelTime,
+            Criterion::LoadBalance,
+        ]
+    );
+
+    let weighted = ObjectiveConfig::from_toml(
+        r#"
+        mode = "weighted"
+        occupancy_target = 0.9
+
+        [[objectives]]
+        criterion = "lives-saved"
+        weight = 10
+
+        [[objectives]]
+        criterion = "travel-time"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(weighted.mode, ObjectiveMode::Weighted);
+    assert_eq!(weighted.occupancy_target, 0.9);
+    assert_eq!(
+        weighted.objectives,
+        [
+            ObjectiveSpec {
+                criterion: Criterion::LivesSaved,
+                weight: 10.0,
+            },
+            ObjectiveSpec {
+                criterion: Criterion::TravelTime,
+                weight: 1.0,
+            },
+        ]
+    );
+
+    let error = |text: &str| ObjectiveConfig::from_toml(text).unwrap_err();
+    assert!(matches!(
+        error("objectives = []"),
+        ObjectiveConfigError::Empty
+    ));
+    assert!(matches!(
+        error("[[objectives]]\ncriterion = \"fastest\""),
+        ObjectiveConfigError::Parse(_)
+    ));
+    assert!(matches!(
+        error("colour = \"red\"\n[[objectives]]\ncriterion = \"unassigned\""),
+        ObjectiveConfigError::Parse(_)
+    ));
+    assert!(matches!(
+        error(
+            "[[objectives]]\ncriterion = \"unassigned\"\n[[objectives]]\ncriterion = \"unassigned\""
+        ),
+        ObjectiveConfigError::Duplicate(Criterion::Unassigned)
+    ));
+    assert!(matches!(
+        error("[[objectives]]\ncriterion = \"travel-time\"\nweight = -1"),
+        ObjectiveConfigError::InvalidWeight {
+            criterion: Criterion::TravelTime,
+            ..
+        }
+    ));
+    assert!(matches!(
+        error("occupancy_target = 0\n[[objectives]]\ncriterion = \"unassigned\""),
+        ObjectiveConfigError::InvalidOccupancyTarget(_)
+    ));
+    assert!(matches!(
+        error(
+            "[[objectives]]\ncriterion = \"max-utilization\"\n[[objectives]]\ncriterion = \"unassigned\""
+        ),
+        ObjectiveConfigError::MaxUtilizationBeforeUnassigned
+    ));
+    assert!(matches!(
+        error(
+            "[[objectives]]\ncriterion = \"red-unassigned\"\n[[objectives]]\ncriterion = \"max-utilization\""
+        ),
+        ObjectiveConfigError::MaxUtilizationBeforeUnassigned
+    ));
+    assert!(
+        ObjectiveConfig::from_toml(
+            "mode = \"weighted\"\n[[objectives]]\ncriterion = \"max-utilization\""
+        )
+        .is_ok()
+    );
+    assert!(matches!(
+        ObjectiveConfig::load("config/missing.toml"),
+        Err(ObjectiveConfigError::Io(_))
+    ));
+}
+
+#[test]
+fn earlier_criteria_win_lexicographic_ties() {
+    // A near and a far red bucket, and one yellow bucket.
+    let problem = Problem {
+        hospitals: vec![hospital(1, 38.72, 10), hospital(2, 38.90, 10)],
+        buckets: vec![
+            bucket(10, 1, Red, 1),
+            bucket(11, 0, Red, 1),
+            bucket(12, 0, Yellow, 1),
+        ],
+        patients: vec![patient(1, Red), patient(2, Yellow)],
+        travel_times: None,
+        survival: SurvivalModel::default(),
+    };
+
+    // Every assignment adds travel, so travel-time alone assigns nobody.
+    // Ranked first, red-unassigned still places the Red patient, and
+    // travel-time then picks the nearer bucket and leaves the Yellow one out.
+    assert_eq!(
+        buckets(
+            config(
+                ObjectiveMode::Lexicographic,
+                &[Criterion::RedUnassigned, Criterion::TravelTime]
+            ),
+            &problem
+        ),
+        [Some(11), None]
+    );
+    assert_eq!(
+        buckets(
+            config(
+                ObjectiveMode::Lexicographic,
+                &[Criterion::TravelTime, Criterion::RedUnassigned]
+            ),
+            &problem
+        ),
+        [None, None]
+    );
+}
+
+/// Nine patients next to a hospital with ten beds, and an empty one further
+/// away with as many.
+fn crowded() -> Problem {
+    Problem {
+        hospitals: vec![hospital(1, 38.70, 10), hospital(2, 38.76, 10)],
+        buckets: vec![bucket(10, 0, Yellow, 10), bucket(20, 1, Yellow, 10)],
+        patients: (1..=9).map(|id| patient(id, Yellow)).collect(),
+        travel_times: None,
+        survival: SurvivalModel::default(),
+    }
+}
+
+fn split(plan: &[Option<i32>]) -> (usize, usize) {
+    let count = |bucket| {
+        plan.iter()
+            .filter(|choice| **choice == Some(bucket))
+            .count()
+    };
+    (count(10), count(20))
+}
+
+#[test]
+fn over_occupancy_keeps_hospitals_under_the_target() {
+    let problem = crowded();
+    let nearest = buckets(
+        config(
+            ObjectiveMode::Lexicographic,
+            &[Criterion::Unassigned, Criterion::TravelTime],
+        ),
+        &problem,
+    );
+    assert_eq!(split(&nearest), (9, 0));
+
+    // 85% of ten beds leaves room for eight.
+    let capped = buckets(
+        config(
+            ObjectiveMode::Lexicographic,
+            &[
+                Criterion::Unassigned,
+                Criterion::OverOccupancy,
+                Criterion::TravelTime,
+            ],
+        ),
+        &problem,
+    );
+    assert_eq!(split(&capped), (8, 1));
+}
+
+#[test]
+fn max_utilization_spreads_patients() {
+    let problem = crowded();
+    let lexicographic = buckets(
+        config(
+            ObjectiveMode::Lexicographic,
+            &[
+                Criterion::Unassigned,
+                Criterion::MaxUtilization,
+                Criterion::TravelTime,
+            ],
+        ),
+        &problem,
+    );
+    assert_eq!(split(&lexicographic), (5, 4));
+
+    let weighted = |fairness: f64| {
+        let mut config = config(
+            ObjectiveMode::Weighted,
+            &[
+                Criterion::Unassigned,
+                Criterion::TravelTime,
+                Criterion::MaxUtilization,
+            ],
+        );
+        config.objectives[0].weight = 1_000.0;
+        config.objectives[2].weight = fairness;
+        buckets(config, &problem)
+    };
+    assert_eq!(split(&weighted(0.0)), (9, 0));
+    assert_eq!(split(&weighted(1_000.0)), (5, 4));
+}
+
+#[test]
+fn occupied_beds_count_towards_occupancy() {
+    // Seven of the near hospital's ten beds are taken, so three are free.
+    let mut problem = crowded();
+    problem.hospitals[0].occupied_beds = 7;
+    problem.buckets[0].capacity = 3;
+    problem.patients.truncate(3);
+
+    let nearest = buckets(
+        config(
+            ObjectiveMode::Lexicographic,
+            &[Criterion::Unassigned, Criterion::TravelTime],
+        ),
+        &problem,
+    );
+    assert_eq!(split(&nearest), (3, 0));
+
+    // The 85% target leaves room for one more patient there.
+    let capped = buckets(
+        config(
+            ObjectiveMode::Lexicographic,
+            &[
+                Criterion::Unassigned,
+                Criterion::OverOccupancy,
+                Criterion::TravelTime,
+            ],
+        ),
+        &problem,
+    );
+    assert_eq!(split(&capped), (1, 2));
+
+    // Already at 70%, it is the fullest hospital even without new patients.
+    let fairest = buckets(
+        config(
+            ObjectiveMode::Lexicographic,
+            &[
+                Criterion::Unassigned,
+                Criterion::MaxUtilization,
+                Criterion::TravelTime,
+            ],
+        ),
+        &problem,
+    );
+    assert_eq!(split(&fairest), (0, 3));
+}
+
+#[test]
+fn weighted_max_utilization_tries_every_level() {
+    // Only the three-bed hospital takes red patients, so placing a patient
+    // needs a third more utilization. The idle ten-bed hospital adds levels
+    // in between that change nothing, and the total cost plateaus there.
+    let problem = Problem {
+        hospitals: vec![hospital(1, 38.70, 10), hospital(2, 38.76, 3)],
+        buckets: vec![bucket(10, 0, Green, 10), bucket(20, 1, Red, 3)],
+        patients: (1..=3).map(|id| patient(id, Red)).collect(),
+        travel_times: None,
+        survival: SurvivalModel::default(),
+    };
+    let mut config = config(
+        ObjectiveMode::Weighted,
+        &[Criterion::Unassigned, Criterion::MaxUtilization],
+    );
+    config.objectives[0].weight = 1_000.0;
+    config.objectives[1].weight = 100.0;
+
+    assert_eq!(buckets(config, &problem), [Some(20); 3]);
+}
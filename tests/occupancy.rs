@@ -0,0 +1,187 @@
+use chrono::{DateTime, Duration};
+use entity::{
+    TriageLevel::{self, Red, Yellow},
+    bed_occupancy, hospital_speciality, patient,
+    patient::PatientStatus,
+};
+use hospitopt_rs::occupancy::{availability_at, reserve_en_route};
+use sea_orm::prelude::DateTimeWithTimeZone;
+
+fn at() -> DateTimeWithTimeZone {
+    DateTime::parse_from_rfc3339("2026-10-13T12:00:00+01:00").unwrap()
+}
+
+fn bucket(id: i32, triage_level: TriageLevel, beds_capacity: i32) -> hospital_speciality::Model {
+    hospital_speciality::Model {
+        id,
+        hospital_id: 1,
+        triage_level,
+        waiting_time_seconds: 600,
+        beds_capacity,
+    }
+}
+
+fn record(
+    id: i32,
+    triage_level: TriageLevel,
+    occupied_beds: i32,
+    minutes_before: i64,
+) -> bed_occupancy::Model {
+    bed_occupancy::Model {
+        id,
+        hospital_id: 1,
+        triage_level,
+        occupied_beds,
+        recorded_at: at() - Duration::minutes(minutes_before),
+    }
+}
+
+/// `(speciality_id, occupied, available)` per bucket.
+fn occupied(
+    specialities: &[hospital_speciality::Model],
+    occupancy: &[bed_occupancy::Model],
+) -> Vec<(i32, i32, i32)> {
+    availability_at(specialities, occupancy, at())
+        .into_iter()
+        .map(|entry| (entry.speciality_id, entry.occupied, entry.available))
+        .collect()
+}
+
+#[test]
+fn occupied_beds_fill_buckets_in_id_order() {
+    let specialities = [
+        bucket(12, Red, 4),
+        bucket(10, Red, 3),
+        bucket(11, Yellow, 2),
+    ];
+    assert_eq!(
+        occupied(&specialities, &[record(1, Red, 5, 10)]),
+        [(10, 3, 0), (11, 0, 2), (12, 2, 2)]
+    );
+}
+
+#[test]
+fn occupancy_above_capacity_is_clamped() {
+    let specialities = [bucket(10, Red, 3), bucket(11, Red, 2)];
+    assert_eq!(
+        occupied(&specialities, &[record(1, Red, 9, 10)]),
+        [(10, 3, 0), (11, 2, 0)]
+    );
+    assert_eq!(
+        occupied(&specialities, &[record(1, Red, -4, 10)]),
+        [(10, 0, 3), (11, 0, 2)]
+    );
+}
+
+#[test]
+fn the_latest_record_wins() {
+    let specialities = [bucket(10, Red, 10)];
+    // Records after `at` are ignored, and ties on time go to the higher id.
+    let occupancy = [
+        record(1, Red, 2, 30),
+        record(4, Red, 6, 5),
+        record(3, Red, 5, 5),
+        record(2, Red, 9, -5),
+    ];
+    assert_eq!(occupied(&specialities, &occupancy), [(10, 6, 4)]);
+
+    let availability = availability_at(&specialities, &occupancy, at());
+    assert_eq!(
+        availability[0].recorded_at,
+        Some(at() - Duration::minutes(5))
+    );
+    assert_eq!(
+        availability_at(&specialities, &[], at())[0].recorded_at,
+        None
+    );
+}
+
+fn patient(
+    id: i32,
+    condition: TriageLevel,
+    status: PatientStatus,
+    assigned_hospital_id: Option<i32>,
+) -> patient::Model {
+    patient::Model {
+        id,
+        condition,
+        latitude: 38.73,
+        longitude: -9.15,
+        status,
+        reported_at: at(),
+        admitted_at: None,
+        assigned_hospital_id,
+    }
+}
+
+#[test]
+fn en_route_patients_reserve_beds() {
+    let specialities = [
+        bucket(10, Red, 1),
+        bucket(11, Red, 2),
+        bucket(12, Yellow, 2),
+    ];
+    let mut availability = availability_at(&specialities, &[], at());
+    reserve_en_route(
+        &mut availability,
+        &[
+            patient(1, Red, PatientStatus::Dispatched, Some(1)),
+            patient(2, Red, PatientStatus::InTransport, Some(1)),
+            // Not en route, sent elsewhere, or with no hospital yet.
+            patient(3, Yellow, PatientStatus::Reported, Some(1)),
+            patient(4, Yellow, PatientStatus::Admitted, Some(1)),
+            patient(5, Yellow, PatientStatus::Dispatched, Some(2)),
+            patient(6, Yellow, PatientStatus::Dispatched, None),
+        ],
+        at(),
+    );
+
+    let occupied: Vec<(i32, i32, i32)> = availability
+        .into_iter()
+        .map(|entry| (entry.speciality_id, entry.occupied, entry.available))
+        .collect();
+    assert_eq!(occupied, [(10, 1, 0), (11, 1, 1), (12, 0, 2)]);
+    assert!(
+        PatientStatus::PENDING
+            .iter()
+            .all(|status| status.is_pending())
+    );
+    assert!(
+        !PatientStatus::EN_ROUTE
+            .iter()
+            .any(|status| status.is_pending())
+    );
+}
+
+#[test]
+fn a_past_time_only_reserves_for_patients_reported_by_then() {
+    let specialities = [bucket(10, Red, 3)];
+    let earlier = at() - Duration::hours(1);
+    let patients = [
+        patient(1, Red, PatientStatus::InTransport, Some(1)),
+        patient::Model {
+            reported_at: earlier - Duration::minutes(10),
+            ..patient(2, Red, PatientStatus::Dispatched, Some(1))
+        },
+        // Admitted before the requested time, so already in the occupancy.
+        patient::Model {
+            reported_at: earlier - Duration::minutes(30),
+            admitted_at: Some(earlier - Duration::minutes(5)),
+            ..patient(3, Red, PatientStatus::InTransport, Some(1))
+        },
+    ];
+
+    let mut availability = availability_at(&specialities, &[], earlier);
+    reserve_en_route(&mut availability, &patients, earlier);
+    assert_eq!(
+        (availability[0].occupied, availability[0].available),
+        (1, 2)
+    );
+
+    let mut availability = availability_at(&specialities, &[], at());
+    reserve_en_route(&mut availability, &patients, at());
+    assert_eq!(
+        (availability[0].occupied, availability[0].available),
+        (2, 1)
+    );
+}
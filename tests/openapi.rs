@@ -0,0 +1,104 @@
+//! Checks that `hospitopt_rs::api::router` and the OpenAPI document describe
+//! the same routes.
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+};
+use hospitopt_rs::api::{AppState, PlanFeed, openapi::ApiDoc, router};
+use hospitopt_rs::{
+    optimize::{DEFAULT_SOLVER, SolverRegistry, SurvivalModel},
+    travel::TravelTimes,
+};
+use sea_orm::{DatabaseBackend, MockDatabase};
+use std::{collections::BTreeSet, sync::Arc};
+use tower::ServiceExt;
+use utoipa::OpenApi;
+
+const API: &str = include_str!("../src/api/mod.rs");
+const METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+/// `(method, path)` of every `.route(...)` call in `router`.
+fn registered() -> BTreeSet<(String, String)> {
+    let (_, body) = API.split_once("pub fn router(").unwrap();
+    let (body, _) = body.split_once(".with_state(").unwrap();
+
+    body.split(".route(")
+        .skip(1)
+        .flat_map(|route| {
+            let path = route.split('"').nth(1).unwrap().to_string();
+            // Handlers are called `get`, `delete`, ... too, but only method
+            // routers are followed by an opening parenthesis.
+            METHODS
+                .into_iter()
+                .filter(move |method| {
+                    route.match_indices(&format!("{method}(")).any(|(at, _)| {
+                        !route[..at].ends_with(|c: char| c == ':' || c.is_alphanumeric())
+                    })
+                })
+                .map(move |method| (method.to_string(), path.clone()))
+        })
+        .collect()
+}
+
+/// `(method, path)` of every operation in the document.
+fn documented() -> BTreeSet<(String, String)> {
+    let document = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+    document["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(path, operations)| {
+            operations
+                .as_object()
+                .unwrap()
+                .keys()
+                .filter(|method| METHODS.contains(&method.as_str()))
+                .map(move |method| (method.clone(), path.clone()))
+        })
+        .collect()
+}
+
+#[test]
+fn every_route_is_documented() {
+    let registered = registered();
+    assert_eq!(registered.len(), 18);
+
+    let documented = documented();
+    let missing: Vec<_> = registered.difference(&documented).collect();
+    assert!(
+        missing.is_empty(),
+        "routes missing from the document: {missing:?}"
+    );
+}
+
+#[tokio::test]
+async fn every_documented_operation_is_routed() {
+    let app = router(Arc::new(AppState {
+        db: MockDatabase::new(DatabaseBackend::Postgres).into_connection(),
+        registry: SolverRegistry::default(),
+        default_solver: DEFAULT_SOLVER.to_string(),
+        objectives: None,
+        survival: SurvivalModel::default(),
+        travel_times: TravelTimes::default(),
+        plans: PlanFeed::default(),
+        max_disruption: None,
+    }));
+
+    for (method, path) in documented() {
+        let request = Request::builder()
+            .method(Method::from_bytes(method.to_uppercase().as_bytes()).unwrap())
+            .uri(path.replace("{id}", "1"))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+
+        // The empty mock database fails every query, so handlers answer
+        // with an error of their own, never with the router's 404 or 405.
+        let unrouted = response.status() == StatusCode::METHOD_NOT_ALLOWED
+            || (response.status() == StatusCode::NOT_FOUND
+                && response.headers().get("content-type").is_none());
+        assert!(!unrouted, "{method} {path} is not routed");
+    }
+}
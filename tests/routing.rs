@@ -0,0 +1,389 @@
+#![cfg(feature = "osm")]
+
+use flate2::{Compression, write::ZlibEncoder};
+use hospitopt_rs::{
+    geo::{self, Coordinate, TravelTimeModel},
+    optimize::{HospitalSite, PatientDemand, Problem, SurvivalModel},
+    routing::{RoadGraph, RoutingError, RoutingProfile},
+};
+use prost::Message;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use std::{io::Write, path::PathBuf};
+
+/// Encoder side of the OSM PBF messages, with the tags from `osmformat.proto`
+/// and `fileformat.proto`.
+#[derive(Clone, PartialEq, Message)]
+struct BlobHeader {
+    #[prost(string, tag = "1")]
+    kind: String,
+    #[prost(int32, tag = "3")]
+    datasize: i32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Blob {
+    #[prost(bytes = "vec", optional, tag = "1")]
+    raw: Option<Vec<u8>>,
+    #[prost(int32, optional, tag = "2")]
+    raw_size: Option<i32>,
+    #[prost(bytes = "vec", optional, tag = "3")]
+    zlib_data: Option<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct HeaderBlock {
+    #[prost(string, repeated, tag = "4")]
+    required_features: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct PrimitiveBlock {
+    #[prost(message, optional, tag = "1")]
+    stringtable: Option<StringTable>,
+    #[prost(message, repeated, tag = "2")]
+    primitivegroup: Vec<PrimitiveGroup>,
+    #[prost(int32, optional, tag = "17")]
+    granularity: Option<i32>,
+    #[prost(int64, optional, tag = "19")]
+    lat_offset: Option<i64>,
+    #[prost(int64, optional, tag = "20")]
+    lon_offset: Option<i64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct StringTable {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    s: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct PrimitiveGroup {
+    #[prost(message, repeated, tag = "1")]
+    nodes: Vec<Node>,
+    #[prost(message, optional, tag = "2")]
+    dense: Option<DenseNodes>,
+    #[prost(message, repeated, tag = "3")]
+    ways: Vec<Way>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Node {
+    #[prost(sint64, tag = "1")]
+    id: i64,
+    #[prost(sint64, tag = "8")]
+    lat: i64,
+    #[prost(sint64, tag = "9")]
+    lon: i64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct DenseNodes {
+    #[prost(sint64, repeated, tag = "1")]
+    id: Vec<i64>,
+    #[prost(sint64, repeated, tag = "8")]
+    lat: Vec<i64>,
+    #[prost(sint64, repeated, tag = "9")]
+    lon: Vec<i64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Way {
+    #[prost(int64, tag = "1")]
+    id: i64,
+    #[prost(uint32, repeated, tag = "2")]
+    keys: Vec<u32>,
+    #[prost(uint32, repeated, tag = "3")]
+    vals: Vec<u32>,
+    #[prost(sint64, repeated, tag = "8")]
+    refs: Vec<i64>,
+}
+
+/// Node positions of the fixture, by OSM id. 109 and 110 are only used by a
+/// footway and must not end up in the graph.
+const NODES: [(i64, f64, f64); 10] = [
+    (101, 38.700, -9.100),
+    (102, 38.701, -9.101),
+    (103, 38.710, -9.100),
+    (104, 38.711, -9.101),
+    (105, 38.720, -9.100),
+    (106, 38.721, -9.101),
+    (107, 38.730, -9.100),
+    (108, 38.731, -9.101),
+    (109, 38.740, -9.100),
+    (110, 38.741, -9.101),
+];
+
+type Tags = &'static [(&'static str, &'static str)];
+
+/// `(way id, node ids, tags)`: one way per direction rule.
+const WAYS: [(i64, [i64; 2], Tags); 5] = [
+    (1, [101, 102], &[("highway", "primary"), ("oneway", "yes")]),
+    (2, [103, 104], &[("highway", "primary"), ("oneway", "-1")]),
+    (
+        3,
+        [105, 106],
+        &[("highway", "residential"), ("junction", "roundabout")],
+    ),
+    (4, [107, 108], &[("highway", "residential")]),
+    (5, [109, 110], &[("highway", "footway")]),
+];
+
+fn units(degrees: f64, granularity: i64, offset: i64) -> i64 {
+    ((degrees * 1e9 - offset as f64) / granularity as f64).round() as i64
+}
+
+fn delta_coded(values: impl IntoIterator<Item = i64>) -> Vec<i64> {
+    let mut previous = 0;
+    values
+        .into_iter()
+        .map(|value| {
+            let delta = value - previous;
+            previous = value;
+            delta
+        })
+        .collect()
+}
+
+/// Nodes 101-107 as raw dense nodes with the default granularity; node 108
+/// onwards as plain nodes plus every way in a zlib block with a coarser
+/// granularity and non-zero offsets.
+fn blocks() -> Vec<PrimitiveBlock> {
+    let dense = &NODES[..7];
+    let first = PrimitiveBlock {
+        stringtable: Some(StringTable::default()),
+        primitivegroup: vec![PrimitiveGroup {
+            dense: Some(DenseNodes {
+                id: delta_coded(dense.iter().map(|node| node.0)),
+                lat: delta_coded(dense.iter().map(|node| units(node.1, 100, 0))),
+                lon: delta_coded(dense.iter().map(|node| units(node.2, 100, 0))),
+            }),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let (granularity, lat_offset, lon_offset) = (1_000, 500, -700);
+    let mut strings = vec![Vec::new()];
+    let mut string = |value: &str| {
+        let index = strings
+            .iter()
+            .position(|existing| existing == value.as_bytes())
+            .unwrap_or_else(|| {
+                strings.push(value.as_bytes().to_vec());
+                strings.len() - 1
+            });
+        index as u32
+    };
+    let ways = WAYS
+        .iter()
+        .map(|(id, refs, tags)| Way {
+            id: *id,
+            keys: tags.iter().map(|(key, _)| string(key)).collect(),
+            vals: tags.iter().map(|(_, value)| string(value)).collect(),
+            refs: delta_coded(refs.iter().copied()),
+        })
+        .collect();
+    let second = PrimitiveBlock {
+        stringtable: Some(StringTable { s: strings }),
+        primitivegroup: vec![
+            PrimitiveGroup {
+                nodes: NODES[7..]
+                    .iter()
+                    .map(|&(id, latitude, longitude)| Node {
+                        id,
+                        lat: units(latitude, granularity, lat_offset),
+                        lon: units(longitude, granularity, lon_offset),
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+            PrimitiveGroup {
+                ways,
+                ..Default::default()
+            },
+        ],
+        granularity: Some(granularity as i32),
+        lat_offset: Some(lat_offset),
+        lon_offset: Some(lon_offset),
+    };
+
+    vec![first, second]
+}
+
+fn write_extract(name: &str, required_features: &[&str], blocks: &[PrimitiveBlock]) -> PathBuf {
+    let mut file = Vec::new();
+    let mut blob = |kind: &str, data: Vec<u8>, compress: bool| {
+        let blob = if compress {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&data).unwrap();
+            Blob {
+                raw_size: Some(data.len() as i32),
+                zlib_data: Some(encoder.finish().unwrap()),
+                ..Default::default()
+            }
+        } else {
+            Blob {
+                raw: Some(data),
+                ..Default::default()
+            }
+        }
+        .encode_to_vec();
+        let header = BlobHeader {
+            kind: kind.to_string(),
+            datasize: blob.len() as i32,
+        }
+        .encode_to_vec();
+        file.extend_from_slice(&(header.len() as u32).to_be_bytes());
+        file.extend_from_slice(&header);
+        file.extend_from_slice(&blob);
+    };
+
+    let header = HeaderBlock {
+        required_features: required_features.iter().map(|f| f.to_string()).collect(),
+    };
+    blob("OSMHeader", header.encode_to_vec(), false);
+    for (index, block) in blocks.iter().enumerate() {
+        blob("OSMData", block.encode_to_vec(), index % 2 == 1);
+    }
+
+    let path =
+        std::env::temp_dir().join(format!("hospitopt-{}-{name}.osm.pbf", std::process::id()));
+    std::fs::write(&path, file).unwrap();
+    path
+}
+
+fn reachable(graph: &RoadGraph, from: usize, to: usize) -> bool {
+    graph.shortest_seconds(from, &[to])[to].is_finite()
+}
+
+#[test]
+fn reads_nodes_ways_and_directions_from_a_pbf_extract() {
+    let path = write_extract("fixture", &["OsmSchema-V0.6", "DenseNodes"], &blocks());
+    let graph = RoadGraph::from_pbf(&path, &RoutingProfile::default());
+    std::fs::remove_file(&path).unwrap();
+    let graph = graph.unwrap();
+
+    // The footway and its two nodes are dropped; two-way roads add two edges.
+    assert_eq!(graph.node_count(), 8);
+    assert_eq!(graph.edge_count(), 5);
+
+    let node = |id: i64| {
+        let (_, latitude, longitude) = NODES.iter().find(|node| node.0 == id).unwrap();
+        let (node, km) = graph.snap(Coordinate::new(*latitude, *longitude)).unwrap();
+        assert!(km < 0.001, "node {id} decoded {km} km away");
+        node
+    };
+
+    // oneway=yes
+    assert!(reachable(&graph, node(101), node(102)));
+    assert!(!reachable(&graph, node(102), node(101)));
+    // oneway=-1
+    assert!(!reachable(&graph, node(103), node(104)));
+    assert!(reachable(&graph, node(104), node(103)));
+    // Roundabouts are one-way without a tag.
+    assert!(reachable(&graph, node(105), node(106)));
+    assert!(!reachable(&graph, node(106), node(105)));
+    // Everything else is two-way.
+    assert!(reachable(&graph, node(107), node(108)));
+    assert!(reachable(&graph, node(108), node(107)));
+}
+
+#[test]
+fn rejects_unsupported_required_features() {
+    let path = write_extract(
+        "features",
+        &["OsmSchema-V0.6", "HistoricalInformation"],
+        &[],
+    );
+    let result = RoadGraph::from_pbf(&path, &RoutingProfile::default());
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(result, Err(RoutingError::Unsupported(_))));
+}
+
+/// 0 → 1 → 2 → 3 one minute apart, and an isolated node 4 far away.
+fn line() -> RoadGraph {
+    let coordinates = vec![
+        Coordinate::new(38.70, -9.10),
+        Coordinate::new(38.71, -9.10),
+        Coordinate::new(38.72, -9.10),
+        Coordinate::new(38.73, -9.10),
+        Coordinate::new(38.80, -9.30),
+    ];
+    RoadGraph::from_edges(coordinates, vec![(0, 1, 60.0), (1, 2, 60.0), (2, 3, 60.0)])
+}
+
+#[test]
+fn shortest_seconds_stops_once_targets_are_settled() {
+    let graph = line();
+
+    assert_eq!(
+        graph.shortest_seconds(0, &[3]),
+        [0.0, 60.0, 120.0, 180.0, f64::INFINITY]
+    );
+    // Node 1 is settled before its edges are relaxed, so 2 is never reached.
+    let early = graph.shortest_seconds(0, &[1]);
+    assert_eq!(early[1], 60.0);
+    assert!(early[2].is_infinite());
+    assert!(graph.shortest_seconds(3, &[0])[0].is_infinite());
+}
+
+#[test]
+fn unreachable_pairs_fall_back_to_great_circle_times() {
+    let graph = line();
+    let hospital = |id: i32, latitude: f64, longitude: f64| HospitalSite {
+        id,
+        name: format!("Hospital {id}"),
+        latitude,
+        longitude,
+        bed_capacity: 10,
+        occupied_beds: 0,
+    };
+    let problem = Problem {
+        hospitals: vec![hospital(1, 38.73, -9.10), hospital(2, 38.80, -9.30)],
+        buckets: Vec::new(),
+        patients: vec![PatientDemand {
+            id: 1,
+            condition: entity::TriageLevel::Red,
+            latitude: 38.70,
+            longitude: -9.10,
+        }],
+        travel_times: None,
+        survival: SurvivalModel::default(),
+    };
+    let model = TravelTimeModel::default();
+
+    let matrix = graph.patients_to_hospitals(&problem, &RoutingProfile::default(), &model, None, 9);
+
+    assert!((matrix.get(0, 0) - 180.0).abs() < 1e-6);
+    assert_eq!(
+        matrix.get(0, 1),
+        model.travel_seconds(
+            Coordinate::new(38.70, -9.10),
+            Coordinate::new(38.80, -9.30),
+            None,
+            9
+        )
+    );
+}
+
+#[test]
+fn snapping_matches_a_linear_scan() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut point = || Coordinate::new(rng.random_range(38.6..38.9), rng.random_range(-9.4..-9.0));
+    let coordinates: Vec<Coordinate> = (0..500).map(|_| point()).collect();
+    let queries: Vec<Coordinate> = (0..200).map(|_| point()).collect();
+    let graph = RoadGraph::from_edges(coordinates.clone(), Vec::new());
+
+    for query in queries {
+        let closest = coordinates
+            .iter()
+            .map(|node| geo::haversine_km(query, *node))
+            .fold(f64::INFINITY, f64::min);
+        let (_, km) = graph.snap(query).unwrap();
+        assert_eq!(km, closest);
+    }
+
+    let empty = RoadGraph::from_edges(Vec::new(), Vec::new());
+    assert_eq!(empty.snap(Coordinate::new(38.72, -9.14)), None);
+}
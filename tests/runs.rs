@@ -0,0 +1,153 @@
+use chrono::DateTime;
+use entity::{
+    TriageLevel::{Red, Yellow},
+    bed_occupancy, hospital, hospital_speciality, patient,
+    patient::PatientStatus,
+};
+use hospitopt_rs::{
+    occupancy,
+    optimize::Problem,
+    runs::{dispatch_input_hash, input_hash},
+    scenario::Scenario,
+};
+use sea_orm::prelude::DateTimeWithTimeZone;
+
+fn at() -> DateTimeWithTimeZone {
+    DateTime::parse_from_rfc3339("2026-10-13T23:40:00+01:00").unwrap()
+}
+
+/// Two hospitals with three buckets, one partly occupied, and three
+/// patients, one of them admitted.
+fn rows() -> (
+    Vec<hospital::Model>,
+    Vec<hospital_speciality::Model>,
+    Vec<bed_occupancy::Model>,
+    Vec<patient::Model>,
+) {
+    let hospital = |id: i32, latitude: f64| hospital::Model {
+        id,
+        location_name: format!("Hospital {id}"),
+        latitude,
+        longitude: -9.14,
+        bed_capacity: 20,
+    };
+    let bucket = |id: i32, hospital_id: i32, triage_level| hospital_speciality::Model {
+        id,
+        hospital_id,
+        triage_level,
+        waiting_time_seconds: 600,
+        beds_capacity: 3,
+    };
+    let patient = |id: i32, latitude: f64, status| patient::Model {
+        id,
+        condition: Red,
+        latitude,
+        longitude: -9.15,
+        status,
+        reported_at: at(),
+        admitted_at: None,
+        assigned_hospital_id: None,
+    };
+
+    (
+        vec![hospital(1, 38.72), hospital(2, 38.75)],
+        vec![
+            bucket(11, 1, Red),
+            bucket(12, 2, Red),
+            bucket(13, 2, Yellow),
+        ],
+        vec![bed_occupancy::Model {
+            id: 1,
+            hospital_id: 1,
+            triage_level: Red,
+            occupied_beds: 2,
+            recorded_at: at(),
+        }],
+        vec![
+            patient(4, 38.73, PatientStatus::Reported),
+            patient(5, 38.74, PatientStatus::Reported),
+            patient(6, 38.71, PatientStatus::Admitted),
+        ],
+    )
+}
+
+fn problem(
+    hospitals: &[hospital::Model],
+    specialities: &[hospital_speciality::Model],
+    occupancy: &[bed_occupancy::Model],
+    patients: &[patient::Model],
+) -> Problem {
+    let availability = occupancy::availability_at(specialities, occupancy, at());
+    let live = occupancy::live_specialities(specialities, &availability);
+    Problem::from_models(hospitals, &live, patients)
+        .unwrap()
+        .with_occupied_beds(&availability)
+}
+
+fn reversed<T: Clone>(rows: &[T]) -> Vec<T> {
+    rows.iter().rev().cloned().collect()
+}
+
+#[test]
+fn row_order_does_not_change_the_hash() {
+    let (hospitals, specialities, occupancy, patients) = rows();
+    let hash = input_hash(&problem(&hospitals, &specialities, &occupancy, &patients));
+
+    assert_eq!(
+        input_hash(&problem(
+            &reversed(&hospitals),
+            &reversed(&specialities),
+            &occupancy,
+            &reversed(&patients),
+        )),
+        hash
+    );
+}
+
+#[test]
+fn a_rebuilt_scenario_has_the_same_hash() {
+    let (hospitals, specialities, occupancy, patients) = rows();
+    let expected = problem(&hospitals, &specialities, &occupancy, &patients);
+
+    let availability = occupancy::availability_at(&specialities, &occupancy, at());
+    let scenario = Scenario::from_models(
+        at(),
+        &hospitals,
+        &specialities,
+        &availability,
+        &patients,
+        &[],
+    );
+    assert_eq!(
+        input_hash(&scenario.problem().unwrap()),
+        input_hash(&expected)
+    );
+
+    let replayed = Scenario::from_toml(&scenario.to_toml().unwrap()).unwrap();
+    assert_eq!(
+        input_hash(&replayed.problem().unwrap()),
+        input_hash(&expected)
+    );
+    assert_eq!(
+        dispatch_input_hash(&replayed.dispatch_problem().unwrap()),
+        dispatch_input_hash(&scenario.dispatch_problem().unwrap())
+    );
+}
+
+#[test]
+fn changing_a_bucket_changes_the_hash() {
+    let (hospitals, mut specialities, mut occupancy, patients) = rows();
+    let hash = input_hash(&problem(&hospitals, &specialities, &occupancy, &patients));
+
+    specialities[2].beds_capacity += 1;
+    let resized = input_hash(&problem(&hospitals, &specialities, &occupancy, &patients));
+    assert_ne!(resized, hash);
+
+    // Moving a bed from occupied to free changes the occupancy too.
+    occupancy[0].occupied_beds -= 1;
+    specialities[2].beds_capacity -= 1;
+    assert_ne!(
+        input_hash(&problem(&hospitals, &specialities, &occupancy, &patients)),
+        hash
+    );
+}
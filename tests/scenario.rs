@@ -0,0 +1,131 @@
+use chrono::DateTime;
+use entity::{
+    TriageLevel::{Red, Yellow},
+    ambulance::{self, AmbulanceType, CrewStatus},
+    bed_occupancy, hospital, hospital_speciality, patient,
+    patient::PatientStatus,
+};
+use hospitopt_rs::{
+    dispatch::DispatchProblem,
+    occupancy,
+    optimize::Problem,
+    scenario::{Scenario, ScenarioError},
+};
+
+/// Two hospitals, one with a partly occupied red bucket, a pending and an
+/// admitted patient, and an ambulance.
+fn snapshot() -> Scenario {
+    let at = DateTime::parse_from_rfc3339("2026-10-13T23:40:00+01:00").unwrap();
+    let hospital = |id: i32, latitude: f64| hospital::Model {
+        id,
+        location_name: format!("Hospital {id}"),
+        latitude,
+        longitude: -9.14,
+        bed_capacity: 20,
+    };
+    let bucket = |id: i32, hospital_id: i32, triage_level| hospital_speciality::Model {
+        id,
+        hospital_id,
+        triage_level,
+        waiting_time_seconds: 600,
+        beds_capacity: 3,
+    };
+    let patient = |id: i32, status| patient::Model {
+        id,
+        condition: Red,
+        latitude: 38.73,
+        longitude: -9.15,
+        status,
+        reported_at: at,
+        admitted_at: None,
+        assigned_hospital_id: None,
+    };
+
+    let hospitals = [hospital(2, 38.75), hospital(1, 38.72)];
+    let specialities = [
+        bucket(11, 1, Red),
+        bucket(12, 2, Red),
+        bucket(13, 2, Yellow),
+    ];
+    let occupancy = [bed_occupancy::Model {
+        id: 1,
+        hospital_id: 1,
+        triage_level: Red,
+        occupied_beds: 2,
+        recorded_at: at,
+    }];
+    let patients = [
+        patient(5, PatientStatus::Reported),
+        patient(4, PatientStatus::Admitted),
+    ];
+    let ambulances = [ambulance::Model {
+        id: 1,
+        call_sign: "INEM 01".to_string(),
+        ambulance_type: AmbulanceType::Als,
+        crew_status: CrewStatus::Available,
+        capacity: 1,
+        latitude: 38.74,
+        longitude: -9.13,
+        home_base: "Lisboa".to_string(),
+        home_latitude: 38.71,
+        home_longitude: -9.13,
+    }];
+
+    let availability = occupancy::availability_at(&specialities, &occupancy, at);
+    let live = occupancy::live_specialities(&specialities, &availability);
+    let scenario = Scenario::from_models(
+        at,
+        &hospitals,
+        &specialities,
+        &availability,
+        &patients,
+        &ambulances,
+    );
+
+    let problem = scenario.problem().unwrap();
+    assert_eq!(
+        problem,
+        Problem::from_models(&hospitals, &live, &patients)
+            .unwrap()
+            .with_occupied_beds(&availability)
+    );
+    assert_eq!(problem.hospitals[0].occupied_beds, 2);
+    let mut dispatch =
+        DispatchProblem::from_models(&hospitals, &live, &patients, &ambulances).unwrap();
+    dispatch.problem = dispatch.problem.with_occupied_beds(&availability);
+    assert_eq!(scenario.dispatch_problem().unwrap(), dispatch);
+    scenario
+}
+
+#[test]
+fn round_trips_through_toml_and_json() {
+    let scenario = snapshot();
+
+    let toml = scenario.to_toml().unwrap();
+    assert_eq!(Scenario::from_toml(&toml).unwrap(), scenario);
+
+    let json = serde_json::to_string(&scenario).unwrap();
+    assert_eq!(Scenario::from_json(&json).unwrap(), scenario);
+}
+
+#[test]
+fn rejects_other_versions_and_duplicate_ids() {
+    let mut scenario = snapshot();
+    scenario.version = 2;
+    let toml = scenario.to_toml().unwrap();
+    assert!(matches!(
+        Scenario::from_toml(&toml),
+        Err(ScenarioError::UnsupportedVersion(2))
+    ));
+
+    let mut scenario = snapshot();
+    scenario.patients.push(scenario.patients[0].clone());
+    let toml = scenario.to_toml().unwrap();
+    assert!(matches!(
+        Scenario::from_toml(&toml),
+        Err(ScenarioError::DuplicateId {
+            table: "patients",
+            id: 4
+        })
+    ));
+}
@@ -0,0 +1,92 @@
+use hospitopt_rs::{
+    optimize::{GreedySolver, SurvivalModel},
+    scenario::Scenario,
+    simulation::{SimulationConfig, SimulationReport, simulate},
+    travel::TravelTimes,
+};
+use std::time::Duration;
+
+/// One small hospital that fills up within the shift; it has no orange beds.
+const SCENARIO: &str = r#"
+version = 1
+taken_at = "2026-10-13T20:00:00+01:00"
+
+[[hospitals]]
+id = 1
+name = "Small"
+latitude = 38.7487
+longitude = -9.1603
+bed_capacity = 10
+
+[[hospitals.triage_buckets]]
+id = 1
+triage_level = "red"
+waiting_time_seconds = 300
+beds_capacity = 2
+occupied_beds = 1
+
+[[hospitals.triage_buckets]]
+id = 2
+triage_level = "yellow"
+waiting_time_seconds = 900
+beds_capacity = 4
+
+[[hospitals.triage_buckets]]
+id = 3
+triage_level = "green"
+waiting_time_seconds = 1800
+beds_capacity = 4
+
+[[patients]]
+id = 7
+condition = "red"
+latitude = 38.7369
+longitude = -9.1427
+"#;
+
+fn run(seed: u64) -> SimulationReport {
+    let scenario = Scenario::from_toml(SCENARIO).unwrap();
+    let config = SimulationConfig {
+        duration: Duration::from_secs(6 * 3_600),
+        arrivals_per_hour: 12.0,
+        seed,
+        ..Default::default()
+    };
+
+    let mut report = simulate(
+        &scenario,
+        &GreedySolver,
+        &TravelTimes::default(),
+        &SurvivalModel::default(),
+        &config,
+    )
+    .unwrap();
+    report.solver_time = Duration::ZERO;
+    report
+}
+
+#[test]
+fn same_seed_same_shift() {
+    assert_eq!(run(3), run(3));
+    assert_ne!(run(3), run(4));
+}
+
+#[test]
+fn every_patient_is_accounted_for() {
+    let report = run(3);
+
+    let arrivals: usize = report.levels.iter().map(|level| level.arrivals).sum();
+    let treated: usize = report.levels.iter().map(|level| level.treated).sum();
+    let turned_away: usize = report.levels.iter().map(|level| level.turned_away).sum();
+    assert!(arrivals > 1, "the shift should see new arrivals");
+    assert_eq!(arrivals, treated + turned_away + report.unfinished);
+
+    let orange = &report.levels[1];
+    assert_eq!(orange.treated, 0, "no hospital takes orange patients");
+    assert_eq!(orange.turned_away, orange.arrivals);
+
+    let hospital = &report.hospitals[0];
+    assert_eq!(hospital.beds, 10);
+    assert!(hospital.peak_occupied <= hospital.beds);
+    assert!((0.0..=1.0).contains(&hospital.utilization));
+}
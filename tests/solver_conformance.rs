@@ -0,0 +1,12 @@
+use hospitopt_rs::optimize::{SolverRegistry, conformance};
+
+#[test]
+fn registered_backends_pass_conformance() {
+    let registry = SolverRegistry::default();
+
+    for solver in registry.solvers() {
+        if let Err(failure) = conformance::check(solver) {
+            panic!("backend '{}' failed conformance: {failure}", solver.name());
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use entity::TriageLevel::{self, Orange, Red};
+use hospitopt_rs::optimize::{ParseSurvivalModelError, SurvivalCurve, SurvivalModel};
+
+fn curve(baseline: f64, midpoint_minutes: f64, scale_minutes: f64) -> SurvivalCurve {
+    SurvivalCurve {
+        baseline,
+        midpoint_minutes,
+        scale_minutes,
+    }
+}
+
+#[test]
+fn probability_never_rises_with_time() {
+    let model = SurvivalModel::default();
+
+    for level in TriageLevel::ALL {
+        let curve = model.curve(level);
+        assert!(
+            (curve.probability(0.0) - curve.baseline).abs() < 1e-12,
+            "{level:?}"
+        );
+
+        let mut previous = curve.probability(0.0);
+        for minute in 1..=24 * 60 {
+            let probability = curve.probability(f64::from(minute) * 60.0);
+            assert!(probability <= previous, "{level:?} rose at {minute} min");
+            assert!(probability > 0.0);
+            previous = probability;
+        }
+    }
+}
+
+#[test]
+fn negative_times_count_as_immediate_treatment() {
+    let curve = curve(0.9, 30.0, 10.0);
+
+    assert_eq!(curve.probability(-600.0), curve.probability(0.0));
+}
+
+#[test]
+fn probability_halves_at_the_midpoint() {
+    // Normalizing to `baseline` at zero lifts the midpoint by
+    // `e^(-midpoint/scale)`, which vanishes once the midpoint is a few
+    // scales out.
+    let steep = curve(1.0, 60.0, 5.0);
+    assert!((steep.probability(60.0 * 60.0) - 0.5).abs() < 1e-5);
+
+    let shallow = curve(0.8, 30.0, 10.0);
+    let expected = 0.8 * (1.0 + (-3.0f64).exp()) / 2.0;
+    assert!((shallow.probability(30.0 * 60.0) - expected).abs() < 1e-12);
+}
+
+#[test]
+fn parses_overrides_and_keeps_other_defaults() {
+    let model: SurvivalModel = " red=0.9/25/8 , Laranja=0.95/90/30,".parse().unwrap();
+    let defaults = SurvivalModel::default();
+
+    assert_eq!(*model.curve(Red), curve(0.9, 25.0, 8.0));
+    assert_eq!(*model.curve(Orange), curve(0.95, 90.0, 30.0));
+    for level in &TriageLevel::ALL[2..] {
+        assert_eq!(model.curve(*level), defaults.curve(*level));
+    }
+    assert_eq!("".parse::<SurvivalModel>().unwrap(), defaults);
+}
+
+#[test]
+fn rejects_malformed_entries() {
+    for entry in [
+        "red",
+        "purple=0.9/25/8",
+        "red=0.9/25",
+        "red=0.9/25/8/1",
+        "red=high/25/8",
+        "red=0/25/8",
+        "red=1.1/25/8",
+        "red=0.9/-1/8",
+        "red=0.9/25/0",
+    ] {
+        let input = format!("orange=0.95/90/30,{entry}");
+        assert_eq!(
+            input.parse::<SurvivalModel>(),
+            Err(ParseSurvivalModelError::Invalid(entry.to_string())),
+            "{entry}"
+        );
+    }
+}
+
+#[test]
+fn rejects_duplicate_levels() {
+    // Also when the level is spelled differently the second time.
+    let result = "red=0.9/25/8,Vermelho=0.8/20/5".parse::<SurvivalModel>();
+
+    assert_eq!(result, Err(ParseSurvivalModelError::Duplicate(Red)));
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "survival curve for 'Emergência (Vermelho)' given more than once"
+    );
+}
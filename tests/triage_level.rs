@@ -0,0 +1,74 @@
+use entity::{
+    Locale,
+    TriageLevel::{self, Blue, Green, Orange, Red, Yellow},
+};
+use sea_orm::ActiveEnum;
+
+#[test]
+fn display_and_labels_parse_back() {
+    for level in TriageLevel::ALL {
+        assert_eq!(level.to_string().parse::<TriageLevel>(), Ok(level));
+        for locale in [Locale::Portuguese, Locale::English] {
+            let label = level.display_name(locale);
+            assert_eq!(label.parse::<TriageLevel>(), Ok(level), "{label}");
+            assert_eq!(label.to_uppercase().parse::<TriageLevel>(), Ok(level));
+        }
+        assert_eq!(level.to_value().parse::<TriageLevel>(), Ok(level));
+    }
+}
+
+#[test]
+fn parses_colours_and_urgencies_ignoring_case_and_whitespace() {
+    assert_eq!(" Vermelho ".parse::<TriageLevel>(), Ok(Red));
+    assert_eq!("ORANGE".parse::<TriageLevel>(), Ok(Orange));
+    assert_eq!("urgente".parse::<TriageLevel>(), Ok(Yellow));
+    assert_eq!("Pouco urgente".parse::<TriageLevel>(), Ok(Green));
+    assert_eq!("não urgente".parse::<TriageLevel>(), Ok(Blue));
+}
+
+#[test]
+fn rejects_misspelled_and_mismatched_labels() {
+    for input in [
+        "",
+        "purple",
+        "Urgente (Vermelho)",
+        "Emergencia (Vermelho)",
+        "Urgente(Amarelo)",
+        "Muito Urgente Laranja",
+        "red-ish",
+    ] {
+        let err = input.parse::<TriageLevel>().unwrap_err();
+        assert!(err.to_string().contains(&format!("'{input}'")), "{err}");
+    }
+}
+
+#[test]
+fn json_only_accepts_database_values() {
+    // The API speaks database values; the free-text labels the column
+    // used to hold no longer deserialize.
+    assert_eq!(serde_json::to_string(&Yellow).unwrap(), r#""yellow""#);
+    assert_eq!(
+        serde_json::from_str::<TriageLevel>(r#""yellow""#).unwrap(),
+        Yellow
+    );
+    for label in TriageLevel::ALL.map(|level| level.display_name(Locale::Portuguese)) {
+        assert!(serde_json::from_str::<TriageLevel>(&format!("\"{label}\"")).is_err());
+    }
+}
+
+#[test]
+fn orders_by_urgency() {
+    let mut levels = vec![Green, Red, Blue, Yellow, Orange];
+    levels.sort();
+
+    assert_eq!(levels, TriageLevel::ALL);
+    assert!(Red < Orange && Yellow < Blue);
+}
+
+#[test]
+fn max_wait_follows_the_manchester_targets() {
+    let minutes = TriageLevel::ALL.map(|level| level.max_wait_seconds() / 60);
+
+    assert_eq!(minutes, [0, 10, 60, 120, 240]);
+    assert!(minutes.is_sorted());
+}